@@ -1,8 +1,8 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use std::path::PathBuf;
 use anyhow::Result;
 
-use crate::core::Engine;
+use crate::core::{Engine, GraphFilter, Granularity, GraphQueryRequest};
 
 #[derive(Parser)]
 #[command(name = "codesworth")]
@@ -60,6 +60,15 @@ pub enum Commands {
         fail_on_changes: bool,
     },
 
+    /// Show whether the stored analysis is current with the source
+    Status,
+
+    /// Explain a function using the stored analysis
+    Explain {
+        /// Function name (`method` or `Type::method`)
+        function: String,
+    },
+
     /// Validate documentation health
     Validate {
         /// Use strict validation rules
@@ -67,6 +76,12 @@ pub enum Commands {
         strict: bool,
     },
 
+    /// Work with the call graph
+    Graph {
+        #[command(subcommand)]
+        command: GraphCommands,
+    },
+
     /// Export for static sites
     Publish {
         /// Output format (hugo, jekyll, gitbook)
@@ -79,6 +94,145 @@ pub enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum GraphCommands {
+    /// Export the call graph (dot, mermaid, graphml, cytoscape)
+    Export {
+        /// Output format (dot, mermaid, graphml, cytoscape)
+        #[arg(long, default_value = "dot")]
+        format: String,
+
+        /// Output file (defaults to call_graph.<ext> in the docs directory)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Source directory to analyze
+        #[arg(short, long)]
+        source: Option<PathBuf>,
+
+        /// Only include these packages (repeatable)
+        #[arg(long)]
+        package: Vec<String>,
+
+        /// Only include functions reachable from this function
+        #[arg(long)]
+        entry: Option<String>,
+
+        /// Maximum call depth from the entry function
+        #[arg(long, requires = "entry")]
+        depth: Option<usize>,
+
+        /// Drop calls resolved with lower confidence (0.0 to 1.0)
+        #[arg(long, default_value_t = 0.0)]
+        min_confidence: f32,
+
+        /// Collapse nodes to this level (method, file, package)
+        #[arg(long, default_value = "method")]
+        collapse: String,
+    },
+
+    /// Show who calls a function
+    Callers {
+        /// Function name (`method` or `Type::method`)
+        function: String,
+
+        /// How many levels of callers to show
+        #[arg(long, default_value_t = 3)]
+        depth: usize,
+
+        #[command(flatten)]
+        query: QueryArgs,
+    },
+
+    /// Show what a function calls
+    Callees {
+        /// Function name (`method` or `Type::method`)
+        function: String,
+
+        /// How many levels of callees to show
+        #[arg(long, default_value_t = 3)]
+        depth: usize,
+
+        #[command(flatten)]
+        query: QueryArgs,
+    },
+
+    /// Find call paths from one function to another
+    Path {
+        /// Calling function
+        from: String,
+
+        /// Called function
+        to: String,
+
+        /// List all simple paths instead of the shortest one
+        #[arg(long)]
+        all: bool,
+
+        /// Maximum number of paths listed with --all
+        #[arg(long, default_value_t = 20)]
+        max_paths: usize,
+
+        #[command(flatten)]
+        query: QueryArgs,
+    },
+
+    /// List every function reachable from a function
+    ReachableFrom {
+        /// Function name (`method` or `Type::method`)
+        function: String,
+
+        /// Maximum call depth (unlimited by default)
+        #[arg(long)]
+        depth: Option<usize>,
+
+        #[command(flatten)]
+        query: QueryArgs,
+    },
+
+    /// List the entry points that can reach a function ("what breaks if I change this?")
+    Impact {
+        /// Function name (`method` or `Type::method`)
+        function: String,
+
+        #[command(flatten)]
+        query: QueryArgs,
+    },
+
+    /// Compare the call graphs of two git revisions (markdown for a PR description, or JSON)
+    Diff {
+        /// Base revision (branch, tag or commit)
+        base: String,
+
+        /// Head revision (branch, tag or commit)
+        head: String,
+
+        /// Source directory to analyze, relative to the current directory
+        #[arg(short, long)]
+        source: Option<PathBuf>,
+
+        /// Print JSON instead of markdown
+        #[arg(long)]
+        json: bool,
+
+        /// Write the report to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Options shared by the graph query commands
+#[derive(Args)]
+pub struct QueryArgs {
+    /// Source directory to analyze
+    #[arg(short, long)]
+    source: Option<PathBuf>,
+
+    /// Print JSON instead of a tree
+    #[arg(long)]
+    json: bool,
+}
+
 impl Cli {
     pub async fn execute(self, mut engine: Engine) -> Result<()> {
         match self.command {
@@ -91,9 +245,45 @@ impl Cli {
             Commands::Sync { dry_run, fail_on_changes } => {
                 engine.sync(dry_run, fail_on_changes).await
             }
+            Commands::Status => {
+                engine.status().await
+            }
+            Commands::Explain { function } => {
+                engine.explain(&function).await
+            }
             Commands::Validate { strict } => {
                 engine.validate(strict).await
             }
+            Commands::Graph { command } => match command {
+                GraphCommands::Export { format, output, source, package, entry, depth, min_confidence, collapse } => {
+                    let filter = GraphFilter {
+                        packages: package,
+                        entry,
+                        max_depth: depth,
+                        min_confidence,
+                        granularity: Granularity::parse(&collapse)?,
+                    };
+                    engine.export_graph(source, &format, output, filter).await
+                }
+                GraphCommands::Callers { function, depth, query } => {
+                    engine.query_graph(query.source, GraphQueryRequest::Callers { function, depth }, query.json).await
+                }
+                GraphCommands::Callees { function, depth, query } => {
+                    engine.query_graph(query.source, GraphQueryRequest::Callees { function, depth }, query.json).await
+                }
+                GraphCommands::Path { from, to, all, max_paths, query } => {
+                    engine.query_graph(query.source, GraphQueryRequest::Path { from, to, all, max_paths }, query.json).await
+                }
+                GraphCommands::ReachableFrom { function, depth, query } => {
+                    engine.query_graph(query.source, GraphQueryRequest::ReachableFrom { function, depth }, query.json).await
+                }
+                GraphCommands::Impact { function, query } => {
+                    engine.query_graph(query.source, GraphQueryRequest::Impact { function }, query.json).await
+                }
+                GraphCommands::Diff { base, head, source, json, output } => {
+                    engine.diff_graph(&base, &head, source, json, output).await
+                }
+            },
             Commands::Publish { format, output } => {
                 engine.publish(&format, output).await
             }
@@ -26,6 +26,10 @@ pub struct LlmConfig {
 
     /// Temperature for LLM responses (0.0 to 1.0)
     pub temperature: Option<f32>,
+
+    /// Maximum number of LLM calls per run, spent on the most central code first (unlimited when unset)
+    #[serde(default)]
+    pub budget: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +51,10 @@ pub struct Config {
 
     /// LLM integration settings
     pub llm: LlmConfig,
+
+    /// Call-chain analysis limits
+    #[serde(default)]
+    pub analysis: AnalysisConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -91,6 +99,45 @@ pub struct GenerationConfig {
     pub hash_algorithm: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnalysisConfig {
+    /// Maximum call depth to trace from an entry point
+    pub max_call_depth: usize,
+
+    /// Maximum number of callees followed from a single method
+    pub max_fan_out: usize,
+
+    /// Maximum number of distinct paths traced from a single entry point
+    pub max_paths: usize,
+
+    /// Stop tracing when a call returns to a method already on the current path
+    pub stop_at_cycles: bool,
+
+    /// Minimum weighted similarity (0.0 to 1.0) of files and methods for a call chain to join a group
+    pub group_similarity: f64,
+
+    /// Maximum number of call chains in one group
+    pub max_group_size: usize,
+
+    /// Maximum number of calls drawn in one sequence diagram; deeper calls are collapsed beyond it
+    pub max_sequence_messages: usize,
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        Self {
+            max_call_depth: 6,
+            max_fan_out: 12,
+            max_paths: 200,
+            stop_at_cycles: true,
+            group_similarity: 0.5,
+            max_group_size: 12,
+            max_sequence_messages: 40,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TemplateConfig {
     /// Custom template directory
@@ -166,7 +213,9 @@ impl Default for Config {
                 base_url: None,
                 max_tokens: Some(2000),
                 temperature: Some(0.3),
+                budget: None,
             },
+            analysis: AnalysisConfig::default(),
         }
     }
 }
@@ -0,0 +1,214 @@
+// src/core/call_graph/analysis_store.rs
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+
+use crate::error::{CodesworthError, Result};
+use super::{
+    CallChainAnalysisResult, CallChain, CallChainGroup, CallGraph, EntryPoint, GroupAnalysis, SideEffects,
+    FailureModes, Concurrency, SystemSynthesis,
+};
+
+/// Version of the store layout. Bump it and add a migration whenever the layout changes.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Where the store lives, relative to the documentation directory
+const STORE_PATH: &str = ".codesworth/analysis.json";
+
+/// Upgrades of a stored layout, one version at a time: `MIGRATIONS[i]` turns version `i + 1` into `i + 2`
+const MIGRATIONS: &[fn(&mut Value)] = &[source_dir_to_list];
+
+/// Everything `generate` worked out, kept so later commands and runs need not redo it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisStore {
+    pub schema_version: u32,
+    /// When the analysis ran (RFC 3339)
+    pub generated_at: String,
+    /// Codesworth version that wrote the store
+    pub generator_version: String,
+    /// Source directories that were analyzed
+    pub source_dirs: Vec<PathBuf>,
+    /// Content hash of every analyzed file, to tell whether the store is still current
+    pub file_hashes: BTreeMap<PathBuf, String>,
+    pub call_graph: CallGraph,
+    pub entry_points: Vec<EntryPoint>,
+    pub call_chains: Vec<CallChain>,
+    pub call_chain_groups: Vec<CallChainGroup>,
+    pub group_analyses: Vec<GroupAnalysis>,
+    pub system_synthesis: SystemSynthesis,
+    #[serde(default)]
+    pub side_effects: SideEffects,
+    #[serde(default)]
+    pub failure_modes: FailureModes,
+    #[serde(default)]
+    pub concurrency: Concurrency,
+}
+
+/// Source files that differ from the ones a store was built from
+#[derive(Debug, Default)]
+pub struct FileChanges {
+    pub added: Vec<PathBuf>,
+    pub modified: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+}
+
+impl AnalysisStore {
+    pub fn path(docs_dir: &Path) -> PathBuf {
+        docs_dir.join(STORE_PATH)
+    }
+
+    pub fn from_result(result: &CallChainAnalysisResult) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            generator_version: env!("CARGO_PKG_VERSION").to_string(),
+            source_dirs: result.source_dirs.clone(),
+            file_hashes: result.file_hashes.clone(),
+            call_graph: result.call_graph.clone(),
+            entry_points: result.entry_points.clone(),
+            call_chains: result.call_chains.clone(),
+            call_chain_groups: result.call_chain_groups.clone(),
+            group_analyses: result.group_analyses.clone(),
+            system_synthesis: result.system_synthesis.clone(),
+            side_effects: result.side_effects.clone(),
+            failure_modes: result.failure_modes.clone(),
+            concurrency: result.concurrency.clone(),
+        }
+    }
+
+    /// Write the store, replacing any earlier one in a single rename
+    pub fn save(&self, docs_dir: &Path) -> Result<()> {
+        let path = Self::path(docs_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, serde_json::to_string(self)?)?;
+        std::fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+
+    /// Read the store, migrating older layouts; `None` when there is none yet
+    pub fn load(docs_dir: &Path) -> Result<Option<Self>> {
+        let path = Self::path(docs_dir);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let value = migrate(serde_json::from_str(&content)?)?;
+        let mut store: Self = serde_json::from_value(value)?;
+        store.call_graph.build_adjacency_lists();
+        Ok(Some(store))
+    }
+
+    /// How the given files differ from the ones this store was built from
+    pub fn changes_since(&self, file_hashes: &BTreeMap<PathBuf, String>) -> FileChanges {
+        let mut changes = FileChanges::default();
+        for (path, hash) in file_hashes {
+            match self.file_hashes.get(path) {
+                None => changes.added.push(path.clone()),
+                Some(stored) if stored != hash => changes.modified.push(path.clone()),
+                Some(_) => {}
+            }
+        }
+        changes.removed = self.file_hashes.keys()
+            .filter(|path| !file_hashes.contains_key(*path))
+            .cloned()
+            .collect();
+        changes
+    }
+}
+
+impl FileChanges {
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    pub fn total(&self) -> usize {
+        self.added.len() + self.modified.len() + self.removed.len()
+    }
+}
+
+/// Bring a stored layout up to the current schema version
+fn migrate(mut value: Value) -> Result<Value> {
+    let version = value.get("schema_version")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| CodesworthError::Store("missing schema_version".to_string()))? as u32;
+
+    if version == 0 || version > SCHEMA_VERSION {
+        return Err(CodesworthError::Store(format!(
+            "schema version {} is not supported by this Codesworth (which writes version {}); run `codesworth generate` to rebuild it",
+            version, SCHEMA_VERSION
+        )));
+    }
+
+    for migration in &MIGRATIONS[(version - 1) as usize..] {
+        migration(&mut value);
+    }
+    value["schema_version"] = Value::from(SCHEMA_VERSION);
+    Ok(value)
+}
+
+/// Version 1 recorded the one analyzed directory as `source_dir`
+fn source_dir_to_list(value: &mut Value) {
+    if let Some(source_dir) = value.as_object_mut().and_then(|store| store.remove("source_dir")) {
+        value["source_dirs"] = Value::Array(vec![source_dir]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_unknown_schema_versions() {
+        let current = serde_json::json!({ "schema_version": SCHEMA_VERSION });
+        assert_eq!(migrate(current).unwrap()["schema_version"], SCHEMA_VERSION);
+
+        assert!(migrate(serde_json::json!({ "schema_version": SCHEMA_VERSION + 1 })).is_err());
+        assert!(migrate(serde_json::json!({ "nodes": [] })).is_err());
+
+        let version_1 = migrate(serde_json::json!({ "schema_version": 1, "source_dir": "src" })).unwrap();
+        assert_eq!(version_1, serde_json::json!({ "schema_version": SCHEMA_VERSION, "source_dirs": ["src"] }));
+    }
+
+    #[test]
+    fn test_reports_file_changes() {
+        let hashes = |entries: &[(&str, &str)]| -> BTreeMap<PathBuf, String> {
+            entries.iter().map(|(path, hash)| (PathBuf::from(path), hash.to_string())).collect()
+        };
+        let store = AnalysisStore {
+            schema_version: SCHEMA_VERSION,
+            generated_at: String::new(),
+            generator_version: String::new(),
+            source_dirs: vec![PathBuf::from("src")],
+            file_hashes: hashes(&[("src/a.rs", "1"), ("src/b.rs", "2"), ("src/c.rs", "3")]),
+            call_graph: CallGraph::new(),
+            entry_points: vec![],
+            call_chains: vec![],
+            call_chain_groups: vec![],
+            group_analyses: vec![],
+            system_synthesis: SystemSynthesis {
+                overall_description: String::new(),
+                key_themes: vec![],
+                theme_sources: BTreeMap::new(),
+                critical_gotchas: vec![],
+                package_summaries: vec![],
+                total_groups_analyzed: 0,
+                overall_confidence: 0.0,
+            },
+            side_effects: SideEffects::default(),
+            failure_modes: FailureModes::default(),
+            concurrency: Concurrency::default(),
+        };
+
+        let changes = store.changes_since(&hashes(&[("src/a.rs", "1"), ("src/b.rs", "9"), ("src/d.rs", "4")]));
+        assert_eq!(changes.added, vec![PathBuf::from("src/d.rs")]);
+        assert_eq!(changes.modified, vec![PathBuf::from("src/b.rs")]);
+        assert_eq!(changes.removed, vec![PathBuf::from("src/c.rs")]);
+        assert!(store.changes_since(&store.file_hashes).is_empty());
+    }
+}
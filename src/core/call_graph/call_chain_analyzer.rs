@@ -1,17 +1,19 @@
 // src/core/call_graph/call_chain_analyzer.rs
-use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use serde::{Serialize, Deserialize};
+use tracing::warn;
 
 use crate::error::Result;
-use super::{CallChainGroup, MethodSignature};
-use super::super::{LlmDocumenter, EnhancementRequest, EnhancementType, DocumentationContext, ParsedFile, ProjectInfo};
+use super::{CallChainGroup, MethodSignature, SideEffects, describe_effects};
+use super::synthesis::{dedup_claims, json_object, rank_gotchas, Reducer, Summary};
+use super::super::{LlmDocumenter, EnhancementRequest, EnhancementType, DocumentationContext, ParsedFile, ParsedModule, ProjectInfo};
 
 /// Analyzes call chain groups using LLM and maintains visited sets for efficiency
 pub struct CallChainAnalyzer {
-    /// Tracks which source files have been sent to LLM
+    /// Tracks which files and methods have been sent to LLM
     visited_files: VisitedSet,
-    /// Cache of previous group analyses
+    /// Previous group analyses, by the hash of their inputs
     analysis_cache: HashMap<String, GroupAnalysis>,
     /// Maximum context size per LLM call
     max_context_size: usize,
@@ -24,6 +26,7 @@ pub struct GroupAnalysis {
     /// LLM-generated description of what these call chains accomplish
     pub description: String,
     /// Purpose of each entry point
+    #[serde(with = "super::call_graph::signature_map")]
     pub entry_point_descriptions: HashMap<MethodSignature, String>,
     /// Component interactions discovered
     pub component_interactions: Vec<ComponentInteraction>,
@@ -35,6 +38,10 @@ pub struct GroupAnalysis {
     pub confidence: f32,
     /// Whether this analysis used incremental context
     pub is_incremental: bool,
+    /// Hash of the group's methods and file contents the analysis was made from;
+    /// empty when the LLM analysis failed
+    #[serde(default)]
+    pub input_hash: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,7 +67,8 @@ pub struct Gotcha {
     pub suggested_action: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Ordered from least to most severe
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum GotchaSeverity {
     Info,
     Warning,
@@ -71,12 +79,37 @@ pub enum GotchaSeverity {
 pub struct SystemSynthesis {
     pub overall_description: String,
     pub key_themes: Vec<String>,
-    pub critical_gotchas: Vec<Gotcha>,
+    /// Groups each key theme was drawn from
+    #[serde(default)]
+    pub theme_sources: BTreeMap<String, Vec<String>>,
+    /// Gotchas from every analyzed group, most severe first
+    pub critical_gotchas: Vec<RankedGotcha>,
+    /// What each package's groups do together
+    #[serde(default)]
+    pub package_summaries: Vec<PackageSummary>,
     pub total_groups_analyzed: usize,
     pub overall_confidence: f32,
 }
 
-/// Tracks which source files have been sent to LLM to avoid redundant analysis
+/// A gotcha reported by one or more groups
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankedGotcha {
+    #[serde(flatten)]
+    pub gotcha: Gotcha,
+    /// Groups whose analysis reported it
+    pub source_groups: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageSummary {
+    pub package: String,
+    pub summary: String,
+    pub themes: Vec<String>,
+    /// Groups the summary was drawn from
+    pub source_groups: Vec<String>,
+}
+
+/// Tracks which source files and methods have been sent to LLM to avoid redundant analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VisitedSet {
     /// Files that have been fully analyzed
@@ -85,6 +118,62 @@ pub struct VisitedSet {
     partially_analyzed_files: HashMap<PathBuf, String>,
     /// Cache of file summaries to use in incremental analysis
     file_summaries: HashMap<PathBuf, String>,
+    /// Methods whose source has been sent
+    #[serde(default)]
+    sent_methods: HashSet<MethodSignature>,
+}
+
+/// Rough size of a token, used to turn the context limit into characters
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Share of the context budget that the call paths and source excerpts may use;
+/// the rest is left for the instructions and the response
+const SOURCE_BUDGET_SHARE: f64 = 0.75;
+
+/// Confidence of an analysis that fell back to the deterministic description
+const FALLBACK_CONFIDENCE: f32 = 0.3;
+
+/// The structured answer requested from the LLM for a group
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct GroupAnalysisResponse {
+    description: String,
+    entry_points: Vec<EntryPointResponse>,
+    component_interactions: Vec<ComponentInteraction>,
+    domain_insights: Vec<DomainInsight>,
+    gotchas: Vec<GotchaResponse>,
+    file_summaries: HashMap<String, String>,
+    confidence: Option<f32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct EntryPointResponse {
+    name: String,
+    description: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct GotchaResponse {
+    category: String,
+    description: String,
+    severity: String,
+    suggested_action: Option<String>,
+}
+
+/// The source sent for one file of a group
+struct FileContext {
+    path: PathBuf,
+    /// Excerpts of the group's methods, or the summary of an already analyzed file
+    /// followed by excerpts of the methods not sent before
+    content: String,
+    /// Whether `content` starts with a summary from the visited set rather than source
+    from_summary: bool,
+    /// Names of the group's methods in this file
+    methods: Vec<String>,
+    /// Methods whose source is in `content`
+    sent: Vec<MethodSignature>,
 }
 
 impl CallChainAnalyzer {
@@ -97,19 +186,101 @@ impl CallChainAnalyzer {
     }
 
     /// Analyze a group of call chains using LLM
+    ///
+    /// Falls back to a deterministic description when the LLM call fails or
+    /// its answer cannot be parsed.
     pub async fn analyze_group(
         &mut self,
         group: &CallChainGroup,
         all_files: &[ParsedFile],
+        side_effects: &SideEffects,
         llm_documenter: &dyn LlmDocumenter,
     ) -> Result<GroupAnalysis> {
         // Check cache first
-        if let Some(cached) = self.analysis_cache.get(&group.group_id) {
-            return Ok(cached.clone());
+        if let Some(cached) = self.cached_analysis(group, all_files) {
+            return Ok(cached);
         }
+        let input_hash = group_input_hash(group, all_files);
+
+        let paths = execution_paths(group);
+        let file_contexts = self.collect_file_contexts(group, all_files, paths.len());
+        let prompt = self.build_group_prompt(group, &paths, &file_contexts, side_effects);
+
+        let Some(context_file) = group.call_chains.first()
+            .and_then(|chain| all_files.iter().find(|file| file.path == chain.entry_point.signature.file_path)) else {
+            return Ok(Self::fallback_analysis(group));
+        };
+
+        let request = EnhancementRequest {
+            enhancement_type: EnhancementType::Custom(prompt),
+            context: DocumentationContext {
+                file: context_file.clone(),
+                target_module: None,
+                related_files: vec![],
+                project_info: ProjectInfo {
+                    name: group.name.clone(),
+                    description: None,
+                    language: context_file.language.clone(),
+                    project_type: None,
+                },
+                architecture_docs: None,
+            },
+            current_content: None,
+            focus_areas: vec!["call_chain_group".to_string()],
+        };
+
+        let response = match llm_documenter.enhance_documentation(request).await {
+            Ok(response) => response,
+            Err(e) => {
+                warn!("LLM analysis of group {} failed: {}", group.group_id, e);
+                return Ok(Self::fallback_analysis(group));
+            }
+        };
+
+        let Some(parsed) = parse_group_response(&response.content) else {
+            warn!("Could not parse the LLM analysis of group {}; using a structural description", group.group_id);
+            return Ok(Self::fallback_analysis(group));
+        };
 
-        // Simple analysis for now
         let analysis = GroupAnalysis {
+            group_id: group.group_id.clone(),
+            description: parsed.description,
+            entry_point_descriptions: match_entry_points(group, parsed.entry_points),
+            component_interactions: parsed.component_interactions,
+            domain_insights: parsed.domain_insights,
+            gotchas: parsed.gotchas.into_iter().map(|gotcha| Gotcha {
+                category: gotcha.category,
+                description: gotcha.description,
+                severity: GotchaSeverity::parse(&gotcha.severity),
+                suggested_action: gotcha.suggested_action.filter(|action| !action.trim().is_empty()),
+            }).collect(),
+            confidence: parsed.confidence.or(response.confidence).unwrap_or(0.7).clamp(0.0, 1.0),
+            is_incremental: file_contexts.iter().any(|context| context.from_summary),
+            input_hash: input_hash.clone(),
+        };
+
+        // Later groups get summaries of these files instead of their source
+        for context in &file_contexts {
+            self.visited_files.mark_sent(context.sent.iter().cloned());
+            if context.from_summary {
+                continue;
+            }
+            let summary = parsed.file_summaries.iter()
+                .find(|(path, _)| context.path.ends_with(path.as_str()))
+                .map(|(_, summary)| summary.clone())
+                .unwrap_or_else(|| format!("Defines {}", context.methods.join(", ")));
+            self.visited_files.mark_analyzed(context.path.clone(), summary);
+        }
+
+        // Cache the result
+        self.analysis_cache.insert(input_hash, analysis.clone());
+
+        Ok(analysis)
+    }
+
+    /// The structural description used when no LLM analysis is available
+    fn fallback_analysis(group: &CallChainGroup) -> GroupAnalysis {
+        GroupAnalysis {
             group_id: group.group_id.clone(),
             description: format!("Call chain group with {} chains involving {} files",
                                  group.call_chains.len(), group.involved_files.len()),
@@ -117,31 +288,214 @@ impl CallChainAnalyzer {
             component_interactions: Vec::new(),
             domain_insights: Vec::new(),
             gotchas: Vec::new(),
-            confidence: 0.7,
+            confidence: FALLBACK_CONFIDENCE,
             is_incremental: false,
-        };
+            input_hash: String::new(),
+        }
+    }
 
-        // Cache the result
-        self.analysis_cache.insert(group.group_id.clone(), analysis.clone());
+    /// A previous analysis of the same methods and file contents, if there is one
+    pub fn cached_analysis(&self, group: &CallChainGroup, all_files: &[ParsedFile]) -> Option<GroupAnalysis> {
+        let mut cached = self.analysis_cache.get(&group_input_hash(group, all_files))?.clone();
+        cached.group_id = group.group_id.clone();
+        Some(cached)
+    }
 
-        Ok(analysis)
+    /// Offer analyses from an earlier run for reuse where their inputs are unchanged
+    pub fn reuse_analyses(&mut self, analyses: impl IntoIterator<Item = GroupAnalysis>) {
+        for analysis in analyses {
+            if !analysis.input_hash.is_empty() {
+                self.analysis_cache.insert(analysis.input_hash.clone(), analysis);
+            }
+        }
+    }
+
+    /// Source excerpts of the group's methods, file by file in call order, until
+    /// what is left of the source budget after `reserved` characters is spent
+    fn collect_file_contexts(&self, group: &CallChainGroup, all_files: &[ParsedFile], reserved: usize) -> Vec<FileContext> {
+        let mut file_order: Vec<&PathBuf> = Vec::new();
+        for step in group.call_chains.iter().flat_map(|chain| &chain.steps) {
+            if !file_order.contains(&&step.method.file_path) {
+                file_order.push(&step.method.file_path);
+            }
+        }
+
+        let mut budget = ((self.max_context_size as f64 * CHARS_PER_TOKEN as f64 * SOURCE_BUDGET_SHARE) as usize)
+            .saturating_sub(reserved);
+        let mut contexts = Vec::new();
+
+        for path in file_order {
+            let mut methods: Vec<&MethodSignature> = group.all_methods.iter()
+                .filter(|method| &method.file_path == path)
+                .collect();
+            methods.sort_by_key(|method| method.to_unique_string());
+            let method_names: Vec<String> = methods.iter().map(|method| method.display_name()).collect();
+
+            let file = all_files.iter().find(|file| &file.path == path);
+            if let Some(summary) = self.visited_files.summary_of(path) {
+                // Methods of the file that no earlier group sent still need their source
+                let unsent: Vec<&MethodSignature> = methods.iter().copied()
+                    .filter(|method| !self.visited_files.was_sent(method))
+                    .collect();
+                let excerpt = file.map(|file| method_excerpts(file, &unsent)).unwrap_or_default();
+                let mut content = summary.to_string();
+                let mut sent = Vec::new();
+                if !excerpt.is_empty() && content.len() + 1 + excerpt.len() <= budget {
+                    content.push('\n');
+                    content.push_str(&excerpt);
+                    sent = unsent.into_iter().cloned().collect();
+                }
+                budget = budget.saturating_sub(content.len());
+                contexts.push(FileContext {
+                    path: path.clone(),
+                    content,
+                    from_summary: true,
+                    methods: method_names,
+                    sent,
+                });
+                continue;
+            }
+
+            let Some(file) = file else {
+                continue;
+            };
+            let excerpt = method_excerpts(file, &methods);
+            if excerpt.is_empty() || excerpt.len() > budget {
+                continue;
+            }
+            budget -= excerpt.len();
+            contexts.push(FileContext {
+                path: path.clone(),
+                content: excerpt,
+                from_summary: false,
+                methods: method_names,
+                sent: methods.into_iter().cloned().collect(),
+            });
+        }
+
+        contexts
     }
 
-    /// Generate overall system synthesis from all group analyses
-    pub fn synthesize_system_understanding(&self, analyses: &[GroupAnalysis]) -> Result<SystemSynthesis> {
-        let overall_description = if analyses.is_empty() {
-            "System analyzed using call-chain analysis".to_string()
+    fn build_group_prompt(&self, group: &CallChainGroup, paths: &str, file_contexts: &[FileContext], side_effects: &SideEffects) -> String {
+        let mut prompt = String::new();
+
+        prompt.push_str(&format!(
+            "Explain what the '{}' group of execution paths does. Each path starts at an entry point \
+            and follows the calls it makes.\n\n",
+            group.name
+        ));
+
+        prompt.push_str(paths);
+        prompt.push('\n');
+
+        let sites = side_effects.sites_in_group(group);
+        if !sites.is_empty() {
+            prompt.push_str(&format!("SIDE EFFECTS (found from library calls; these paths {}):\n",
+                                     describe_effects(&side_effects.effects_of_group(group))));
+            for (method, site) in sites {
+                prompt.push_str(&format!("- {} {} via {} (line {})\n",
+                                         method.display_name(), site.effect.describe(), site.call, site.line));
+            }
+            prompt.push('\n');
+        }
+
+        prompt.push_str("SOURCE:\n");
+        for context in file_contexts {
+            if context.from_summary {
+                let shown = if context.sent.is_empty() { "summary only" } else { "summary, then methods not shown before" };
+                prompt.push_str(&format!(
+                    "--- {} (analyzed earlier; {}, involved methods: {}) ---\n{}\n",
+                    context.path.display(), shown, context.methods.join(", "), context.content
+                ));
+            } else {
+                prompt.push_str(&format!("--- {} ---\n{}\n", context.path.display(), context.content));
+            }
+        }
+        let omitted = group.involved_files.len().saturating_sub(file_contexts.len());
+        if omitted > 0 {
+            prompt.push_str(&format!("({} more file(s) omitted to fit the context limit)\n", omitted));
+        }
+        prompt.push('\n');
+
+        prompt.push_str(
+            "Respond in JSON format only:\n\
+            {\n\
+              \"description\": \"What these paths accomplish and why, in 2-4 sentences\",\n\
+              \"entry_points\": [{\"name\": \"Entry point name as listed above\", \"description\": \"Its purpose\"}],\n\
+              \"component_interactions\": [{\"from_component\": \"Caller\", \"to_component\": \"Callee\", \"interaction_type\": \"call, event, query, ...\", \"description\": \"What passes between them\"}],\n\
+              \"domain_insights\": [{\"category\": \"Business rule, data flow, ...\", \"insight\": \"The insight\", \"evidence\": [\"Method or line supporting it\"]}],\n\
+              \"gotchas\": [{\"category\": \"Error handling, concurrency, ...\", \"description\": \"The pitfall\", \"severity\": \"info | warning | critical\", \"suggested_action\": \"How to avoid it\"}],\n\
+              \"file_summaries\": {\"path as listed above\": \"One sentence on the file's role\"},\n\
+              \"confidence\": 0.0\n\
+            }\n"
+        );
+
+        prompt
+    }
+
+    /// Generate overall system synthesis from all group analyses: each package's
+    /// groups are summarised first, then the package summaries system-wide.
+    /// Returns the synthesis and the number of LLM calls it made.
+    pub async fn synthesize_system_understanding(
+        &self,
+        groups: &[CallChainGroup],
+        analyses: &[GroupAnalysis],
+        llm_documenter: &dyn LlmDocumenter,
+        call_budget: Option<usize>,
+    ) -> Result<(SystemSynthesis, usize)> {
+        let mut by_package: BTreeMap<String, Vec<Summary>> = BTreeMap::new();
+        for analysis in analyses {
+            let Some(group) = groups.iter().find(|group| group.group_id == analysis.group_id) else {
+                continue;
+            };
+            let package = group.call_chains.first()
+                .map(|chain| chain.entry_point.signature.package_name())
+                .unwrap_or_else(|| "root".to_string());
+            by_package.entry(package).or_default().push(Summary::from_group(group, analysis));
+        }
+
+        let mut reducer = Reducer::new(llm_documenter, self.max_context_size, call_budget);
+
+        // Map: one summary per package
+        let mut package_summaries = Vec::new();
+        for (package, summaries) in by_package {
+            let subject = format!("the '{}' package", package);
+            package_summaries.push(reducer.reduce(&package, &subject, summaries).await);
+        }
+
+        // Reduce: one summary for the system, from the package summaries
+        let packages: Vec<PackageSummary> = package_summaries.iter().map(|summary| PackageSummary {
+            package: summary.label.clone(),
+            summary: summary.text.clone(),
+            themes: summary.themes.iter().map(|theme| theme.text.clone()).collect(),
+            source_groups: summary.sources.iter().cloned().collect(),
+        }).collect();
+        let system = if package_summaries.len() == 1 {
+            reducer.reduce_batch("system", "the whole system", package_summaries).await
         } else {
-            format!("System with {} call chain groups analyzed", analyses.len())
+            reducer.reduce("system", "the whole system", package_summaries).await
         };
 
-        Ok(SystemSynthesis {
-            overall_description,
-            key_themes: vec!["Call Chain Analysis".to_string()],
-            critical_gotchas: Vec::new(),
+        let themes = dedup_claims(system.themes);
+        let overall_confidence = if analyses.is_empty() {
+            0.5
+        } else {
+            analyses.iter().map(|analysis| analysis.confidence).sum::<f32>() / analyses.len() as f32
+        };
+
+        let synthesis = SystemSynthesis {
+            overall_description: system.text,
+            key_themes: themes.iter().map(|theme| theme.text.clone()).collect(),
+            theme_sources: themes.into_iter()
+                .map(|theme| (theme.text, theme.sources.into_iter().collect()))
+                .collect(),
+            critical_gotchas: rank_gotchas(analyses),
+            package_summaries: packages,
             total_groups_analyzed: analyses.len(),
-            overall_confidence: 0.7,
-        })
+            overall_confidence,
+        };
+
+        Ok((synthesis, reducer.calls_made))
     }
 }
 
@@ -151,12 +505,272 @@ impl VisitedSet {
             fully_analyzed_files: HashSet::new(),
             partially_analyzed_files: HashMap::new(),
             file_summaries: HashMap::new(),
+            sent_methods: HashSet::new(),
+        }
+    }
+
+    /// Record that a file's source has been sent, with a summary to send in its place from now on
+    pub fn mark_analyzed(&mut self, path: PathBuf, summary: String) {
+        self.partially_analyzed_files.remove(&path);
+        self.file_summaries.insert(path.clone(), summary);
+        self.fully_analyzed_files.insert(path);
+    }
+
+    /// Record that the source of these methods has been sent
+    pub fn mark_sent(&mut self, methods: impl IntoIterator<Item = MethodSignature>) {
+        self.sent_methods.extend(methods);
+    }
+
+    /// Whether the source of a method has already been sent
+    pub fn was_sent(&self, method: &MethodSignature) -> bool {
+        self.sent_methods.contains(method)
+    }
+
+    /// Summary of a file whose source has already been sent
+    pub fn summary_of(&self, path: &Path) -> Option<&str> {
+        if !self.fully_analyzed_files.contains(path) {
+            return None;
         }
+        self.file_summaries.get(path).map(String::as_str)
     }
 }
 
+impl Default for VisitedSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GotchaSeverity {
+    fn parse(severity: &str) -> Self {
+        match severity.trim().to_lowercase().as_str() {
+            "critical" | "high" => GotchaSeverity::Critical,
+            "warning" | "medium" => GotchaSeverity::Warning,
+            _ => GotchaSeverity::Info,
+        }
+    }
+}
+
+/// The entry points of a group and the calls each one makes, as listed in the prompt
+fn execution_paths(group: &CallChainGroup) -> String {
+    let mut paths = String::from("EXECUTION PATHS:\n");
+    for chain in &group.call_chains {
+        paths.push_str(&format!("Entry point: {} ({:?})\n",
+                                chain.entry_point.signature.display_name(), chain.entry_point.entry_type));
+        for step in &chain.steps {
+            paths.push_str(&format!("{}- {}\n", "  ".repeat(step.depth), step.method.display_name()));
+        }
+    }
+    paths
+}
+
+/// Hash of everything a group's analysis depends on: its methods and the contents of their files
+fn group_input_hash(group: &CallChainGroup, all_files: &[ParsedFile]) -> String {
+    use sha2::{Sha256, Digest};
+
+    let mut methods: Vec<String> = group.all_methods.iter()
+        .map(|method| format!("{}#{}", method.file_path.display(), method.to_unique_string()))
+        .collect();
+    methods.sort();
+
+    let mut files: Vec<(&PathBuf, &str)> = all_files.iter()
+        .filter(|file| group.involved_files.contains(&file.path))
+        .map(|file| (&file.path, file.content_hash.as_str()))
+        .collect();
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for method in &methods {
+        hasher.update(method.as_bytes());
+        hasher.update(b"\n");
+    }
+    for (path, content_hash) in files {
+        hasher.update(path.to_string_lossy().as_bytes());
+        hasher.update(content_hash.as_bytes());
+        hasher.update(b"\n");
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// The JSON object in an LLM answer, which may be wrapped in a code fence or prose
+fn parse_group_response(content: &str) -> Option<GroupAnalysisResponse> {
+    serde_json::from_str::<GroupAnalysisResponse>(json_object(content)?).ok()
+        .filter(|response| !response.description.trim().is_empty())
+}
+
+/// Attach the LLM's entry point descriptions to the group's entry points by name
+fn match_entry_points(group: &CallChainGroup, entry_points: Vec<EntryPointResponse>) -> HashMap<MethodSignature, String> {
+    let mut descriptions = HashMap::new();
+    for entry_point in entry_points {
+        let name = entry_point.name.trim();
+        let matched = group.call_chains.iter()
+            .map(|chain| &chain.entry_point.signature)
+            .find(|signature| signature.display_name() == name)
+            .or_else(|| group.call_chains.iter()
+                .map(|chain| &chain.entry_point.signature)
+                .find(|signature| signature.method_name == name || name.ends_with(&format!("::{}", signature.method_name))));
+        if let Some(signature) = matched {
+            if !entry_point.description.trim().is_empty() {
+                descriptions.insert(signature.clone(), entry_point.description);
+            }
+        }
+    }
+    descriptions
+}
+
+/// Source lines of the given methods, in file order
+fn method_excerpts(file: &ParsedFile, methods: &[&MethodSignature]) -> String {
+    let lines: Vec<&str> = file.source_content.lines().collect();
+    let mut ranges: Vec<(usize, usize)> = methods.iter()
+        .filter_map(|method| find_line_range(&file.modules, method))
+        .collect();
+    ranges.sort();
+    ranges.dedup();
+
+    let mut excerpt = String::new();
+    for (start, end) in ranges {
+        let start = start.max(1);
+        let end = end.min(lines.len());
+        if start > end {
+            continue;
+        }
+        excerpt.push_str(&format!("// lines {}-{}\n", start, end));
+        for line in &lines[start - 1..end] {
+            excerpt.push_str(line);
+            excerpt.push('\n');
+        }
+    }
+    excerpt
+}
+
+/// Line range of the parsed function a method signature was built from
+fn find_line_range(modules: &[ParsedModule], method: &MethodSignature) -> Option<(usize, usize)> {
+    modules.iter().find_map(|module| {
+        let is_function = matches!(module.item_type.as_str(), "function" | "method");
+        if is_function && module.name == method.method_name
+            && module.signature.as_deref().unwrap_or_default() == method.signature {
+            Some(module.line_range)
+        } else {
+            find_line_range(&module.children, method)
+        }
+    })
+}
+
 impl Default for CallChainAnalyzer {
     fn default() -> Self {
         Self::new(1000000)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use super::*;
+    use crate::core::call_graph::{CallChain, CallType, EntryPoint, EntryPointType};
+    use crate::core::call_graph::call_chain_tracer::CallStep;
+
+    fn function(name: &str, line_range: (usize, usize)) -> ParsedModule {
+        ParsedModule {
+            name: name.to_string(),
+            item_type: "function".to_string(),
+            visibility: "pub".to_string(),
+            docs: None,
+            signature: Some(format!("fn {}()", name)),
+            line_range,
+            children: vec![],
+        }
+    }
+
+    #[test]
+    fn test_parses_fenced_response() {
+        let content = "Here is the analysis:\n```json\n{\"description\": \"Places orders.\", \
+            \"gotchas\": [{\"category\": \"Errors\", \"description\": \"Swallows failures\", \"severity\": \"Critical\"}]}\n```";
+        let response = parse_group_response(content).unwrap();
+
+        assert_eq!(response.description, "Places orders.");
+        assert!(matches!(GotchaSeverity::parse(&response.gotchas[0].severity), GotchaSeverity::Critical));
+        assert!(parse_group_response("{\"description\": \"\"}").is_none());
+        assert!(parse_group_response("no json here").is_none());
+    }
+
+    #[test]
+    fn test_sends_summaries_for_visited_files() {
+        let path = PathBuf::from("/p/src/orders.rs");
+        let file = ParsedFile {
+            path: path.clone(),
+            language: "rust".to_string(),
+            content_hash: String::new(),
+            modified_time: SystemTime::now(),
+            modules: vec![function("place", (1, 3)), function("unrelated", (4, 4))],
+            file_docs: None,
+            source_content: "fn place() {\n    save();\n}\nfn unrelated() {}\n".to_string(),
+            calls: vec![],
+        };
+        let place = MethodSignature::new(path.clone(), "place".to_string(), None, None, "fn place()".to_string());
+        let group = CallChainGroup {
+            group_id: "group-1".to_string(),
+            name: "Place".to_string(),
+            slug: "place".to_string(),
+            call_chains: vec![CallChain {
+                entry_point: EntryPoint {
+                    signature: place.clone(),
+                    entry_type: EntryPointType::PublicApi,
+                    confidence: 0.8,
+                    reasoning: String::new(),
+                    routes: vec![],
+                },
+                steps: vec![CallStep {
+                    method: place.clone(),
+                    depth: 0,
+                    call_site_line: 0,
+                    call_type: CallType::Direct,
+                    callees: vec![],
+                }],
+                involved_files: HashSet::from([path.clone()]),
+                has_cycles: false,
+                complexity_score: 1,
+                truncated: false,
+            }],
+            involved_files: HashSet::from([path.clone()]),
+            total_complexity: 1,
+            primary_entry_points: vec![place.clone()],
+            all_methods: HashSet::from([place.clone()]),
+        };
+
+        let mut analyzer = CallChainAnalyzer::new(1000);
+        let contexts = analyzer.collect_file_contexts(&group, std::slice::from_ref(&file), 0);
+        assert_eq!(contexts.len(), 1);
+        assert!(!contexts[0].from_summary);
+        assert_eq!(contexts[0].content, "// lines 1-3\nfn place() {\n    save();\n}\n");
+
+        // The execution paths come out of the same budget as the source
+        let paths = execution_paths(&group);
+        let small = CallChainAnalyzer::new(14);
+        assert_eq!(small.collect_file_contexts(&group, std::slice::from_ref(&file), 0).len(), 1);
+        assert!(small.collect_file_contexts(&group, std::slice::from_ref(&file), paths.len()).is_empty());
+
+        analyzer.visited_files.mark_analyzed(path.clone(), "Order placement".to_string());
+        analyzer.visited_files.mark_sent([place.clone()]);
+        let contexts = analyzer.collect_file_contexts(&group, std::slice::from_ref(&file), 0);
+        assert!(contexts[0].from_summary);
+        assert_eq!(contexts[0].content, "Order placement");
+        assert_eq!(contexts[0].methods, vec!["place".to_string()]);
+
+        // A method of a visited file that was not sent before comes with its source
+        let unrelated = MethodSignature::new(path, "unrelated".to_string(), None, None, "fn unrelated()".to_string());
+        let mut wider = group.clone();
+        wider.all_methods.insert(unrelated.clone());
+        let contexts = analyzer.collect_file_contexts(&wider, std::slice::from_ref(&file), 0);
+        assert_eq!(contexts[0].content, "Order placement\n// lines 4-4\nfn unrelated() {}\n");
+        assert_eq!(contexts[0].sent, vec![unrelated]);
+
+        // An earlier analysis is reused only while the file is unchanged
+        let mut previous = CallChainAnalyzer::fallback_analysis(&group);
+        previous.input_hash = group_input_hash(&group, std::slice::from_ref(&file));
+        analyzer.reuse_analyses([previous]);
+        assert!(analyzer.cached_analysis(&group, std::slice::from_ref(&file)).is_some());
+        let edited = ParsedFile { content_hash: "edited".to_string(), ..file };
+        assert!(analyzer.cached_analysis(&group, &[edited]).is_none());
+    }
+}
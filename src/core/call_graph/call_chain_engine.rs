@@ -1,13 +1,45 @@
 // src/core/call_graph/call_chain_engine.rs
-use std::path::{Path, PathBuf};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use serde::{Serialize, Deserialize};
 use tracing::{info, debug};
 
+use crate::config::AnalysisConfig;
 use crate::error::Result;
 use super::{
     CallGraph, EntryPointDetector, CallChainTracer, CallChainGrouper, CallChainAnalyzer,
-    CallChain, CallChainGroup, GroupAnalysis, SystemSynthesis, EntryPoint
+    CallChain, CallChainGroup, GroupAnalysis, SystemSynthesis, EntryPoint, AnalysisStore,
+    ExampleMiner, UsageExample, CallNode, TestCoverageAnalyzer, TestCoverage, CallType,
+    DeadCodeAnalyzer, DeadCodeReport, DeadCodeKind, DeadCodeConfidence, CentralityAnalyzer, Centrality, SequenceDiagram, SideEffectAnalyzer, SideEffects,
+    describe_effects, FailureModeAnalyzer, FailureModes, ConcurrencyAnalyzer, Concurrency, HttpRoute
 };
-use super::super::{LlmDocumenter, CodeParser, ParsedFile};
+use super::super::{LlmDocumenter, CodeParser};
+use super::call_chain_grouper::entry_point_key;
+
+/// Number of functions listed in `critical-functions.md`
+const CRITICAL_FUNCTIONS_LISTED: usize = 30;
+
+/// Offline call graph viewer; the analysis data is inlined in place of `/*GRAPH_DATA*/null`
+const GRAPH_VIEWER_TEMPLATE: &str = include_str!("graph_viewer.html");
+
+/// `groups/index.json`: each group's current and previous page names, so renamed
+/// pages leave a redirect behind and pages of removed groups can be cleaned up
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct GroupPageIndex {
+    groups: BTreeMap<String, GroupPage>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct GroupPage {
+    name: String,
+    slug: String,
+    /// Earlier slugs that now redirect to `slug`
+    #[serde(default)]
+    aliases: BTreeSet<String>,
+    /// Entry points of the group's chains, to find the group again if its ID changes
+    #[serde(default)]
+    entry_points: BTreeSet<String>,
+}
 
 /// Main orchestrator for call-chain analysis
 pub struct CallChainEngine {
@@ -15,11 +47,27 @@ pub struct CallChainEngine {
     call_chain_tracer: CallChainTracer,
     call_chain_grouper: CallChainGrouper,
     call_chain_analyzer: CallChainAnalyzer,
+    example_miner: ExampleMiner,
+    test_coverage_analyzer: TestCoverageAnalyzer,
+    dead_code_analyzer: DeadCodeAnalyzer,
+    centrality_analyzer: CentralityAnalyzer,
+    side_effect_analyzer: SideEffectAnalyzer,
+    failure_mode_analyzer: FailureModeAnalyzer,
+    concurrency_analyzer: ConcurrencyAnalyzer,
+    sequence_diagram: SequenceDiagram,
     max_depth: usize,
+    /// Maximum number of LLM calls for group analysis (unlimited when unset)
+    llm_budget: Option<usize>,
+    /// Synthesis from an earlier run, with the input hashes of the analyses it was made from
+    previous_synthesis: Option<(BTreeSet<String>, SystemSynthesis)>,
 }
 
 #[derive(Debug, Clone)]
 pub struct CallChainAnalysisResult {
+    /// Source directories that were analyzed
+    pub source_dirs: Vec<PathBuf>,
+    /// Content hash of every analyzed file
+    pub file_hashes: BTreeMap<PathBuf, String>,
     /// The complete call graph
     pub call_graph: CallGraph,
     /// All detected entry points
@@ -32,6 +80,20 @@ pub struct CallChainAnalysisResult {
     pub group_analyses: Vec<GroupAnalysis>,
     /// Overall system understanding
     pub system_synthesis: SystemSynthesis,
+    /// Usage examples mined from tests and cross-package call sites
+    pub usage_examples: Vec<UsageExample>,
+    /// Which tests exercise each function
+    pub test_coverage: TestCoverage,
+    /// Functions no production entry point reaches
+    pub dead_code: DeadCodeReport,
+    /// Functions ranked by fan-in, fan-out, betweenness and PageRank
+    pub centrality: Centrality,
+    /// I/O each function performs, directly and through its callees
+    pub side_effects: SideEffects,
+    /// Errors that can reach each entry point, and where they are handled
+    pub failure_modes: FailureModes,
+    /// Spawns, pools, channels, locks and async boundaries in each function
+    pub concurrency: Concurrency,
     /// Performance statistics
     pub stats: AnalysisStatistics,
 }
@@ -49,20 +111,41 @@ pub struct AnalysisStatistics {
 }
 
 impl CallChainEngine {
-    pub fn new(max_depth: usize, max_context_size: usize) -> Self {
+    pub fn new(analysis: &AnalysisConfig, max_context_size: usize, llm_budget: Option<usize>) -> Self {
         Self {
             entry_point_detector: EntryPointDetector::new(),
-            call_chain_tracer: CallChainTracer::new(max_depth),
-            call_chain_grouper: CallChainGrouper::new(),
+            call_chain_tracer: CallChainTracer::new(analysis),
+            call_chain_grouper: CallChainGrouper::new(analysis),
             call_chain_analyzer: CallChainAnalyzer::new(max_context_size),
-            max_depth,
+            example_miner: ExampleMiner::default(),
+            test_coverage_analyzer: TestCoverageAnalyzer::new(),
+            dead_code_analyzer: DeadCodeAnalyzer::new(),
+            centrality_analyzer: CentralityAnalyzer::new(),
+            side_effect_analyzer: SideEffectAnalyzer::new(),
+            failure_mode_analyzer: FailureModeAnalyzer::new(),
+            concurrency_analyzer: ConcurrencyAnalyzer::new(),
+            sequence_diagram: SequenceDiagram::new(analysis),
+            max_depth: analysis.max_call_depth,
+            llm_budget,
+            previous_synthesis: None,
         }
     }
 
+    /// Reuse LLM results from an earlier run wherever their inputs are unchanged
+    pub fn reuse_results(&mut self, store: AnalysisStore) {
+        let inputs: BTreeSet<String> = store.group_analyses.iter()
+            .map(|analysis| analysis.input_hash.clone())
+            .collect();
+        if !inputs.is_empty() && !inputs.contains("") {
+            self.previous_synthesis = Some((inputs, store.system_synthesis));
+        }
+        self.call_chain_analyzer.reuse_analyses(store.group_analyses);
+    }
+
     /// Perform complete call-chain analysis on a codebase
-    pub async fn analyze_codebase<P: AsRef<Path>>(
+    pub async fn analyze_codebase(
         &mut self,
-        source_dir: P,
+        source_dirs: &[PathBuf],
         parser: &mut CodeParser,
         llm_documenter: Option<&dyn LlmDocumenter>,
     ) -> Result<CallChainAnalysisResult> {
@@ -71,16 +154,24 @@ impl CallChainEngine {
 
         // Step 1: Parse all files
         info!("📖 Parsing source files...");
-        let parsed_files = parser.parse_directory(&source_dir).await?;
+        let mut parsed_files = Vec::new();
+        for source_dir in source_dirs {
+            parsed_files.extend(parser.parse_directory(source_dir).await?);
+        }
+        let file_hashes = parser.hash_directories(source_dirs)?;
         info!("Found {} source files", parsed_files.len());
 
         // Step 2: Build call graph
         info!("🕸️ Building call graph...");
         let call_graph = CallGraph::build_from_files(&parsed_files)?;
         let graph_stats = call_graph.get_statistics();
-        info!("Built call graph: {} methods, {} calls, {} cycles detected",
+        info!("Built call graph: {} methods, {} calls, {} recursive clusters detected",
               graph_stats.total_methods, graph_stats.total_calls, graph_stats.cycles);
 
+        // Step 2b: Mine usage examples from real call sites
+        let usage_examples = self.example_miner.mine_examples(&call_graph, &parsed_files);
+        info!("Mined {} usage examples", usage_examples.len());
+
         // Step 3: Detect entry points
         info!("🚪 Detecting entry points...");
         let entry_points = self.entry_point_detector.detect_entry_points(&call_graph)?;
@@ -91,59 +182,129 @@ impl CallChainEngine {
                    ep.signature.display_name(), ep.entry_type, ep.confidence);
         }
 
+        // Step 3b: Index which tests reach which functions
+        let test_coverage = self.test_coverage_analyzer.analyze(&call_graph, &entry_points);
+        info!("{} functions are exercised by tests", test_coverage.tested_by.len());
+
+        // Step 3c: Find code no production entry point reaches
+        let dead_code = self.dead_code_analyzer.analyze(&call_graph, &entry_points, &test_coverage);
+        info!("{} functions look unused", dead_code.total());
+
+        // Step 3d: Rank functions by centrality so the most important code is documented first
+        let centrality = self.centrality_analyzer.analyze(&call_graph);
+        if let Some(top) = centrality.ranked.first() {
+            info!("Most central function: {} (score {:.2})", top.method.display_name(), top.score);
+        }
+
+        // Step 3e: Find the I/O functions perform and what reaches it
+        let side_effects = self.side_effect_analyzer.analyze(&call_graph, &parsed_files);
+        info!("{} functions perform I/O directly", side_effects.direct.len());
+
+        // Step 3f: Follow errors from where they start to the entry points they reach
+        let failure_modes = self.failure_mode_analyzer.analyze(&call_graph, &parsed_files, &entry_points);
+        info!("{} entry points can fail", failure_modes.by_entry_point.len());
+
+        // Step 3g: Find where work runs concurrently and which shared state it locks
+        let concurrency = self.concurrency_analyzer.analyze(&call_graph, &parsed_files);
+        info!("{} functions use concurrency constructs ({} contended locks)",
+              concurrency.sites.len(), concurrency.contention.len());
+
         // Step 4: Trace call chains
         info!("🔗 Tracing call chains (max depth: {})...", self.max_depth);
         let call_chains = self.call_chain_tracer.trace_all_chains(&call_graph, &entry_points)?;
-        info!("Traced {} call chains", call_chains.len());
+        info!("Traced {} call chains ({} with cycles, {} cut short by limits)",
+              call_chains.len(),
+              call_chains.iter().filter(|chain| chain.has_cycles).count(),
+              call_chains.iter().filter(|chain| chain.truncated).count());
 
-        // Step 5: Group call chains by file sets
-        info!("📦 Grouping call chains by involved files...");
-        let call_chain_groups = self.call_chain_grouper.group_call_chains(call_chains.clone())?;
+        // Step 5: Cluster call chains that touch similar files and methods
+        info!("📦 Grouping call chains by similarity...");
+        let mut call_chain_groups = self.call_chain_grouper.group_call_chains(call_chains.clone(), &call_graph.http_routes)?;
+        call_chain_groups.sort_by(|a, b| {
+            centrality.score_of_all(&b.all_methods).total_cmp(&centrality.score_of_all(&a.all_methods))
+                .then(a.group_id.cmp(&b.group_id))
+        });
         let grouping_stats = self.call_chain_grouper.get_grouping_statistics(&call_chain_groups);
         info!("Created {} groups (avg {:.1} chains per group)",
               grouping_stats.total_groups, grouping_stats.avg_chains_per_group);
 
-        // Step 6: Analyze groups with LLM (if available)
+        // Step 6: Analyze groups with LLM (if available), reusing earlier analyses of unchanged groups
         let mut group_analyses = Vec::new();
         let mut llm_calls_made = 0;
+        let mut reused_analyses = 0;
+        let mut budget_spent = false;
 
-        if let Some(llm) = llm_documenter {
+        if llm_documenter.is_some() {
             info!("🧠 Analyzing groups with LLM...");
+        } else {
+            info!("No LLM configured, skipping group analysis");
+        }
 
-            for (i, group) in call_chain_groups.iter().enumerate() {
-                let group_name = self.call_chain_grouper.get_group_name(group);
-                info!("Analyzing group {}/{}: {}", i + 1, call_chain_groups.len(), group_name);
-
-                match self.call_chain_analyzer.analyze_group(group, &parsed_files, llm).await {
-                    Ok(analysis) => {
-                        llm_calls_made += 1;
-                        debug!("Group analysis complete (confidence: {:.2}): {}",
-                               analysis.confidence, 
-                               analysis.description.chars().take(100).collect::<String>());
-                        group_analyses.push(analysis);
-                    }
-                    Err(e) => {
-                        tracing::warn!("Failed to analyze group {}: {}", group_name, e);
-                        // Continue with other groups
-                    }
+        for (i, group) in call_chain_groups.iter().enumerate() {
+            if let Some(analysis) = self.call_chain_analyzer.cached_analysis(group, &parsed_files) {
+                reused_analyses += 1;
+                group_analyses.push(analysis);
+                continue;
+            }
+
+            let Some(llm) = llm_documenter else {
+                continue;
+            };
+            if self.llm_budget.is_some_and(|budget| llm_calls_made >= budget) {
+                if !budget_spent {
+                    info!("LLM budget of {} calls spent; lower-ranked groups are left without analysis", llm_calls_made);
+                    budget_spent = true;
                 }
+                continue;
             }
-        } else {
-            info!("No LLM configured, skipping group analysis");
+
+            let group_name = self.call_chain_grouper.get_group_name(group);
+            info!("Analyzing group {}/{}: {}", i + 1, call_chain_groups.len(), group_name);
+
+            match self.call_chain_analyzer.analyze_group(group, &parsed_files, &side_effects, llm).await {
+                Ok(analysis) => {
+                    llm_calls_made += 1;
+                    debug!("Group analysis complete (confidence: {:.2}): {}",
+                           analysis.confidence, 
+                           analysis.description.chars().take(100).collect::<String>());
+                    group_analyses.push(analysis);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to analyze group {}: {}", group_name, e);
+                    // Continue with other groups
+                }
+            }
+        }
+        if reused_analyses > 0 {
+            info!("Reused {} group analyses whose code is unchanged", reused_analyses);
         }
 
         // Step 7: Synthesize overall understanding
         info!("🎯 Synthesizing system understanding...");
-        let system_synthesis = if !group_analyses.is_empty() {
-            self.call_chain_analyzer.synthesize_system_understanding(&group_analyses)?
-        } else {
-            SystemSynthesis {
+        let analysis_inputs: BTreeSet<String> = group_analyses.iter()
+            .map(|analysis| analysis.input_hash.clone())
+            .collect();
+        let system_synthesis = match (&self.previous_synthesis, llm_documenter) {
+            (Some((inputs, synthesis)), _) if *inputs == analysis_inputs => {
+                info!("Group analyses are unchanged; reusing the previous synthesis");
+                synthesis.clone()
+            }
+            (_, Some(llm)) if !group_analyses.is_empty() => {
+                let call_budget = self.llm_budget.map(|budget| budget.saturating_sub(llm_calls_made));
+                let (synthesis, synthesis_calls) = self.call_chain_analyzer
+                    .synthesize_system_understanding(&call_chain_groups, &group_analyses, llm, call_budget).await?;
+                llm_calls_made += synthesis_calls;
+                synthesis
+            }
+            _ => SystemSynthesis {
                 overall_description: "Call-chain analysis completed without LLM enhancement".to_string(),
                 key_themes: vec!["Code Structure".to_string()],
+                theme_sources: BTreeMap::new(),
                 critical_gotchas: vec![],
+                package_summaries: vec![],
                 total_groups_analyzed: 0,
                 overall_confidence: 0.5,
-            }
+            },
         };
 
         let analysis_time_ms = start_time.elapsed().as_millis();
@@ -161,12 +322,21 @@ impl CallChainEngine {
         };
 
         Ok(CallChainAnalysisResult {
+            source_dirs: source_dirs.to_vec(),
+            file_hashes,
             call_graph,
             entry_points,
             call_chains,
             call_chain_groups,
             group_analyses,
             system_synthesis,
+            usage_examples,
+            test_coverage,
+            dead_code,
+            centrality,
+            side_effects,
+            failure_modes,
+            concurrency,
             stats,
         })
     }
@@ -188,9 +358,24 @@ impl CallChainEngine {
         // Generate group documentation
         self.generate_group_documentation(analysis_result, output_dir).await?;
 
+        // Generate per-package API reference
+        self.generate_api_documentation(analysis_result, output_dir).await?;
+
+        // Generate the ranked list of critical functions
+        self.generate_critical_functions(analysis_result, output_dir).await?;
+
+        // Generate the index of HTTP endpoints
+        self.generate_endpoint_index(analysis_result, output_dir).await?;
+
+        // Generate the dead code report
+        self.generate_dead_code_report(analysis_result, output_dir).await?;
+
         // Generate call graph visualization data
         self.generate_call_graph_data(analysis_result, output_dir).await?;
 
+        // Generate the offline interactive graph viewer
+        self.generate_graph_viewer(analysis_result, output_dir).await?;
+
         info!("✅ Documentation generation complete");
         Ok(())
     }
@@ -214,40 +399,123 @@ impl CallChainEngine {
             analysis_result.stats.groups_created
         ));
 
+        content.push_str("Browse the [interactive call graph](./graph.html) (works offline), \
+                          or start with the [most critical functions](./critical-functions.md).\n\n");
+        if !analysis_result.call_graph.http_routes.is_empty() {
+            content.push_str(&format!(
+                "The system serves {} HTTP route(s); see the [endpoint index](./endpoints.md).\n\n",
+                analysis_result.call_graph.http_routes.len()
+            ));
+        }
+        if analysis_result.dead_code.total() > 0 {
+            content.push_str(&format!(
+                "{} function(s) look unused; see the [dead code report](./dead-code.md).\n\n",
+                analysis_result.dead_code.total()
+            ));
+        }
+
         // System synthesis
         let synthesis = &analysis_result.system_synthesis;
         content.push_str("## System Understanding\n\n");
         content.push_str(&synthesis.overall_description);
         content.push_str("\n\n");
 
+        let group_links = |group_ids: &mut dyn Iterator<Item = &String>| -> String {
+            group_ids
+                .filter_map(|group_id| analysis_result.call_chain_groups.iter().find(|group| &group.group_id == group_id))
+                .map(|group| format!("[{}](./groups/{}.md)", self.call_chain_grouper.get_group_name(group), group.slug))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
         if !synthesis.key_themes.is_empty() {
             content.push_str("### Key Themes\n\n");
             for theme in &synthesis.key_themes {
-                content.push_str(&format!("- {}\n", theme));
+                let sources = synthesis.theme_sources.get(theme)
+                    .map(|group_ids| group_links(&mut group_ids.iter()))
+                    .unwrap_or_default();
+                if sources.is_empty() {
+                    content.push_str(&format!("- {}\n", theme));
+                } else {
+                    content.push_str(&format!("- {} (from {})\n", theme, sources));
+                }
             }
-            content.push_str("\n");
+            content.push('\n');
+        }
+
+        if synthesis.package_summaries.len() > 1 {
+            content.push_str("### By Package\n\n");
+            for package in &synthesis.package_summaries {
+                content.push_str(&format!("- **{}**: {}\n", package.package, package.summary));
+            }
+            content.push('\n');
+        }
+
+        if !synthesis.critical_gotchas.is_empty() {
+            content.push_str("### Gotchas\n\n");
+            for ranked in &synthesis.critical_gotchas {
+                content.push_str(&format!("- **{:?}** ({}): {} (from {})\n",
+                                          ranked.gotcha.severity, ranked.gotcha.category, ranked.gotcha.description,
+                                          group_links(&mut ranked.source_groups.iter())));
+            }
+            content.push('\n');
         }
 
         // Entry points
         content.push_str("## Entry Points\n\n");
-        content.push_str("These are the main ways users and external systems interact with this codebase:\n\n");
+        content.push_str("These are the main ways users and external systems interact with this codebase, most central first:\n\n");
 
-        for entry_point in &analysis_result.entry_points {
+        let centrality = &analysis_result.centrality;
+        let chain_score = |entry_point: &EntryPoint| {
+            centrality.score_of_all(analysis_result.call_chains.iter()
+                .filter(|chain| chain.entry_point.signature == entry_point.signature)
+                .flat_map(|chain| chain.steps.iter().map(|step| &step.method)))
+        };
+        let mut entry_points: Vec<&EntryPoint> = analysis_result.entry_points.iter().collect();
+        entry_points.sort_by(|a, b| chain_score(b).total_cmp(&chain_score(a)));
+
+        for entry_point in entry_points {
             content.push_str(&format!(
                 "### {} ({:?})\n\n",
                 entry_point.signature.display_name(),
                 entry_point.entry_type
             ));
             content.push_str(&format!("**File**: {}\n", entry_point.signature.file_path.display()));
+            if !entry_point.routes.is_empty() {
+                content.push_str(&format!("**Routes**: {}\n", format_routes(&entry_point.routes)));
+            }
             content.push_str(&format!("**Confidence**: {:.2}\n", entry_point.confidence));
+            let effects = analysis_result.side_effects.effects_of(&entry_point.signature);
+            if !effects.is_empty() {
+                content.push_str(&format!("**Side effects**: {}\n", describe_effects(&effects)));
+            }
+            let unhandled = analysis_result.failure_modes.unhandled_types(&entry_point.signature);
+            if !unhandled.is_empty() {
+                let names: Vec<String> = unhandled.iter().map(|name| format!("`{}`", name)).collect();
+                content.push_str(&format!("**Unhandled errors**: {}\n", names.join(", ")));
+            }
             content.push_str(&format!("**Reasoning**: {}\n\n", entry_point.reasoning));
         }
 
+        // Recursive structures
+        content.push_str(&self.format_recursive_structures(analysis_result, output_dir));
+
+        // API reference
+        let mut packages: Vec<(String, Vec<&CallNode>)> = self.public_api_by_package(analysis_result).into_iter().collect();
+        packages.sort_by(|(a, _), (b, _)| centrality.package_score(b).total_cmp(&centrality.package_score(a)));
+        if !packages.is_empty() {
+            content.push_str("## API Reference\n\n");
+            for (package, nodes) in &packages {
+                content.push_str(&format!("- [{}](./api/{}.md) ({} public functions)\n", package, package, nodes.len()));
+            }
+            content.push('\n');
+        }
+
         // Group summaries
         content.push_str("## Execution Path Groups\n\n");
         content.push_str("Related execution paths grouped by the files they involve:\n\n");
 
-        for (i, group) in analysis_result.call_chain_groups.iter().enumerate() {
+        for group in &analysis_result.call_chain_groups {
             let group_name = self.call_chain_grouper.get_group_name(group);
             content.push_str(&format!("### {}\n\n", group_name));
 
@@ -260,12 +528,12 @@ impl CallChainEngine {
                 group.total_complexity
             ));
 
-            if let Some(analysis) = analysis_result.group_analyses.get(i) {
+            if let Some(analysis) = self.analysis_for(analysis_result, &group.group_id) {
                 content.push_str(&format!("- **Purpose**: {}\n",
                                           analysis.description.split('.').next().unwrap_or("Unknown")));
             }
 
-            content.push_str(&format!("\n[View detailed analysis](./groups/{}.md)\n\n", group.group_id));
+            content.push_str(&format!("\n[View detailed analysis](./groups/{}.md)\n\n", group.slug));
         }
 
         // Write overview file
@@ -284,8 +552,8 @@ impl CallChainEngine {
         let groups_dir = output_dir.join("groups");
         std::fs::create_dir_all(&groups_dir)?;
 
-        for (group, analysis) in analysis_result.call_chain_groups.iter()
-            .zip(analysis_result.group_analyses.iter()) {
+        for group in &analysis_result.call_chain_groups {
+            let analysis = self.analysis_for(analysis_result, &group.group_id);
 
             let mut content = String::new();
             let group_name = self.call_chain_grouper.get_group_name(group);
@@ -293,12 +561,28 @@ impl CallChainEngine {
             // Header
             content.push_str(&format!("# {}\n\n", group_name));
             content.push_str(&format!("**Group ID**: {}\n", group.group_id));
-            content.push_str(&format!("**Analysis Confidence**: {:.2}\n\n", analysis.confidence));
+            if let Some(analysis) = analysis {
+                content.push_str(&format!("**Analysis Confidence**: {:.2}\n", analysis.confidence));
+            }
+            content.push('\n');
 
             // Description
             content.push_str("## What This Code Does\n\n");
-            content.push_str(&analysis.description);
+            match analysis {
+                Some(analysis) => content.push_str(&analysis.description),
+                None => content.push_str(&format!(
+                    "{} execution path(s) involving {} file(s).",
+                    group.call_chains.len(),
+                    group.involved_files.len()
+                )),
+            }
             content.push_str("\n\n");
+            if let Some(analysis) = analysis {
+                content.push_str(&self.format_group_analysis(group, analysis));
+            }
+            content.push_str(&self.format_group_side_effects(group, &analysis_result.side_effects));
+            content.push_str(&self.format_group_failure_modes(group, &analysis_result.failure_modes));
+            content.push_str(&self.format_group_concurrency(group, &analysis_result.concurrency));
 
             // Execution paths
             content.push_str("## Execution Paths\n\n");
@@ -309,23 +593,41 @@ impl CallChainEngine {
                     chain.entry_point.signature.display_name(),
                     chain.entry_point.confidence
                 ));
+                if !chain.entry_point.routes.is_empty() {
+                    content.push_str(&format!("**Routes**: {}\n\n", format_routes(&chain.entry_point.routes)));
+                }
+
+                content.push_str("```mermaid\n");
+                content.push_str(&self.sequence_diagram.render(chain));
+                content.push_str("```\n\n<details>\n<summary>Steps</summary>\n\n");
 
+                // Each step shows where the function is defined and, below the entry
+                // point, the line of the caller that calls it
+                let file_name = |path: &Path| path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                let mut caller_files: Vec<&Path> = Vec::new();
                 for step in &chain.steps {
-                    let indent = "  ".repeat(step.depth);
-                    content.push_str(&format!(
-                        "{}{}. {} ({}:{})\n",
-                        indent,
+                    let mut line = format!(
+                        "{}{}. {} ({}:{})",
+                        "  ".repeat(step.depth),
                         step.depth,
                         step.method.display_name(),
-                        step.method.file_path.file_name()
-                            .unwrap_or_default()
-                            .to_string_lossy(),
-                        step.call_site_line
-                    ));
+                        file_name(&step.method.file_path),
+                        self.node_line(analysis_result, &step.method)
+                    );
+                    caller_files.truncate(step.depth);
+                    if let Some(caller_file) = caller_files.last() {
+                        line.push_str(&format!(", called at {}:{}", file_name(caller_file), step.call_site_line));
+                    }
+                    caller_files.push(&step.method.file_path);
+                    content.push_str(&line);
+                    content.push('\n');
                 }
-                content.push_str("\n");
+                content.push_str("\n</details>\n\n");
             }
 
+            // Test coverage
+            content.push_str(&self.format_group_test_coverage(group, &analysis_result.test_coverage));
+
             // Involved files
             content.push_str("## Files Involved\n\n");
             for file in &group.involved_files {
@@ -334,10 +636,734 @@ impl CallChainEngine {
             content.push_str("\n");
 
             // Write group file
-            let group_file = groups_dir.join(format!("{}.md", group.group_id));
+            let group_file = groups_dir.join(format!("{}.md", group.slug));
             std::fs::write(group_file, content)?;
         }
 
+        self.write_group_redirects(analysis_result, &groups_dir)?;
+
+        Ok(())
+    }
+
+    /// The structured parts of a group's LLM analysis, each section only when it has content
+    fn format_group_analysis(&self, group: &CallChainGroup, analysis: &GroupAnalysis) -> String {
+        let mut content = String::new();
+
+        let entry_points: Vec<(String, &String)> = group.call_chains.iter()
+            .filter_map(|chain| analysis.entry_point_descriptions.get(&chain.entry_point.signature)
+                .map(|description| (chain.entry_point.signature.display_name(), description)))
+            .collect();
+        if !entry_points.is_empty() {
+            content.push_str("## Entry Points\n\n");
+            for (name, description) in entry_points {
+                content.push_str(&format!("- **{}**: {}\n", name, description));
+            }
+            content.push('\n');
+        }
+
+        if !analysis.component_interactions.is_empty() {
+            content.push_str("## Component Interactions\n\n");
+            for interaction in &analysis.component_interactions {
+                content.push_str(&format!("- **{}** → **{}** ({}): {}\n",
+                                          interaction.from_component, interaction.to_component,
+                                          interaction.interaction_type, interaction.description));
+            }
+            content.push('\n');
+        }
+
+        if !analysis.domain_insights.is_empty() {
+            content.push_str("## Domain Insights\n\n");
+            for insight in &analysis.domain_insights {
+                content.push_str(&format!("- **{}**: {}", insight.category, insight.insight));
+                if !insight.evidence.is_empty() {
+                    content.push_str(&format!(" _(see {})_", insight.evidence.join(", ")));
+                }
+                content.push('\n');
+            }
+            content.push('\n');
+        }
+
+        if !analysis.gotchas.is_empty() {
+            content.push_str("## Gotchas\n\n");
+            for gotcha in &analysis.gotchas {
+                content.push_str(&format!("- **{:?}** ({}): {}", gotcha.severity, gotcha.category, gotcha.description));
+                if let Some(action) = &gotcha.suggested_action {
+                    content.push_str(&format!(" — {}", action));
+                }
+                content.push('\n');
+            }
+            content.push('\n');
+        }
+
+        content
+    }
+
+    /// Leave a redirect page wherever a group's page used to be, and remove the pages
+    /// of groups that are gone. Earlier pages come from `groups/index.json` (a group whose
+    /// ID changed is found again by the entry points it shares with a current group) and
+    /// from `group-<hash>.md` pages written before groups had names.
+    fn write_group_redirects(&self, analysis_result: &CallChainAnalysisResult, groups_dir: &Path) -> Result<()> {
+        let index_path = groups_dir.join("index.json");
+        let previous: GroupPageIndex = std::fs::read_to_string(&index_path).ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        let groups = &analysis_result.call_chain_groups;
+        let current_slugs: BTreeSet<&str> = groups.iter().map(|group| group.slug.as_str()).collect();
+        let entry_points: Vec<BTreeSet<String>> = groups.iter()
+            .map(|group| group.call_chains.iter().map(entry_point_key).collect())
+            .collect();
+
+        // Hand each earlier group's pages to the group that took its place: the one with
+        // the same ID, else the one sharing most of its entry points
+        let mut aliases: Vec<BTreeSet<String>> = vec![BTreeSet::new(); groups.len()];
+        let mut removed_pages = BTreeSet::new();
+        for (group_id, page) in &previous.groups {
+            let successor = groups.iter().position(|group| &group.group_id == group_id).or_else(|| {
+                entry_points.iter()
+                    .map(|current| current.intersection(&page.entry_points).count())
+                    .enumerate()
+                    .filter(|&(_, shared)| shared > 0)
+                    .max_by_key(|&(index, shared)| (shared, std::cmp::Reverse(index)))
+                    .map(|(index, _)| index)
+            });
+
+            let pages = page.aliases.iter().chain(std::iter::once(&page.slug)).cloned();
+            match successor {
+                Some(index) => aliases[index].extend(pages),
+                None => removed_pages.extend(pages),
+            }
+        }
+
+        for slug in &removed_pages {
+            let page_path = groups_dir.join(format!("{}.md", slug));
+            if !current_slugs.contains(slug.as_str()) && page_path.exists() {
+                std::fs::remove_file(page_path)?;
+            }
+        }
+
+        let mut index = GroupPageIndex::default();
+        for ((group, mut aliases), entry_points) in groups.iter().zip(aliases).zip(entry_points) {
+            if groups_dir.join(format!("{}.md", group.group_id)).exists() {
+                aliases.insert(group.group_id.clone());
+            }
+            aliases.retain(|alias| !current_slugs.contains(alias.as_str()));
+
+            let name = self.call_chain_grouper.get_group_name(group);
+            for alias in &aliases {
+                std::fs::write(
+                    groups_dir.join(format!("{}.md", alias)),
+                    format!(
+                        "---\nredirect_to: {slug}.md\n---\n\n# {name}\n\nThis page has moved to [{name}](./{slug}.md).\n",
+                        slug = group.slug,
+                        name = name
+                    ),
+                )?;
+            }
+
+            index.groups.insert(group.group_id.clone(), GroupPage { name, slug: group.slug.clone(), aliases, entry_points });
+        }
+
+        std::fs::write(index_path, serde_json::to_string_pretty(&index)?)?;
+        Ok(())
+    }
+
+    /// Generate one API reference page per package, with mined usage examples
+    async fn generate_api_documentation(
+        &self,
+        analysis_result: &CallChainAnalysisResult,
+        output_dir: &Path,
+    ) -> Result<()> {
+        let api_dir = output_dir.join("api");
+        std::fs::create_dir_all(&api_dir)?;
+
+        for (package, nodes) in self.public_api_by_package(analysis_result) {
+            let mut content = String::new();
+
+            content.push_str(&format!("# {} API\n\n", package));
+            content.push_str(&format!("Public types, functions and methods in the `{}` package.\n\n", package));
+
+            let mut current_type: Option<(&PathBuf, &str)> = None;
+            let mut type_example_sites: HashSet<(&PathBuf, usize)> = HashSet::new();
+
+            for node in nodes {
+                let signature = &node.signature;
+
+                // Introduce each type with the examples that construct it
+                let node_type = signature.class_name.as_deref().map(|class| (&signature.file_path, class));
+                if node_type != current_type {
+                    current_type = node_type;
+                    type_example_sites.clear();
+
+                    if let Some((type_file, class)) = node_type {
+                        let type_examples: Vec<_> = analysis_result.usage_examples.iter()
+                            .filter(|example| &example.target.file_path == type_file && example.type_name.as_deref() == Some(class))
+                            .collect();
+                        if !type_examples.is_empty() {
+                            content.push_str(&format!("## {}\n\n", class));
+                            content.push_str(&self.format_usage_examples(&type_examples, &api_dir));
+                            type_example_sites.extend(type_examples.iter().map(|example| (&example.file_path, example.line)));
+                        }
+                    }
+                }
+
+                content.push_str(&format!("## {}\n\n", signature.display_name()));
+                content.push_str(&format!(
+                    "**Source**: [{}:{}]({})\n\n",
+                    signature.file_path.display(),
+                    node.line_range.0,
+                    source_link(&api_dir, &signature.file_path, node.line_range.0)
+                ));
+
+                if !signature.signature.is_empty() {
+                    content.push_str(&format!("```{}\n{}\n```\n\n", fence_language(&signature.file_path), signature.signature));
+                }
+
+                if let Some(docs) = &node.documentation {
+                    content.push_str(docs);
+                    content.push_str("\n\n");
+                }
+
+                content.push_str(&self.format_dynamic_calls(analysis_result, signature, &api_dir));
+
+                let tests = analysis_result.test_coverage.tests_for(signature);
+                if tests.is_empty() {
+                    content.push_str("**Tested by**: *no tests reach this function*\n\n");
+                } else {
+                    let test_links: Vec<String> = tests.iter()
+                        .map(|test| format!(
+                            "[`{}`]({})",
+                            test.display_name(),
+                            source_link(&api_dir, &test.file_path, self.node_line(analysis_result, test))
+                        ))
+                        .collect();
+                    content.push_str(&format!("**Tested by**: {}\n\n", test_links.join(", ")));
+                }
+
+                // Call sites already shown for the type are not repeated for its constructor
+                let examples: Vec<_> = analysis_result.usage_examples.iter()
+                    .filter(|example| &example.target == signature && example.type_name.is_none())
+                    .filter(|example| !type_example_sites.contains(&(&example.file_path, example.line)))
+                    .collect();
+                content.push_str(&self.format_usage_examples(&examples, &api_dir));
+            }
+
+            if let Some(untested) = analysis_result.test_coverage.untested_public_api.get(&package) {
+                content.push_str("## Untested Public API\n\n");
+                content.push_str("No test entry point reaches these functions:\n\n");
+                for function in untested {
+                    content.push_str(&format!(
+                        "- `{}` ({}:{})\n",
+                        function.display_name(),
+                        function.file_path.display(),
+                        self.node_line(analysis_result, function)
+                    ));
+                }
+                content.push('\n');
+            }
+
+            std::fs::write(api_dir.join(format!("{}.md", package)), content)?;
+        }
+
+        Ok(())
+    }
+
+    /// List the implementations each interface/trait call in `caller` may dispatch to
+    fn format_dynamic_calls(&self, analysis_result: &CallChainAnalysisResult, caller: &super::MethodSignature, from_dir: &Path) -> String {
+        let mut targets_by_line: BTreeMap<usize, Vec<&super::MethodSignature>> = BTreeMap::new();
+        for edge in &analysis_result.call_graph.edges {
+            if &edge.caller == caller && matches!(edge.call_type, CallType::Dynamic) {
+                targets_by_line.entry(edge.call_site_line).or_default().push(&edge.callee);
+            }
+        }
+
+        let mut content = String::new();
+        for (line, targets) in targets_by_line {
+            let links: Vec<String> = targets.iter()
+                .map(|target| format!(
+                    "[`{}`]({})",
+                    target.display_name(),
+                    source_link(from_dir, &target.file_path, self.node_line(analysis_result, target))
+                ))
+                .collect();
+            content.push_str(&format!("**May dispatch to** (line {}): {}\n\n", line, links.join(", ")));
+        }
+
+        content
+    }
+
+    /// Summarise which of a group's functions are exercised by tests
+    fn format_group_test_coverage(&self, group: &CallChainGroup, coverage: &TestCoverage) -> String {
+        let mut methods: Vec<_> = group.all_methods.iter()
+            .filter(|method| !group.call_chains.iter().any(|chain| {
+                &chain.entry_point.signature == *method
+                    && matches!(chain.entry_point.entry_type, super::EntryPointType::Test)
+            }))
+            .collect();
+        if methods.is_empty() {
+            return String::new();
+        }
+        methods.sort_by_key(|method| method.to_unique_string());
+
+        let mut content = String::new();
+        content.push_str("## Test Coverage\n\n");
+
+        let (tested, untested): (Vec<_>, Vec<_>) = methods.into_iter()
+            .partition(|method| !coverage.tests_for(method).is_empty());
+
+        for method in &tested {
+            let tests: Vec<String> = coverage.tests_for(method).iter()
+                .map(|test| format!("`{}`", test.display_name()))
+                .collect();
+            content.push_str(&format!("- `{}` tested by {}\n", method.display_name(), tests.join(", ")));
+        }
+
+        if !untested.is_empty() {
+            let names: Vec<String> = untested.iter()
+                .map(|method| format!("`{}`", method.display_name()))
+                .collect();
+            content.push_str(&format!("- **Not reached by any test**: {}\n", names.join(", ")));
+        }
+        content.push('\n');
+
+        content
+    }
+
+    /// "Side Effects" section: what the group's workflows touch, and the calls that do it
+    fn format_group_side_effects(&self, group: &CallChainGroup, side_effects: &SideEffects) -> String {
+        let effects = side_effects.effects_of_group(group);
+        if effects.is_empty() {
+            return String::new();
+        }
+
+        let mut content = String::new();
+        content.push_str("## Side Effects\n\n");
+        content.push_str(&format!("This workflow {}.\n\n", describe_effects(&effects)));
+
+        for (method, site) in side_effects.sites_in_group(group) {
+            content.push_str(&format!("- {}: `{}` calls `{}` ({}:{})\n",
+                                      site.effect.describe(), method.display_name(), site.call,
+                                      method.file_path.file_name().unwrap_or_default().to_string_lossy(), site.line));
+        }
+        content.push('\n');
+
+        content
+    }
+
+    /// "Failure Modes" section: each error the group's entry points can hit, where it
+    /// starts and whether anything on the way handles it
+    fn format_group_failure_modes(&self, group: &CallChainGroup, failure_modes: &FailureModes) -> String {
+        let modes = failure_modes.of_group(group);
+        if modes.is_empty() {
+            return String::new();
+        }
+
+        let mut content = String::new();
+        content.push_str("## Failure Modes\n\n");
+        content.push_str("| Error | Originates in | Raised by | Handled |\n");
+        content.push_str("|-------|---------------|-----------|---------|\n");
+        for group_mode in modes {
+            let mode = group_mode.mode;
+            let mut handling = Vec::new();
+            if !group_mode.handled_in.is_empty() {
+                let handlers: Vec<String> = group_mode.handled_in.iter().map(|name| format!("`{}`", name)).collect();
+                handling.push(format!("caught in {}", handlers.join(", ")));
+            }
+            if !group_mode.reaches.is_empty() {
+                let entry_points: Vec<String> = group_mode.reaches.iter()
+                    .map(|method| format!("`{}`", method.display_name()))
+                    .collect();
+                handling.push(format!("**unhandled** at {}", entry_points.join(", ")));
+            }
+            content.push_str(&format!("| `{}` | `{}` ({}:{}) | {} | {} |\n",
+                                      mode.error_type, mode.origin.display_name(),
+                                      mode.origin.file_path.file_name().unwrap_or_default().to_string_lossy(), mode.line,
+                                      mode.raised_by.replace('|', "\\|"), handling.join("; ")));
+        }
+        content.push('\n');
+
+        content
+    }
+
+    /// "Concurrency" section: where each path fans out, what it locks and hands between
+    /// workers, and the shared state its functions contend on
+    fn format_group_concurrency(&self, group: &CallChainGroup, concurrency: &Concurrency) -> String {
+        if !concurrency.touches_group(group) {
+            return String::new();
+        }
+
+        let mut content = String::new();
+        content.push_str("## Concurrency\n\n");
+
+        for (i, chain) in group.call_chains.iter().enumerate() {
+            let sites = concurrency.sites_on_chain(chain);
+            if sites.is_empty() {
+                continue;
+            }
+
+            let fan_outs = sites.iter().filter(|(_, site)| site.kind.fans_out()).count();
+            let summary = if fan_outs > 0 {
+                format!("fans out at {} point(s)", fan_outs)
+            } else {
+                "runs sequentially".to_string()
+            };
+            content.push_str(&format!("**Path {}: {}** {}\n\n", i + 1, chain.entry_point.signature.display_name(), summary));
+
+            for (method, site) in sites {
+                let warning = if site.held_across_await {
+                    " ⚠️ blocking lock held across an `await`"
+                } else {
+                    ""
+                };
+                content.push_str(&format!("- {}: `{}` in `{}` ({}:{}){}\n",
+                                          site.kind.label(), site.construct, method.display_name(),
+                                          method.file_path.file_name().unwrap_or_default().to_string_lossy(), site.line, warning));
+            }
+            content.push('\n');
+        }
+
+        let contention = concurrency.contention_in_group(group);
+        if !contention.is_empty() {
+            content.push_str("**Shared state**:\n");
+            for contended in contention {
+                let holders: Vec<String> = contended.holders.iter().map(|method| format!("`{}`", method.display_name())).collect();
+                content.push_str(&format!("- `{}` is locked by {}\n", contended.state, holders.join(", ")));
+            }
+            content.push('\n');
+        }
+
+        content
+    }
+
+    /// "Recursive structures" section listing each cluster of mutually recursive methods
+    fn format_recursive_structures(&self, analysis_result: &CallChainAnalysisResult, from_dir: &Path) -> String {
+        let clusters = &analysis_result.call_graph.recursive_clusters;
+        if clusters.is_empty() {
+            return String::new();
+        }
+
+        let mut content = String::from("## Recursive Structures\n\n");
+        content.push_str("Methods in each cluster can call back into one another, directly or indirectly:\n\n");
+
+        for (index, cluster) in clusters.iter().enumerate() {
+            let names: Vec<String> = cluster.members.iter().map(|member| member.display_name()).collect();
+            content.push_str(&format!("### Cluster {}: {}\n\n", index + 1, names.join(" ↔ ")));
+
+            if cluster.crosses_packages() {
+                let packages: Vec<String> = cluster.packages().into_iter().collect();
+                content.push_str(&format!("⚠️ Spans packages: {}\n\n", packages.join(", ")));
+            }
+
+            content.push_str("**Members**:\n");
+            for member in &cluster.members {
+                content.push_str(&format!(
+                    "- [`{}`]({})\n",
+                    member.display_name(),
+                    source_link(from_dir, &member.file_path, self.node_line(analysis_result, member))
+                ));
+            }
+
+            if cluster.entry_edges.is_empty() {
+                content.push_str("\n**Entered from**: nowhere else in the project\n");
+            } else {
+                content.push_str("\n**Entered from**:\n");
+                for edge in &cluster.entry_edges {
+                    content.push_str(&format!(
+                        "- `{}` → `{}` ([line {}]({}))\n",
+                        edge.caller.display_name(),
+                        edge.callee.display_name(),
+                        edge.call_site_line,
+                        source_link(from_dir, &edge.caller.file_path, edge.call_site_line)
+                    ));
+                }
+            }
+
+            let files: Vec<String> = cluster.files.iter().map(|file| file.display().to_string()).collect();
+            content.push_str(&format!("\n**Files**: {}\n\n", files.join(", ")));
+        }
+
+        content
+    }
+
+    /// Find the LLM analysis for a group, if one was made
+    fn analysis_for<'a>(&self, analysis_result: &'a CallChainAnalysisResult, group_id: &str) -> Option<&'a GroupAnalysis> {
+        analysis_result.group_analyses.iter().find(|analysis| analysis.group_id == group_id)
+    }
+
+    /// First line of a method in its source file (1 if unknown)
+    fn node_line(&self, analysis_result: &CallChainAnalysisResult, method: &super::MethodSignature) -> usize {
+        analysis_result.call_graph.nodes.get(method)
+            .map(|node| node.line_range.0)
+            .unwrap_or(1)
+    }
+
+    /// Render an "Examples" section, empty when there are no examples
+    fn format_usage_examples(&self, examples: &[&UsageExample], api_dir: &Path) -> String {
+        if examples.is_empty() {
+            return String::new();
+        }
+
+        let mut content = String::from("### Examples\n\n");
+        for example in examples {
+            content.push_str(&format!("```{}\n{}\n```\n\n", fence_language(&example.file_path), example.snippet));
+            content.push_str(&format!(
+                "*From `{}`{} at [{}:{}]({})*\n\n",
+                example.caller.display_name(),
+                if example.from_test { " (test)" } else { "" },
+                example.file_path.display(),
+                example.line,
+                source_link(api_dir, &example.file_path, example.line)
+            ));
+        }
+        content
+    }
+
+    /// Public, non-test methods grouped by package, in source order
+    fn public_api_by_package<'a>(&self, analysis_result: &'a CallChainAnalysisResult) -> BTreeMap<String, Vec<&'a CallNode>> {
+        let mut packages: BTreeMap<String, Vec<&CallNode>> = BTreeMap::new();
+
+        for node in analysis_result.call_graph.nodes.values() {
+            if node.visibility == "public" && !node.is_test {
+                packages.entry(node.signature.package_name()).or_default().push(node);
+            }
+        }
+
+        for nodes in packages.values_mut() {
+            nodes.sort_by(|a, b| {
+                a.signature.file_path.cmp(&b.signature.file_path)
+                    .then(a.line_range.0.cmp(&b.line_range.0))
+            });
+        }
+
+        packages
+    }
+
+    /// Generate `critical-functions.md`, ranking functions by centrality
+    async fn generate_critical_functions(
+        &self,
+        analysis_result: &CallChainAnalysisResult,
+        output_dir: &Path,
+    ) -> Result<()> {
+        let mut content = String::from("# Critical Functions\n\n");
+        content.push_str(
+            "Functions ranked by how central they are to the call graph: PageRank (how much of the program \
+            ends up calling them), betweenness (how many call paths pass through them), fan-in and fan-out. \
+            Changes here ripple furthest, so these are documented first.\n\n"
+        );
+
+        let critical: Vec<_> = analysis_result.centrality.ranked.iter()
+            .filter(|function| function.score > 0.0)
+            .filter(|function| analysis_result.call_graph.nodes.get(&function.method).is_some_and(|node| !node.is_test))
+            .take(CRITICAL_FUNCTIONS_LISTED)
+            .collect();
+
+        if critical.is_empty() {
+            content.push_str("No calls between functions were found.\n");
+        } else {
+            content.push_str("| Rank | Function | Package | Fan-in | Fan-out | Betweenness | PageRank | Score |\n");
+            content.push_str("|---:|---|---|---:|---:|---:|---:|---:|\n");
+            for (rank, function) in critical.iter().enumerate() {
+                content.push_str(&format!(
+                    "| {} | [`{}`]({}) | {} | {} | {} | {:.3} | {:.4} | {:.2} |\n",
+                    rank + 1,
+                    function.method.display_name(),
+                    source_link(output_dir, &function.method.file_path, self.node_line(analysis_result, &function.method)),
+                    function.method.package_name(),
+                    function.fan_in,
+                    function.fan_out,
+                    function.betweenness,
+                    function.page_rank,
+                    function.score
+                ));
+            }
+            content.push('\n');
+
+            content.push_str("## Packages\n\n");
+            let mut packages: Vec<(&String, &f64)> = analysis_result.centrality.package_scores.iter().collect();
+            packages.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+            for (package, score) in packages {
+                content.push_str(&format!("- **{}**: {:.2}\n", package, score));
+            }
+        }
+
+        std::fs::write(output_dir.join("critical-functions.md"), content)?;
+        Ok(())
+    }
+
+    /// Generate `endpoints.md`, listing every HTTP route with its handler and the execution path it starts
+    async fn generate_endpoint_index(
+        &self,
+        analysis_result: &CallChainAnalysisResult,
+        output_dir: &Path,
+    ) -> Result<()> {
+        let routes = &analysis_result.call_graph.http_routes;
+        if routes.is_empty() {
+            return Ok(());
+        }
+
+        let mut content = String::from("# HTTP Endpoints\n\n");
+        content.push_str("Routes declared with web frameworks, each linked to its handler and the execution path it starts.\n\n");
+        content.push_str("| Method | Path | Handler | Framework | Execution path |\n");
+        content.push_str("|---|---|---|---|---|\n");
+
+        let mut sorted: Vec<_> = routes.iter().collect();
+        sorted.sort_by(|a, b| a.path.cmp(&b.path).then(a.method.cmp(&b.method)));
+        for route in sorted {
+            let handler = &route.handler;
+            let handler_line = analysis_result.call_graph.nodes.get(handler)
+                .map_or(route.line, |node| node.line_range.0);
+            let path = analysis_result.call_chain_groups.iter()
+                .find_map(|group| group.call_chains.iter()
+                    .position(|chain| chain.entry_point.signature == *handler)
+                    .map(|index| format!("[{}, path {}](./groups/{}.md)",
+                                         self.call_chain_grouper.get_group_name(group), index + 1, group.slug)))
+                .unwrap_or_else(|| "*not traced*".to_string());
+            content.push_str(&format!(
+                "| {} | `{}` | [`{}`]({}) | {} | {} |\n",
+                route.method.as_deref().unwrap_or("ANY"),
+                route.path.replace('|', "\\|"),
+                handler.display_name(),
+                source_link(output_dir, &handler.file_path, handler_line),
+                route.framework,
+                path
+            ));
+        }
+
+        std::fs::write(output_dir.join("endpoints.md"), content)?;
+        Ok(())
+    }
+
+    /// Generate `dead-code.md`, listing unused functions by package
+    async fn generate_dead_code_report(
+        &self,
+        analysis_result: &CallChainAnalysisResult,
+        output_dir: &Path,
+    ) -> Result<()> {
+        let report = &analysis_result.dead_code;
+        let mut content = String::from("# Dead Code Report\n\n");
+
+        content.push_str(
+            "Functions that no production entry point (`main` or a public function nothing in the project calls) \
+            can reach. Confidence is lowered when a function may be called through dynamic dispatch, reflection \
+            or from outside the project, so review before deleting anything.\n\n"
+        );
+
+        if report.total() == 0 {
+            content.push_str("No unused functions found.\n");
+            std::fs::write(output_dir.join("dead-code.md"), content)?;
+            return Ok(());
+        }
+
+        let count = |kind: DeadCodeKind| report.by_package.values().flatten().filter(|finding| finding.kind == kind).count();
+        content.push_str(&format!(
+            "- **Private, never called**: {}\n- **Public, only reached from tests**: {}\n- **Unreachable**: {}\n\n",
+            count(DeadCodeKind::UncalledPrivate),
+            count(DeadCodeKind::TestOnly),
+            count(DeadCodeKind::Unreachable)
+        ));
+
+        for (package, findings) in &report.by_package {
+            content.push_str(&format!("## {}\n\n", package));
+            content.push_str("| Function | Finding | Confidence | Why |\n|---|---|---|---|\n");
+
+            for finding in findings {
+                let kind = match finding.kind {
+                    DeadCodeKind::UncalledPrivate => "Private, never called",
+                    DeadCodeKind::TestOnly => "Test-only",
+                    DeadCodeKind::Unreachable => "Unreachable",
+                };
+                let confidence = match finding.confidence {
+                    DeadCodeConfidence::High => "High",
+                    DeadCodeConfidence::Medium => "Medium",
+                    DeadCodeConfidence::Low => "Low",
+                };
+                content.push_str(&format!(
+                    "| [`{}`]({}) | {} | {} | {} |\n",
+                    finding.method.display_name(),
+                    source_link(output_dir, &finding.method.file_path, finding.line),
+                    kind,
+                    confidence,
+                    finding.reasons.join("; ")
+                ));
+            }
+            content.push('\n');
+        }
+
+        std::fs::write(output_dir.join("dead-code.md"), content)?;
+        Ok(())
+    }
+
+    /// Generate a self-contained `graph.html` that renders the call graph without any external assets
+    async fn generate_graph_viewer(
+        &self,
+        analysis_result: &CallChainAnalysisResult,
+        output_dir: &Path,
+    ) -> Result<()> {
+        let call_graph = &analysis_result.call_graph;
+
+        let mut methods: Vec<&CallNode> = call_graph.nodes.values().collect();
+        methods.sort_by(|a, b| {
+            a.signature.file_path.cmp(&b.signature.file_path)
+                .then(a.line_range.0.cmp(&b.line_range.0))
+        });
+        let ids: HashMap<&super::MethodSignature, String> = methods.iter()
+            .enumerate()
+            .map(|(index, node)| (&node.signature, format!("n{}", index)))
+            .collect();
+
+        let mut group_pages: HashMap<&super::MethodSignature, &str> = HashMap::new();
+        for group in &analysis_result.call_chain_groups {
+            for method in &group.all_methods {
+                group_pages.entry(method).or_insert(&group.slug);
+            }
+        }
+
+        let nodes: Vec<serde_json::Value> = methods.iter()
+            .map(|node| {
+                let signature = &node.signature;
+                let doc = if node.visibility == "public" && !node.is_test {
+                    format!("api/{}.md#{}", signature.package_name(), heading_anchor(&signature.display_name()))
+                } else if let Some(slug) = group_pages.get(signature) {
+                    format!("groups/{}.md", slug)
+                } else {
+                    source_link(output_dir, &signature.file_path, node.line_range.0)
+                };
+
+                serde_json::json!({
+                    "id": ids[signature],
+                    "label": signature.display_name(),
+                    "package": signature.package_name(),
+                    "file": signature.file_path.display().to_string(),
+                    "line": node.line_range.0,
+                    "doc": doc,
+                })
+            })
+            .collect();
+
+        let edges: Vec<serde_json::Value> = call_graph.edges.iter()
+            .filter_map(|edge| Some(serde_json::json!({
+                "source": ids.get(&edge.caller)?,
+                "target": ids.get(&edge.callee)?,
+                "call_type": edge.call_type,
+                "confidence": edge.confidence,
+            })))
+            .collect();
+
+        let entry_points: Vec<&String> = analysis_result.entry_points.iter()
+            .filter_map(|entry_point| ids.get(&entry_point.signature))
+            .collect();
+
+        let data = serde_json::json!({
+            "nodes": nodes,
+            "edges": edges,
+            "entry_points": entry_points,
+        });
+
+        // Keep the inlined JSON from closing the script element
+        let data = serde_json::to_string(&data)?.replace("</", "<\\/");
+        let html = GRAPH_VIEWER_TEMPLATE.replace("/*GRAPH_DATA*/null", &data);
+        std::fs::write(output_dir.join("graph.html"), html)?;
+
         Ok(())
     }
 
@@ -351,6 +1377,9 @@ impl CallChainEngine {
         let graph_data = serde_json::json!({
             "nodes": analysis_result.call_graph.nodes.values().collect::<Vec<_>>(),
             "edges": analysis_result.call_graph.edges,
+            "unresolved_calls": analysis_result.call_graph.unresolved_calls,
+            "recursive_clusters": analysis_result.call_graph.recursive_clusters,
+            "http_routes": analysis_result.call_graph.http_routes,
             "entry_points": analysis_result.entry_points,
             "statistics": analysis_result.call_graph.get_statistics()
         });
@@ -362,8 +1391,151 @@ impl CallChainEngine {
     }
 }
 
+/// GitHub-style anchor for a markdown heading
+fn heading_anchor(heading: &str) -> String {
+    heading.to_lowercase()
+        .chars()
+        .filter(|ch| ch.is_alphanumeric() || *ch == ' ' || *ch == '-' || *ch == '_')
+        .map(|ch| if ch == ' ' { '-' } else { ch })
+        .collect()
+}
+
+/// Routes as inline code, e.g. "`GET /orders`, `POST /orders`"
+fn format_routes(routes: &[HttpRoute]) -> String {
+    routes.iter().map(|route| format!("`{}`", route.endpoint())).collect::<Vec<_>>().join(", ")
+}
+
+/// Relative link from a documentation directory to a line in a source file
+fn source_link(from_dir: &Path, file_path: &Path, line: usize) -> String {
+    let base = std::fs::canonicalize(from_dir).unwrap_or_else(|_| from_dir.to_path_buf());
+    let target = std::fs::canonicalize(file_path).unwrap_or_else(|_| file_path.to_path_buf());
+
+    let base_components: Vec<Component> = base.components().collect();
+    let target_components: Vec<Component> = target.components().collect();
+    let common = base_components.iter()
+        .zip(target_components.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut relative = PathBuf::new();
+    for _ in common..base_components.len() {
+        relative.push("..");
+    }
+    for component in &target_components[common..] {
+        relative.push(component);
+    }
+
+    format!("{}#L{}", relative.to_string_lossy().replace('\\', "/"), line)
+}
+
+/// Markdown code fence language for a source file
+fn fence_language(file_path: &Path) -> &'static str {
+    match file_path.extension().and_then(|ext| ext.to_str()) {
+        Some("rs") => "rust",
+        Some("java") => "java",
+        Some("py") => "python",
+        Some("cs") => "csharp",
+        Some("js") | Some("jsx") | Some("mjs") => "javascript",
+        Some("ts") | Some("tsx") => "typescript",
+        _ => "",
+    }
+}
+
 impl Default for CallChainEngine {
     fn default() -> Self {
-        Self::new(6, 1000000) // 6 levels deep, 1M token context
+        Self::new(&AnalysisConfig::default(), 1000000, None) // 1M token context
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    async fn generate(source_dir: &Path, output_dir: &Path) -> CallChainAnalysisResult {
+        let config = Config::default();
+        let mut parser = CodeParser::new(&config.parsing).unwrap();
+        let mut engine = CallChainEngine::new(&config.analysis, 8000, None);
+        let result = engine.analyze_codebase(&[source_dir.to_path_buf()], &mut parser, None).await.unwrap();
+        engine.generate_documentation(&result, output_dir).await.unwrap();
+        result
+    }
+
+    fn page_names(groups_dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(groups_dir).unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[tokio::test]
+    async fn test_group_pages_follow_their_groups() {
+        let project = tempfile::tempdir().unwrap();
+        let (source_dir, output_dir) = (project.path().join("src"), project.path().join("docs"));
+        std::fs::create_dir_all(source_dir.join("jobs")).unwrap();
+        let orders = "pub fn handle() {\n    validate();\n    save();\n}\n\nfn validate() {\n    check();\n}\n\nfn check() {}\n\nfn save() {}\n";
+        std::fs::write(source_dir.join("orders.rs"), orders).unwrap();
+        std::fs::write(source_dir.join("server.rs"), "fn main() {\n    orders::handle();\n}\n\npub fn serve() {\n    orders::handle();\n}\n").unwrap();
+        std::fs::write(source_dir.join("jobs/cleanup.rs"), "pub fn run_cleanup() {\n    purge();\n}\n\nfn purge() {}\n").unwrap();
+
+        let first = generate(&source_dir, &output_dir).await;
+        let slugs: Vec<&str> = first.call_chain_groups.iter().map(|group| group.slug.as_str()).collect();
+        assert_eq!(slugs, vec!["main", "jobs-run-cleanup"]);
+
+        // Without `main`, its group is led by `serve` and gets a new ID; the cleanup job is gone
+        std::fs::write(source_dir.join("server.rs"), "pub fn serve() {\n    orders::handle();\n}\n").unwrap();
+        std::fs::remove_file(source_dir.join("jobs/cleanup.rs")).unwrap();
+        let second = generate(&source_dir, &output_dir).await;
+        assert_ne!(second.call_chain_groups[0].group_id, first.call_chain_groups[0].group_id);
+
+        let groups_dir = output_dir.join("groups");
+        assert_eq!(page_names(&groups_dir), vec!["index.json", "main.md", "src-serve.md"]);
+        let redirect = std::fs::read_to_string(groups_dir.join("main.md")).unwrap();
+        assert!(redirect.starts_with("---\nredirect_to: src-serve.md\n---\n"), "{}", redirect);
+
+        let index: GroupPageIndex = serde_json::from_str(&std::fs::read_to_string(groups_dir.join("index.json")).unwrap()).unwrap();
+        let page = &index.groups[&second.call_chain_groups[0].group_id];
+        assert_eq!(page.aliases, BTreeSet::from(["main".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_store_round_trips() {
+        let project = tempfile::tempdir().unwrap();
+        let (source_dir, output_dir) = (project.path().join("src"), project.path().join("docs"));
+        std::fs::create_dir_all(&source_dir).unwrap();
+        std::fs::write(source_dir.join("server.rs"), "fn main() {\n    orders::handle();\n}\n").unwrap();
+        std::fs::write(source_dir.join("orders.rs"), "pub fn handle() {\n    save();\n}\n\nfn save() {}\n").unwrap();
+
+        let result = generate(&source_dir, &output_dir).await;
+        AnalysisStore::from_result(&result).save(&output_dir).unwrap();
+        let store = AnalysisStore::load(&output_dir).unwrap().unwrap();
+
+        assert_eq!(store.source_dirs, vec![source_dir.clone()]);
+        assert_eq!(store.file_hashes, result.file_hashes);
+        assert_eq!(store.call_graph.nodes.len(), result.call_graph.nodes.len());
+        assert_eq!(store.call_graph.get_callees(&result.entry_points[0].signature).len(), 1);
+        let group_ids = |groups: &[CallChainGroup]| groups.iter().map(|group| group.group_id.clone()).collect::<Vec<_>>();
+        assert_eq!(group_ids(&store.call_chain_groups), group_ids(&result.call_chain_groups));
+        assert_eq!(store.group_analyses.len(), result.group_analyses.len());
+
+        let parser = CodeParser::new(&Config::default().parsing).unwrap();
+        assert!(store.changes_since(&parser.hash_directory(&source_dir).unwrap()).is_empty());
+        std::fs::write(source_dir.join("orders.rs"), "pub fn handle() {}\n").unwrap();
+        let changes = store.changes_since(&parser.hash_directory(&source_dir).unwrap());
+        assert_eq!(changes.modified, vec![source_dir.join("orders.rs")]);
+    }
+
+    #[tokio::test]
+    async fn test_endpoints_link_handler_definitions() {
+        let project = tempfile::tempdir().unwrap();
+        let (source_dir, output_dir) = (project.path().join("src"), project.path().join("docs"));
+        std::fs::create_dir_all(source_dir.join("api")).unwrap();
+        let routes = "pub fn router() -> Router {\n    Router::new()\n        .route(\"/health\", get(health))\n}\n\nasync fn health() -> &'static str {\n    \"ok\"\n}\n";
+        std::fs::write(source_dir.join("api/routes.rs"), routes).unwrap();
+
+        generate(&source_dir, &output_dir).await;
+        let endpoints = std::fs::read_to_string(output_dir.join("endpoints.md")).unwrap();
+        assert!(endpoints.contains("[`health`](../src/api/routes.rs#L6)"), "{}", endpoints);
+    }
+}
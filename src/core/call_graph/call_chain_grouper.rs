@@ -1,21 +1,39 @@
 // src/core/call_graph/call_chain_grouper.rs
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
 use serde::{Serialize, Deserialize};
 
+use crate::config::AnalysisConfig;
 use crate::error::Result;
-use super::{CallChain, MethodSignature};
+use super::{CallChain, EntryPointType, HttpRoute, MethodSignature};
 
-/// Groups call chains that involve the exact same set of source files
-pub struct CallChainGrouper;
+/// Longest slug used as a group page file name
+const MAX_SLUG_LENGTH: usize = 60;
+
+/// Share of chain similarity that comes from files; the rest comes from methods
+const FILE_SIMILARITY_WEIGHT: f64 = 0.6;
+
+/// Clusters call chains that touch similar files and methods
+pub struct CallChainGrouper {
+    /// Minimum average similarity for a chain to join a group
+    similarity_threshold: f64,
+    /// Maximum number of chains in one group
+    max_group_size: usize,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CallChainGroup {
     /// Unique identifier for this group
     pub group_id: String,
-    /// All call chains in this group
+    /// Readable name derived from the group's main entry point
+    #[serde(default)]
+    pub name: String,
+    /// File name of the group's page (without `.md`), unique among groups
+    #[serde(default)]
+    pub slug: String,
+    /// All call chains in this group, the most representative first
     pub call_chains: Vec<CallChain>,
-    /// The exact set of files involved in all chains
+    /// Every file involved in any of the chains
     pub involved_files: HashSet<PathBuf>,
     /// Combined complexity score for the group
     pub total_complexity: u32,
@@ -25,41 +43,181 @@ pub struct CallChainGroup {
     pub all_methods: HashSet<MethodSignature>,
 }
 
+/// A chain's files and methods as sorted feature ids
+struct ChainFeatures {
+    files: Vec<usize>,
+    methods: Vec<usize>,
+}
+
 impl CallChainGrouper {
-    pub fn new() -> Self {
-        Self
+    pub fn new(config: &AnalysisConfig) -> Self {
+        Self {
+            similarity_threshold: config.group_similarity,
+            max_group_size: config.max_group_size.max(1),
+        }
     }
 
-    /// Group call chains by their exact file sets
-    pub fn group_call_chains(&self, call_chains: Vec<CallChain>) -> Result<Vec<CallChainGroup>> {
-        let mut file_set_to_chains: HashMap<Vec<PathBuf>, Vec<CallChain>> = HashMap::new();
+    /// Cluster call chains by weighted Jaccard similarity of their files and, to a lesser
+    /// extent, their methods.
+    ///
+    /// Features shared by many chains (utility files, logging helpers) are weighted
+    /// down by inverse chain frequency, so they don't pull unrelated chains together.
+    /// Chains are visited in entry point order and join the group they are most
+    /// similar to on average, as long as it is similar enough and not full.
+    pub fn group_call_chains(&self, call_chains: Vec<CallChain>, routes: &[HttpRoute]) -> Result<Vec<CallChainGroup>> {
+        let mut call_chains = call_chains;
+        call_chains.sort_by_cached_key(entry_point_key);
 
-        // Group chains by exact file sets
-        for chain in call_chains {
-            // Convert HashSet to sorted Vec for use as HashMap key
-            let mut file_vec: Vec<PathBuf> = chain.involved_files.iter().cloned().collect();
-            file_vec.sort();
+        let (features, weights) = self.features(&call_chains);
 
-            file_set_to_chains.entry(file_vec).or_insert_with(Vec::new).push(chain);
+        let mut clusters: Vec<Vec<usize>> = Vec::new();
+        for (index, chain_features) in features.iter().enumerate() {
+            let best = clusters.iter()
+                .enumerate()
+                .filter(|(_, members)| members.len() < self.max_group_size)
+                .map(|(cluster, members)| {
+                    let total: f64 = members.iter()
+                        .map(|&member| similarity(chain_features, &features[member], &weights))
+                        .sum();
+                    (cluster, total / members.len() as f64)
+                })
+                .filter(|(_, similarity)| *similarity >= self.similarity_threshold)
+                .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)));
+
+            match best {
+                Some((cluster, _)) => clusters[cluster].push(index),
+                None => clusters.push(vec![index]),
+            }
+        }
+
+        // The medoid leads its group: it names the group and determines its ID.
+        // Production entry points lead over tests.
+        for members in &mut clusters {
+            let best_rank = members.iter().map(|&member| entry_rank(&call_chains[member])).min().unwrap_or(0);
+            let candidates: Vec<usize> = members.iter().copied()
+                .filter(|&member| entry_rank(&call_chains[member]) == best_rank)
+                .collect();
+            let anchor = medoid(&candidates, members, &features, &weights);
+            members.retain(|&member| member != anchor);
+            members.insert(0, anchor);
         }
+        let group_ids: Vec<String> = clusters.iter()
+            .map(|members| self.generate_group_id(&call_chains[members[0]]))
+            .collect();
 
-        // Convert groups to structured format
+        let mut slots: Vec<Option<CallChain>> = call_chains.into_iter().map(Some).collect();
         let mut groups = Vec::new();
-        for (file_vec, chains) in file_set_to_chains {
-            let file_set: HashSet<PathBuf> = file_vec.into_iter().collect();
-            let group = self.create_group(file_set, chains)?;
-            groups.push(group);
+        for (members, group_id) in clusters.into_iter().zip(group_ids) {
+            let chains: Vec<CallChain> = members.into_iter()
+                .filter_map(|member| slots[member].take())
+                .collect();
+            groups.push(self.create_group(group_id, chains)?);
         }
 
+        self.name_groups(&mut groups, routes);
+
         // Sort groups by total complexity (most complex first)
-        groups.sort_by(|a, b| b.total_complexity.cmp(&a.total_complexity));
+        groups.sort_by(|a, b| b.total_complexity.cmp(&a.total_complexity).then(a.group_id.cmp(&b.group_id)));
 
         Ok(groups)
     }
 
-    /// Create a structured group from file set and chains
-    fn create_group(&self, file_set: HashSet<PathBuf>, chains: Vec<CallChain>) -> Result<CallChainGroup> {
-        let group_id = self.generate_group_id(&file_set);
+    /// Name each group after its leading entry point (its HTTP route, if it serves one)
+    /// and give it a unique slug. Slugs depend only on the leading entry point, so page
+    /// paths stay put when other chains join or leave the group.
+    fn name_groups(&self, groups: &mut [CallChainGroup], routes: &[HttpRoute]) {
+        let mut order: Vec<usize> = (0..groups.len()).collect();
+        order.sort_by(|&a, &b| groups[a].group_id.cmp(&groups[b].group_id));
+
+        let mut taken = BTreeSet::new();
+        for index in order {
+            let group = &mut groups[index];
+            let Some(anchor) = group.call_chains.first().map(|chain| &chain.entry_point) else {
+                continue;
+            };
+            let signature = &anchor.signature;
+
+            let route = routes.iter().find(|route| route.handler == *signature);
+            let (title, slug_source) = match (route, &anchor.entry_type) {
+                (Some(route), _) => (route.endpoint(), route.endpoint()),
+                (None, EntryPointType::Main) => ("Main".to_string(), "main".to_string()),
+                (None, EntryPointType::Test) => {
+                    let name = signature.method_name.trim_start_matches("test_").trim_start_matches("test");
+                    (format!("Test: {}", sentence(name)), format!("test {}", name))
+                }
+                (None, _) => match &signature.class_name {
+                    Some(class) => (
+                        format!("{}: {}", class, sentence(&signature.method_name)),
+                        format!("{} {}", class, signature.method_name),
+                    ),
+                    None => (
+                        format!("{}: {}", signature.package_name(), sentence(&signature.method_name)),
+                        format!("{} {}", signature.package_name(), signature.method_name),
+                    ),
+                },
+            };
+
+            group.name = match group.call_chains.len() - 1 {
+                0 => title,
+                1 => format!("{} and 1 related flow", title),
+                others => format!("{} and {} related flows", title, others),
+            };
+
+            let base = slugify(&slug_source);
+            let mut slug = base.clone();
+            let mut suffix = 2;
+            while !taken.insert(slug.clone()) {
+                slug = format!("{}-{}", base, suffix);
+                suffix += 1;
+            }
+            group.slug = slug;
+        }
+    }
+
+    /// Feature ids per chain, and each feature's weight: `ln(1 + chains / chains containing it)`
+    fn features(&self, call_chains: &[CallChain]) -> (Vec<ChainFeatures>, Vec<f64>) {
+        let mut ids: HashMap<String, usize> = HashMap::new();
+        let mut chain_counts: Vec<usize> = Vec::new();
+
+        let features = call_chains.iter()
+            .map(|chain| {
+                let mut feature_ids = |keys: Vec<String>| {
+                    let mut chain_features: Vec<usize> = keys.into_iter()
+                        .map(|key| {
+                            let next = ids.len();
+                            *ids.entry(key).or_insert(next)
+                        })
+                        .collect();
+                    chain_features.sort_unstable();
+                    chain_features.dedup();
+
+                    chain_counts.resize(ids.len(), 0);
+                    for &feature in &chain_features {
+                        chain_counts[feature] += 1;
+                    }
+                    chain_features
+                };
+
+                ChainFeatures {
+                    files: feature_ids(chain.involved_files.iter().map(|file| format!("file:{}", file.display())).collect()),
+                    methods: feature_ids(chain.steps.iter().map(|step| format!("method:{}", step.method.to_unique_string())).collect()),
+                }
+            })
+            .collect();
+
+        let total = call_chains.len().max(1) as f64;
+        let weights = chain_counts.iter()
+            .map(|&count| (1.0 + total / count.max(1) as f64).ln())
+            .collect();
+
+        (features, weights)
+    }
+
+    /// Create a structured group from its chains
+    fn create_group(&self, group_id: String, chains: Vec<CallChain>) -> Result<CallChainGroup> {
+        let involved_files: HashSet<PathBuf> = chains.iter()
+            .flat_map(|chain| chain.involved_files.iter().cloned())
+            .collect();
 
         let total_complexity: u32 = chains.iter()
             .map(|chain| chain.complexity_score)
@@ -89,26 +247,23 @@ impl CallChainGrouper {
 
         Ok(CallChainGroup {
             group_id,
+            name: String::new(),
+            slug: String::new(),
             call_chains: chains,
-            involved_files: file_set,
+            involved_files,
             total_complexity,
             primary_entry_points,
             all_methods,
         })
     }
 
-    /// Generate a unique ID for the group based on file set
-    fn generate_group_id(&self, file_set: &HashSet<PathBuf>) -> String {
+    /// Generate a group ID from the group's most representative entry point, so the ID
+    /// survives chains joining or leaving the group
+    fn generate_group_id(&self, anchor: &CallChain) -> String {
         use sha2::{Sha256, Digest};
 
-        // Sort files for consistent hashing
-        let mut sorted_files: Vec<_> = file_set.iter().collect();
-        sorted_files.sort();
-
         let mut hasher = Sha256::new();
-        for file in sorted_files {
-            hasher.update(file.to_string_lossy().as_bytes());
-        }
+        hasher.update(entry_point_key(anchor).as_bytes());
 
         let hash = format!("{:x}", hasher.finalize());
         format!("group-{}", &hash[..8])
@@ -116,6 +271,10 @@ impl CallChainGrouper {
 
     /// Get a human-readable name for the group
     pub fn get_group_name(&self, group: &CallChainGroup) -> String {
+        if !group.name.is_empty() {
+            return group.name.clone();
+        }
+
         // Try to use primary entry point names
         if !group.primary_entry_points.is_empty() {
             let entry_names: Vec<String> = group.primary_entry_points.iter()
@@ -167,6 +326,217 @@ pub struct GroupingStats {
 
 impl Default for CallChainGrouper {
     fn default() -> Self {
-        Self::new()
+        Self::new(&AnalysisConfig::default())
+    }
+}
+
+/// Identifies a chain by its entry point, independent of where the project is checked out
+pub(super) fn entry_point_key(chain: &CallChain) -> String {
+    let signature = &chain.entry_point.signature;
+    format!("{}/{}", signature.package_name(), signature.to_unique_string())
+}
+
+/// Similarity of two chains (0.0 to 1.0)
+fn similarity(a: &ChainFeatures, b: &ChainFeatures, weights: &[f64]) -> f64 {
+    FILE_SIMILARITY_WEIGHT * weighted_jaccard(&a.files, &b.files, weights)
+        + (1.0 - FILE_SIMILARITY_WEIGHT) * weighted_jaccard(&a.methods, &b.methods, weights)
+}
+
+/// Split an identifier (`placeOrder`, `place_order`, `PlaceOrder`) into lower-case words
+fn words(identifier: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+
+    for c in identifier.chars() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            previous_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && previous_lower && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
     }
-}
\ No newline at end of file
+
+    words
+}
+
+/// `place_order` -> "Place order"
+fn sentence(identifier: &str) -> String {
+    let text = words(identifier).join(" ");
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => identifier.to_string(),
+    }
+}
+
+/// `GET /api/orders/{id}` -> `get-api-orders-id`, at most `MAX_SLUG_LENGTH` characters
+fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    for word in words(text) {
+        if slug.len() + word.len() + 1 > MAX_SLUG_LENGTH && !slug.is_empty() {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word);
+    }
+
+    if slug.is_empty() { "group".to_string() } else { slug }
+}
+
+/// Weighted Jaccard similarity of two sorted feature lists
+fn weighted_jaccard(a: &[usize], b: &[usize], weights: &[f64]) -> f64 {
+    let (mut i, mut j) = (0, 0);
+    let (mut shared, mut union) = (0.0, 0.0);
+
+    while i < a.len() || j < b.len() {
+        match (a.get(i), b.get(j)) {
+            (Some(x), Some(y)) if x == y => {
+                shared += weights[*x];
+                union += weights[*x];
+                i += 1;
+                j += 1;
+            }
+            (Some(x), Some(y)) if x < y => {
+                union += weights[*x];
+                i += 1;
+            }
+            (Some(x), None) => {
+                union += weights[*x];
+                i += 1;
+            }
+            (_, Some(y)) => {
+                union += weights[*y];
+                j += 1;
+            }
+            (None, None) => break,
+        }
+    }
+
+    if union == 0.0 { 0.0 } else { shared / union }
+}
+
+/// How strongly a chain's entry point should lead its group (lower leads)
+fn entry_rank(chain: &CallChain) -> u8 {
+    match chain.entry_point.entry_type {
+        EntryPointType::Main => 0,
+        EntryPointType::HttpHandler => 1,
+        EntryPointType::ExternalInterface => 2,
+        EntryPointType::PublicApi => 3,
+        EntryPointType::Test => 4,
+    }
+}
+
+/// The candidate most similar to the rest of its group; ties go to the earliest entry point
+fn medoid(candidates: &[usize], members: &[usize], features: &[ChainFeatures], weights: &[f64]) -> usize {
+    candidates.iter()
+        .map(|&member| {
+            let total: f64 = members.iter()
+                .filter(|&&other| other != member)
+                .map(|&other| similarity(&features[member], &features[other], weights))
+                .sum();
+            (member, total)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(member, _)| member)
+        .unwrap_or(members[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::call_graph::{CallType, EntryPoint, EntryPointType};
+    use crate::core::call_graph::call_chain_tracer::CallStep;
+
+    /// A chain from `entry` through the given methods, each `file::method`
+    fn chain(entry: &str, methods: &[&str]) -> CallChain {
+        let signature = |spec: &str| {
+            let (file, name) = spec.split_once("::").unwrap();
+            MethodSignature::new(PathBuf::from(format!("/p/src/{}.rs", file)), name.to_string(), None, None, String::new())
+        };
+        let steps: Vec<CallStep> = std::iter::once(entry).chain(methods.iter().copied())
+            .enumerate()
+            .map(|(depth, spec)| CallStep {
+                method: signature(spec),
+                depth,
+                call_site_line: 1,
+                call_type: CallType::Direct,
+                callees: Vec::new(),
+            })
+            .collect();
+
+        CallChain {
+            entry_point: EntryPoint {
+                signature: signature(entry),
+                entry_type: EntryPointType::ExternalInterface,
+                confidence: 0.8,
+                reasoning: String::new(),
+                routes: vec![],
+            },
+            involved_files: steps.iter().map(|step| step.method.file_path.clone()).collect(),
+            steps,
+            has_cycles: false,
+            complexity_score: 1,
+            truncated: false,
+        }
+    }
+
+    fn entry_names(groups: &[CallChainGroup]) -> Vec<Vec<String>> {
+        let mut names: Vec<Vec<String>> = groups.iter()
+            .map(|group| {
+                let mut names: Vec<String> = group.call_chains.iter().map(|c| c.entry_point.signature.method_name.clone()).collect();
+                names.sort();
+                names
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_groups_similar_chains_despite_utility_files() {
+        let chains = vec![
+            chain("orders::place", &["orders::validate", "orders::save", "util::log"]),
+            chain("orders::amend", &["orders::validate", "orders::save", "util::log", "util::clock"]),
+            chain("users::signup", &["users::hash", "users::save", "util::log"]),
+            chain("users::login", &["users::hash", "users::check", "util::log"]),
+        ];
+
+        let groups = CallChainGrouper::default().group_call_chains(chains.clone(), &[]).unwrap();
+        assert_eq!(entry_names(&groups), [vec!["amend", "place"], vec!["login", "signup"]]);
+
+        // A new, unrelated chain leaves existing group IDs alone
+        let mut ids: Vec<String> = groups.iter().map(|group| group.group_id.clone()).collect();
+        let mut more = chains;
+        more.push(chain("billing::invoice", &["billing::total", "util::log"]));
+        let regrouped = CallChainGrouper::default().group_call_chains(more, &[]).unwrap();
+        let mut new_ids: Vec<String> = regrouped.iter().map(|group| group.group_id.clone()).collect();
+        ids.sort();
+        new_ids.retain(|id| ids.contains(id));
+        new_ids.sort();
+        assert_eq!(regrouped.len(), 3);
+        assert_eq!(new_ids, ids);
+    }
+
+    #[test]
+    fn test_respects_max_group_size() {
+        let config = AnalysisConfig { max_group_size: 2, ..AnalysisConfig::default() };
+        let chains: Vec<CallChain> = ["a", "b", "c"].iter()
+            .map(|name| chain(&format!("orders::{}", name), &["orders::validate", "orders::save"]))
+            .collect();
+
+        let groups = CallChainGrouper::new(&config).group_call_chains(chains, &[]).unwrap();
+        assert_eq!(entry_names(&groups), [vec!["a", "b"], vec!["c"]]);
+    }
+}
@@ -1,9 +1,10 @@
 // src/core/call_graph/call_chain_tracer.rs
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet};
 use serde::{Serialize, Deserialize};
 
+use crate::config::AnalysisConfig;
 use crate::error::Result;
-use super::{CallGraph, MethodSignature, EntryPoint, CallType};
+use super::{CallGraph, CallEdge, MethodSignature, EntryPoint, CallType};
 
 /// Traces execution paths from entry points through the call graph
 pub struct CallChainTracer {
@@ -11,6 +12,10 @@ pub struct CallChainTracer {
     max_depth: usize,
     /// Whether to stop at cycles
     stop_at_cycles: bool,
+    /// Maximum number of callees followed from a single method
+    max_fan_out: usize,
+    /// Maximum number of distinct paths traced from one entry point
+    max_paths: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +30,9 @@ pub struct CallChain {
     pub has_cycles: bool,
     /// Total complexity score for the chain
     pub complexity_score: u32,
+    /// Whether the depth, fan-out or path limits cut the trace short
+    #[serde(default)]
+    pub truncated: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,53 +50,232 @@ pub struct CallStep {
 }
 
 impl CallChainTracer {
-    pub fn new(max_depth: usize) -> Self {
+    pub fn new(config: &AnalysisConfig) -> Self {
         Self {
-            max_depth,
-            stop_at_cycles: true,
+            max_depth: config.max_call_depth,
+            stop_at_cycles: config.stop_at_cycles,
+            max_fan_out: config.max_fan_out.max(1),
+            max_paths: config.max_paths.max(1),
         }
     }
 
     /// Trace call chains from all entry points
     pub fn trace_all_chains(&self, call_graph: &CallGraph, entry_points: &[EntryPoint]) -> Result<Vec<CallChain>> {
+        let outgoing = outgoing_edges(call_graph);
         let mut all_chains = Vec::new();
 
         for entry_point in entry_points {
-            let chains = self.trace_from_entry_point(call_graph, entry_point)?;
-            all_chains.extend(chains);
+            all_chains.push(self.trace(call_graph, &outgoing, entry_point));
         }
 
         Ok(all_chains)
     }
 
-    /// Trace call chains from a specific entry point
-    pub fn trace_from_entry_point(&self, call_graph: &CallGraph, entry_point: &EntryPoint) -> Result<Vec<CallChain>> {
-        // Simple implementation - create one basic chain per entry point
-        let mut involved_files = HashSet::new();
-        involved_files.insert(entry_point.signature.file_path.clone());
-
-        let entry_step = CallStep {
-            method: entry_point.signature.clone(),
-            depth: 0,
-            call_site_line: 0,
-            call_type: CallType::Direct,
-            callees: call_graph.get_callees(&entry_point.signature).into_iter().cloned().collect(),
+    /// Depth-first walk from the entry point, recording steps in execution order
+    fn trace<'a>(
+        &self,
+        call_graph: &'a CallGraph,
+        outgoing: &HashMap<&'a MethodSignature, Vec<&'a CallEdge>>,
+        entry_point: &'a EntryPoint,
+    ) -> CallChain {
+        let mut trace = Trace {
+            steps: Vec::new(),
+            path: Vec::new(),
+            expanded: HashSet::new(),
+            paths: 0,
+            has_cycles: false,
+            truncated: false,
         };
 
-        let chain = CallChain {
+        self.visit(outgoing, &entry_point.signature, 0, 0, CallType::Direct, &mut trace);
+
+        let mut involved_files = HashSet::new();
+        let mut counted = HashSet::new();
+        let mut complexity_score = 0u32;
+        for step in &trace.steps {
+            involved_files.insert(step.method.file_path.clone());
+            if counted.insert(&step.method) {
+                let node_complexity = call_graph.nodes.get(&step.method)
+                    .map(|node| node.complexity_score)
+                    .unwrap_or(1);
+                complexity_score = complexity_score.saturating_add(node_complexity);
+            }
+        }
+
+        CallChain {
             entry_point: entry_point.clone(),
-            steps: vec![entry_step],
+            steps: trace.steps,
             involved_files,
-            has_cycles: false,
-            complexity_score: 10, // Placeholder
-        };
+            has_cycles: trace.has_cycles,
+            complexity_score,
+            truncated: trace.truncated,
+        }
+    }
+
+    fn visit<'a>(
+        &self,
+        outgoing: &HashMap<&'a MethodSignature, Vec<&'a CallEdge>>,
+        method: &'a MethodSignature,
+        depth: usize,
+        call_site_line: usize,
+        call_type: CallType,
+        trace: &mut Trace<'a>,
+    ) {
+        let edges = outgoing.get(method).map(Vec::as_slice).unwrap_or_default();
+
+        trace.steps.push(CallStep {
+            method: method.clone(),
+            depth,
+            call_site_line,
+            call_type,
+            callees: edges.iter().map(|edge| edge.callee.clone()).collect(),
+        });
+
+        if edges.is_empty() {
+            trace.paths += 1;
+            return;
+        }
+
+        // Checked before marking the method expanded, so a shallower path can still expand it
+        if depth >= self.max_depth {
+            trace.truncated = true;
+            trace.paths += 1;
+            return;
+        }
+
+        // A method reached again through another path is recorded but not re-expanded
+        if !trace.expanded.insert(method) {
+            trace.paths += 1;
+            return;
+        }
+
+        if edges.len() > self.max_fan_out {
+            trace.truncated = true;
+        }
 
-        Ok(vec![chain])
+        trace.path.push(method);
+        for edge in edges.iter().take(self.max_fan_out) {
+            if trace.paths >= self.max_paths {
+                trace.truncated = true;
+                break;
+            }
+
+            if trace.path.contains(&&edge.callee) {
+                trace.has_cycles = true;
+                if self.stop_at_cycles {
+                    trace.steps.push(CallStep {
+                        method: edge.callee.clone(),
+                        depth: depth + 1,
+                        call_site_line: edge.call_site_line,
+                        call_type: edge.call_type.clone(),
+                        callees: Vec::new(),
+                    });
+                    trace.paths += 1;
+                    continue;
+                }
+            }
+
+            self.visit(outgoing, &edge.callee, depth + 1, edge.call_site_line, edge.call_type.clone(), trace);
+        }
+        trace.path.pop();
     }
 }
 
+/// Mutable state for a single entry point's trace
+struct Trace<'a> {
+    steps: Vec<CallStep>,
+    /// Methods on the current call path, used for cycle detection
+    path: Vec<&'a MethodSignature>,
+    /// Methods whose callees have already been traced
+    expanded: HashSet<&'a MethodSignature>,
+    /// Number of complete paths (reaching a leaf or a limit) so far
+    paths: usize,
+    has_cycles: bool,
+    truncated: bool,
+}
+
+/// Outgoing edges per caller, one per distinct callee, in call-site order
+fn outgoing_edges(call_graph: &CallGraph) -> HashMap<&MethodSignature, Vec<&CallEdge>> {
+    let mut outgoing: HashMap<&MethodSignature, Vec<&CallEdge>> = HashMap::new();
+
+    for edge in &call_graph.edges {
+        outgoing.entry(&edge.caller).or_default().push(edge);
+    }
+
+    for edges in outgoing.values_mut() {
+        edges.sort_by_key(|edge| edge.call_site_line);
+        let mut seen = HashSet::new();
+        edges.retain(|edge| seen.insert(&edge.callee));
+    }
+
+    outgoing
+}
+
 impl Default for CallChainTracer {
     fn default() -> Self {
-        Self::new(6)
+        Self::new(&AnalysisConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::{self, entry_point, method};
+    use crate::core::call_graph::EntryPointType;
+
+    fn signature(name: &str) -> MethodSignature {
+        method(&format!("src/{}.rs", name), None, name)
+    }
+
+    fn graph(calls: &[(&str, &str, usize)]) -> CallGraph {
+        let calls: Vec<_> = calls.iter().map(|(caller, callee, line)| (signature(caller), signature(callee), *line)).collect();
+        let calls: Vec<_> = calls.iter().map(|(caller, callee, line)| (caller, callee, *line)).collect();
+        let mut graph = test_support::graph(&[], &calls);
+        for node in graph.nodes.values_mut() {
+            node.complexity_score = 3;
+        }
+        graph
     }
-}
\ No newline at end of file
+
+    fn entry(name: &str) -> EntryPoint {
+        entry_point(&signature(name), EntryPointType::Main)
+    }
+
+    #[test]
+    fn test_trace_follows_calls_in_order_and_stops_at_cycles() {
+        let graph = graph(&[("main", "load", 3), ("main", "save", 5), ("load", "parse", 8), ("parse", "load", 12)]);
+        let chain = &CallChainTracer::default().trace_all_chains(&graph, &[entry("main")]).unwrap()[0];
+
+        let steps: Vec<(&str, usize, usize)> = chain.steps.iter()
+            .map(|step| (step.method.method_name.as_str(), step.depth, step.call_site_line))
+            .collect();
+        assert_eq!(steps, vec![("main", 0, 0), ("load", 1, 3), ("parse", 2, 8), ("load", 3, 12), ("save", 1, 5)]);
+        assert!(chain.has_cycles);
+        assert!(!chain.truncated);
+        assert_eq!(chain.involved_files.len(), 4);
+        assert_eq!(chain.complexity_score, 12);
+    }
+
+    #[test]
+    fn test_trace_respects_depth_and_fan_out() {
+        let graph = graph(&[("a", "b", 1), ("b", "c", 2), ("c", "d", 3), ("a", "e", 4)]);
+        let tracer = CallChainTracer::new(&AnalysisConfig { max_call_depth: 2, max_fan_out: 1, ..AnalysisConfig::default() });
+        let chain = &tracer.trace_all_chains(&graph, &[entry("a")]).unwrap()[0];
+
+        let names: Vec<&str> = chain.steps.iter().map(|step| step.method.method_name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "c"]);
+        assert!(chain.truncated);
+    }
+
+    #[test]
+    fn test_shallower_path_expands_method_cut_off_at_max_depth() {
+        let graph = graph(&[("main", "a", 1), ("main", "c", 2), ("a", "b", 3), ("b", "c", 4), ("c", "d", 5)]);
+        let tracer = CallChainTracer::new(&AnalysisConfig { max_call_depth: 3, ..AnalysisConfig::default() });
+        let chain = &tracer.trace_all_chains(&graph, &[entry("main")]).unwrap()[0];
+
+        let steps: Vec<(&str, usize)> = chain.steps.iter()
+            .map(|step| (step.method.method_name.as_str(), step.depth))
+            .collect();
+        assert_eq!(steps, vec![("main", 0), ("a", 1), ("b", 2), ("c", 3), ("c", 1), ("d", 2)]);
+    }
+}
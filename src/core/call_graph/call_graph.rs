@@ -1,10 +1,15 @@
 // src/core/call_graph/call_graph.rs - Fixed call graph building
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use serde::{Serialize, Deserialize};
+use tracing::debug;
 
 use crate::error::Result;
 use super::super::{ParsedFile, ParsedModule};
+use super::call_resolver::{class_name_for_module, CallResolver, Resolution};
+use super::http_routes::{HttpRoute, HttpRouteLinker};
+use super::recursive_clusters::{find_recursive_clusters, RecursiveCluster};
+use super::type_hierarchy::TypeHierarchy;
 
 /// Unique identifier for a method/function in the codebase
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -62,6 +67,49 @@ impl MethodSignature {
             self.method_name.clone()
         }
     }
+
+    /// Package this method belongs to (used to group documentation pages)
+    pub fn package_name(&self) -> String {
+        package_name_for_path(&self.file_path).unwrap_or_else(|| "root".to_string())
+    }
+}
+
+/// Derive a package name from a source file path
+pub fn package_name_for_path(file_path: &Path) -> Option<String> {
+    let path_str = file_path.to_string_lossy();
+
+    if let Some(src_index) = path_str.find("/src/") {
+        let after_src = &path_str[src_index + 5..];
+        if let Some(first_slash) = after_src.find('/') {
+            let package = &after_src[..first_slash];
+            if package != "bin" && package != "test" && package != "tests" {
+                return Some(package.to_string());
+            }
+        }
+    }
+
+    // Fallback to parent directory name
+    file_path.parent()
+        .and_then(|p| p.file_name())
+        .and_then(|name| name.to_str())
+        .map(|s| s.to_string())
+}
+
+/// Check whether a file lives in a test location or follows a test naming convention
+pub fn is_test_path(file_path: &Path) -> bool {
+    let path_str = file_path.to_string_lossy().replace('\\', "/");
+    if path_str.contains("/tests/") || path_str.contains("/test/") || path_str.contains("/__tests__/")
+        || path_str.starts_with("tests/") || path_str.starts_with("test/") {
+        return true;
+    }
+
+    let file_name = file_path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let stem = file_name.split('.').next().unwrap_or_default();
+
+    stem.starts_with("test_") || stem.ends_with("_test") || stem.ends_with("Test") || stem.ends_with("Tests")
+        || file_name.contains(".test.") || file_name.contains(".spec.")
 }
 
 /// Node in the call graph representing a method/function
@@ -74,6 +122,9 @@ pub struct CallNode {
     pub documentation: Option<String>,
     /// Estimated complexity (lines, cyclomatic complexity, etc.)
     pub complexity_score: u32,
+    /// Whether this method is a test (test attribute/annotation, test file or naming)
+    #[serde(default)]
+    pub is_test: bool,
 }
 
 /// Edge in the call graph representing a method call
@@ -87,6 +138,25 @@ pub struct CallEdge {
     pub call_site_line: usize,
     /// Type of call (direct, indirect, async, etc.)
     pub call_type: CallType,
+    /// How certain the resolution of the callee is (0.0 to 1.0)
+    #[serde(default)]
+    pub confidence: f32,
+}
+
+/// Call site that could not be resolved to a single method in the graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnresolvedCall {
+    /// Method making the call
+    pub caller: MethodSignature,
+    /// Name of the called function or method
+    pub callee_name: String,
+    /// Receiver expression, if any
+    pub receiver: Option<String>,
+    /// Line number where the call occurs
+    pub call_site_line: usize,
+    pub call_type: CallType,
+    /// Project methods the call may refer to (empty for calls into external code)
+    pub candidates: Vec<MethodSignature>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -97,21 +167,36 @@ pub enum CallType {
     Conditional,   // inside if/match
     Loop,          // inside loop
     Try,           // inside try/catch
+    Dynamic,       // through an interface/trait, may dispatch to any implementation
+    CrossService,  // HTTP request matched to the route handler that serves it
 }
 
 /// Complete call graph for the codebase
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CallGraph {
     /// All nodes (methods) in the graph
+    #[serde(with = "signature_map")]
     pub nodes: HashMap<MethodSignature, CallNode>,
     /// All edges (calls) in the graph
     pub edges: Vec<CallEdge>,
-    /// Adjacency list for efficient traversal
+    /// Adjacency list for efficient traversal (rebuilt from the edges after loading)
+    #[serde(skip)]
     adjacency_list: HashMap<MethodSignature, Vec<MethodSignature>>,
     /// Reverse adjacency list (who calls this method)
+    #[serde(skip)]
     reverse_adjacency: HashMap<MethodSignature, Vec<MethodSignature>>,
-    /// Detected cycles in the call graph
-    pub cycles: Vec<Vec<MethodSignature>>,
+    /// Groups of mutually recursive methods, each reported once
+    #[serde(default)]
+    pub recursive_clusters: Vec<RecursiveCluster>,
+    /// Calls that were ambiguous or point outside the project
+    #[serde(default)]
+    pub unresolved_calls: Vec<UnresolvedCall>,
+    /// Which types implement/extend which
+    #[serde(default)]
+    pub type_hierarchy: TypeHierarchy,
+    /// HTTP routes declared by server-side handlers
+    #[serde(default)]
+    pub http_routes: Vec<HttpRoute>,
 }
 
 impl CallGraph {
@@ -121,7 +206,10 @@ impl CallGraph {
             edges: Vec::new(),
             adjacency_list: HashMap::new(),
             reverse_adjacency: HashMap::new(),
-            cycles: Vec::new(),
+            recursive_clusters: Vec::new(),
+            unresolved_calls: Vec::new(),
+            type_hierarchy: TypeHierarchy::default(),
+            http_routes: Vec::new(),
         }
     }
 
@@ -134,20 +222,40 @@ impl CallGraph {
             graph.extract_methods_from_file(file)?;
         }
 
-        println!("Extracted {} methods", graph.nodes.len());
+        debug!("Extracted {} methods", graph.nodes.len());
+
+        graph.type_hierarchy = TypeHierarchy::build(files);
 
         // Second pass: extract all method calls
-        for file in files {
-            graph.extract_calls_from_file(file)?;
+        let mut edges = Vec::new();
+        let mut self_calls = Vec::new();
+        let mut unresolved_calls = Vec::new();
+        {
+            let resolver = CallResolver::new(&graph.nodes, &graph.type_hierarchy, files);
+            for file in files {
+                graph.extract_calls_from_file(file, &resolver, &mut edges, &mut self_calls, &mut unresolved_calls);
+            }
+        }
+        for edge in edges {
+            graph.add_edge(edge);
+        }
+        graph.unresolved_calls = unresolved_calls;
+
+        // Link HTTP client calls to the route handlers that serve them, across languages
+        let linker = HttpRouteLinker::new();
+        graph.http_routes = linker.detect_routes(&graph, files);
+        for edge in linker.link_calls(&graph, files, &graph.http_routes) {
+            graph.add_edge(edge);
         }
 
-        println!("Found {} call edges", graph.edges.len());
+        debug!("Found {} call edges ({} unresolved calls)", graph.edges.len(), graph.unresolved_calls.len());
 
         // Build adjacency lists
         graph.build_adjacency_lists();
 
-        // Detect cycles
-        graph.detect_cycles();
+        // Detect recursive clusters; self-calls only matter here, so they never become edges
+        let recursion_edges: Vec<CallEdge> = graph.edges.iter().cloned().chain(self_calls).collect();
+        graph.recursive_clusters = find_recursive_clusters(graph.nodes.keys(), &recursion_edges);
 
         Ok(graph)
     }
@@ -159,6 +267,8 @@ impl CallGraph {
 
     /// Add a call edge to the graph
     pub fn add_edge(&mut self, edge: CallEdge) {
+        self.adjacency_list.entry(edge.caller.clone()).or_default().push(edge.callee.clone());
+        self.reverse_adjacency.entry(edge.callee.clone()).or_default().push(edge.caller.clone());
         self.edges.push(edge);
     }
 
@@ -199,9 +309,10 @@ impl CallGraph {
             total_methods: self.nodes.len(),
             total_calls: self.edges.len(),
             entry_points: self.get_entry_point_candidates().len(),
-            cycles: self.cycles.len(),
+            cycles: self.recursive_clusters.len(),
             max_in_degree: self.nodes.keys().map(|m| self.in_degree(m)).max().unwrap_or(0),
             max_out_degree: self.nodes.keys().map(|m| self.out_degree(m)).max().unwrap_or(0),
+            unresolved_calls: self.unresolved_calls.len(),
         }
     }
 
@@ -238,14 +349,16 @@ impl CallGraph {
                         .map_or(false, |s| s.contains("async")),
                     documentation: module.docs.clone(),
                     complexity_score: self.estimate_complexity(module),
+                    is_test: self.is_test_method(file, module),
                 };
 
                 self.add_node(node);
             }
             "class" | "struct" | "impl" => {
                 // Process methods within classes/structs
+                let class_name = class_name_for_module(module);
                 for child in &module.children {
-                    self.extract_methods_from_module(file, child, Some(&module.name))?;
+                    self.extract_methods_from_module(file, child, Some(&class_name))?;
                 }
             }
             _ => {
@@ -259,160 +372,77 @@ impl CallGraph {
         Ok(())
     }
 
-    /// Extract method calls from a file - THIS IS THE KEY FIX
-    fn extract_calls_from_file(&mut self, file: &ParsedFile) -> Result<()> {
-        let lines: Vec<&str> = file.source_content.lines().collect();
-
-        for (line_number, line) in lines.iter().enumerate() {
-            let calls = self.extract_calls_from_line(line, &file.language);
-
-            for call_name in calls {
-                // Find which method this call is inside
-                if let Some(containing_method) = self.find_containing_method(file, line_number + 1) {
-                    // Try to resolve the call to a known method
-                    if let Some(target_method) = self.resolve_method_call(file, &call_name, &containing_method) {
-                        // CRITICAL: Only add edge if caller != callee
-                        if containing_method != target_method {
-                            let edge = CallEdge {
-                                caller: containing_method,
-                                callee: target_method,
-                                call_site_line: line_number + 1,
-                                call_type: self.detect_call_type(line),
-                            };
-                            self.add_edge(edge);
-                        }
-                    }
-                }
-            }
-        }
-
-        Ok(())
-    }
+    /// Turn the call sites found in a file's syntax tree into edges, recording
+    /// the calls that cannot be resolved
+    fn extract_calls_from_file(
+        &self,
+        file: &ParsedFile,
+        resolver: &CallResolver,
+        edges: &mut Vec<CallEdge>,
+        self_calls: &mut Vec<CallEdge>,
+        unresolved_calls: &mut Vec<UnresolvedCall>,
+    ) {
+        let imports = resolver.imported_names(file);
 
-    /// Extract method calls from a single line of code - ENHANCED VERSION
-    fn extract_calls_from_line(&self, line: &str, language: &str) -> Vec<String> {
-        let mut calls = Vec::new();
-        let trimmed = line.trim();
-
-        match language {
-            "java" => {
-                // Look for method calls: name() or object.name()
-                if let Ok(re) = regex::Regex::new(r"(\w+)\.(\w+)\s*\(") {
-                    for cap in re.captures_iter(trimmed) {
-                        if let Some(method_name) = cap.get(2) {
-                            let method_name = method_name.as_str();
-                            if !self.is_language_keyword(method_name, language) {
-                                calls.push(method_name.to_string());
-                            }
-                        }
-                    }
-                }
+        for call in &file.calls {
+            if file.language == "java" && self.is_java_builtin(&call.callee) {
+                continue;
+            }
 
-                // Also look for direct function calls: name()
-                if let Ok(re) = regex::Regex::new(r"\b(\w+)\s*\(") {
-                    for cap in re.captures_iter(trimmed) {
-                        if let Some(method_name) = cap.get(1) {
-                            let method_name = method_name.as_str();
-                            if !self.is_language_keyword(method_name, language) &&
-                                !self.is_java_builtin(method_name) {
-                                calls.push(method_name.to_string());
-                            }
-                        }
-                    }
+            // Find which method this call is inside
+            let Some(caller) = self.find_containing_method(file, call.line)
+                .and_then(|signature| self.nodes.get(&signature)) else {
+                continue;
+            };
+
+            match resolver.resolve(file, &imports, caller, call) {
+                Resolution::Resolved { target, confidence } => {
+                    // CRITICAL: Only add edge if caller != callee; direct recursion is kept aside
+                    let calls = if caller.signature != target { &mut *edges } else { &mut *self_calls };
+                    calls.push(CallEdge {
+                        caller: caller.signature.clone(),
+                        callee: target,
+                        call_site_line: call.line,
+                        call_type: call.call_type.clone(),
+                        confidence,
+                    });
                 }
-            }
-            "rust" => {
-                // Look for function calls: name() or name!(
-                if let Ok(re) = regex::Regex::new(r"\b([a-zA-Z_][a-zA-Z0-9_]*)\s*[\(]") {
-                    for cap in re.captures_iter(trimmed) {
-                        if let Some(name) = cap.get(1) {
-                            let method_name = name.as_str();
-                            if !self.is_language_keyword(method_name, language) {
-                                calls.push(method_name.to_string());
-                            }
-                        }
+                Resolution::Dispatch { targets, confidence } => {
+                    for target in targets {
+                        let calls = if target != caller.signature { &mut *edges } else { &mut *self_calls };
+                        calls.push(CallEdge {
+                            caller: caller.signature.clone(),
+                            callee: target,
+                            call_site_line: call.line,
+                            call_type: CallType::Dynamic,
+                            confidence,
+                        });
                     }
                 }
-            }
-            "python" => {
-                // Look for function calls: name() or object.name()
-                if let Ok(re) = regex::Regex::new(r"\.?([a-zA-Z_][a-zA-Z0-9_]*)\s*\(") {
-                    for cap in re.captures_iter(trimmed) {
-                        if let Some(name) = cap.get(1) {
-                            let method_name = name.as_str();
-                            if !self.is_language_keyword(method_name, language) {
-                                calls.push(method_name.to_string());
-                            }
-                        }
-                    }
+                // Names passed around as values are only calls if they resolve
+                Resolution::Unresolved { .. } if call.is_reference => {}
+                Resolution::Unresolved { candidates } => {
+                    unresolved_calls.push(UnresolvedCall {
+                        caller: caller.signature.clone(),
+                        callee_name: call.callee.clone(),
+                        receiver: call.receiver.clone(),
+                        call_site_line: call.line,
+                        call_type: call.call_type.clone(),
+                        candidates,
+                    });
                 }
             }
-            _ => {}
-        }
-
-        calls
-    }
-
-    /// Find which method contains a given line number
-    fn find_containing_method(&self, file: &ParsedFile, line_number: usize) -> Option<MethodSignature> {
-        for node in self.nodes.values() {
-            if node.signature.file_path == file.path &&
-                line_number >= node.line_range.0 &&
-                line_number <= node.line_range.1 {
-                return Some(node.signature.clone());
-            }
         }
-        None
     }
 
-    /// Try to resolve a method call to a specific method signature - IMPROVED VERSION
-    fn resolve_method_call(
-        &self,
-        _file: &ParsedFile,
-        call_name: &str,
-        _caller: &MethodSignature,
-    ) -> Option<MethodSignature> {
-        // Find all methods with matching names
-        let mut candidates: Vec<&MethodSignature> = self.nodes.keys()
-            .filter(|sig| sig.method_name == call_name)
-            .collect();
-
-        if candidates.is_empty() {
-            return None;
-        }
-
-        // For now, return the first match
-        // TODO: Improve with scope resolution and import analysis
-        Some(candidates[0].clone())
-    }
-
-    /// Detect the type of method call based on context
-    fn detect_call_type(&self, line: &str) -> CallType {
-        let trimmed = line.trim();
-
-        if trimmed.contains("await") {
-            CallType::Async
-        } else if trimmed.contains("if ") || trimmed.contains("switch ") {
-            CallType::Conditional
-        } else if trimmed.contains("for ") || trimmed.contains("while ") {
-            CallType::Loop
-        } else if trimmed.contains("try") || trimmed.contains("catch") {
-            CallType::Try
-        } else {
-            CallType::Direct
-        }
-    }
-
-    /// Check if a name is a language keyword
-    fn is_language_keyword(&self, name: &str, language: &str) -> bool {
-        let keywords: &[&str] = match language {
-            "java" => &["if", "else", "for", "while", "switch", "case", "break", "continue", "return", "try", "catch", "finally", "throw", "new", "this", "super", "class", "interface", "public", "private", "protected", "static", "final"],
-            "rust" => &["if", "else", "for", "while", "loop", "match", "let", "mut", "fn", "struct", "enum", "impl", "trait", "mod", "use", "pub", "return", "break", "continue"],
-            "python" => &["if", "else", "for", "while", "def", "class", "import", "from", "return", "break", "continue", "try", "except", "finally", "raise", "with", "as"],
-            _ => &[],
-        };
-
-        keywords.contains(&name)
+    /// Find the innermost method containing a given line number
+    pub(super) fn find_containing_method(&self, file: &ParsedFile, line_number: usize) -> Option<MethodSignature> {
+        self.nodes.values()
+            .filter(|node| node.signature.file_path == file.path
+                && line_number >= node.line_range.0
+                && line_number <= node.line_range.1)
+            .min_by_key(|node| (node.line_range.1 - node.line_range.0, node.signature.to_unique_string()))
+            .map(|node| node.signature.clone())
     }
 
     /// Check if a name is a Java built-in method that we should ignore
@@ -421,6 +451,29 @@ impl CallGraph {
         builtins.contains(&name)
     }
 
+    /// Detect test methods from attributes/annotations just above the definition,
+    /// test file locations and test naming conventions
+    fn is_test_method(&self, file: &ParsedFile, module: &ParsedModule) -> bool {
+        const TEST_MARKERS: &[&str] = &[
+            "#[test]", "#[tokio::test", "#[rstest", "@Test", "@ParameterizedTest",
+            "[Fact", "[Theory", "[Test", "[TestMethod", "@pytest",
+        ];
+
+        let start = module.line_range.0.saturating_sub(1);
+        let has_marker = file.source_content.lines()
+            .skip(start.saturating_sub(3))
+            .take(start - start.saturating_sub(3) + 1)
+            .any(|line| {
+                let trimmed = line.trim();
+                TEST_MARKERS.iter().any(|marker| trimmed.starts_with(marker))
+            });
+
+        has_marker
+            || module.name.starts_with("test_")
+            || (is_test_path(&file.path) && module.name.to_lowercase().starts_with("test"))
+            || (file.language == "javascript" && is_test_path(&file.path))
+    }
+
     /// Extract namespace from file path
     fn extract_namespace(&self, file_path: &PathBuf) -> Option<String> {
         // Simple heuristic: use directory structure
@@ -443,7 +496,7 @@ impl CallGraph {
     }
 
     /// Build adjacency lists for efficient traversal
-    fn build_adjacency_lists(&mut self) {
+    pub(super) fn build_adjacency_lists(&mut self) {
         self.adjacency_list.clear();
         self.reverse_adjacency.clear();
 
@@ -461,57 +514,6 @@ impl CallGraph {
                 .push(edge.caller.clone());
         }
     }
-
-    /// Detect cycles in the call graph using DFS
-    fn detect_cycles(&mut self) {
-        let mut visited = HashSet::new();
-        let mut rec_stack = HashSet::new();
-        let mut current_path = Vec::new();
-
-        let node_keys: Vec<_> = self.nodes.keys().cloned().collect();
-
-        for node in node_keys {
-            if !visited.contains(&node) {
-                self.dfs_cycle_detection(
-                    &node,
-                    &mut visited,
-                    &mut rec_stack,
-                    &mut current_path,
-                );
-            }
-        }
-    }
-
-    /// DFS helper for cycle detection
-    fn dfs_cycle_detection(
-        &mut self,
-        node: &MethodSignature,
-        visited: &mut HashSet<MethodSignature>,
-        rec_stack: &mut HashSet<MethodSignature>,
-        current_path: &mut Vec<MethodSignature>,
-    ) {
-        visited.insert(node.clone());
-        rec_stack.insert(node.clone());
-        current_path.push(node.clone());
-
-        let callees: Vec<_> = self.adjacency_list.get(node)
-            .map(|v| v.clone())
-            .unwrap_or_default();
-
-        for callee in callees {
-            if !visited.contains(&callee) {
-                self.dfs_cycle_detection(&callee, visited, rec_stack, current_path);
-            } else if rec_stack.contains(&callee) {
-                if let Some(cycle_start) = current_path.iter().position(|n| n == &callee) {
-                    let cycle = current_path[cycle_start..].to_vec();
-                    self.cycles.push(cycle);
-                }
-            }
-        }
-
-        rec_stack.remove(node);
-        current_path.pop();
-    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -522,10 +524,89 @@ pub struct CallGraphStats {
     pub cycles: usize,
     pub max_in_degree: usize,
     pub max_out_degree: usize,
+    #[serde(default)]
+    pub unresolved_calls: usize,
 }
 
 impl Default for CallGraph {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+/// Serde support for maps keyed by `MethodSignature`, which JSON cannot use as
+/// object keys: they are written as a list of `[key, value]` pairs in a stable order
+pub(crate) mod signature_map {
+    use std::collections::HashMap;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::MethodSignature;
+
+    pub fn serialize<V: Serialize, S: Serializer>(
+        map: &HashMap<MethodSignature, V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<(&MethodSignature, &V)> = map.iter().collect();
+        entries.sort_by_cached_key(|(method, _)| (method.file_path.clone(), method.to_unique_string()));
+        serializer.collect_seq(entries)
+    }
+
+    pub fn deserialize<'de, V: Deserialize<'de>, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<MethodSignature, V>, D::Error> {
+        Ok(Vec::<(MethodSignature, V)>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::parsed_source;
+    use crate::core::languages::{PythonParser, RustParser};
+
+    #[test]
+    fn test_is_test_path() {
+        for path in ["tests/api.rs", "/p/src/test/java/OrderTest.java", "/p/web/__tests__/cart.js", "/p/app/test_orders.py",
+                     "/p/src/orders_test.go", "/p/Api/OrdersTests.cs", "/p/web/cart.test.ts", "/p/web/cart.spec.js"] {
+            assert!(is_test_path(Path::new(path)), "{}", path);
+        }
+        for path in ["/p/src/orders.rs", "/p/src/testing.rs", "/p/app/contest.py", "/p/web/latest.js"] {
+            assert!(!is_test_path(Path::new(path)), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_reports_direct_recursion() {
+        let source = "pub fn walk(depth: u32) {\n    helper(depth);\n}\n\n\
+            fn helper(depth: u32) {\n    if depth > 0 {\n        helper(depth - 1);\n    }\n}\n";
+        let files = vec![parsed_source(&mut RustParser::new().unwrap(), "/p/src/tree.rs", source)];
+
+        let graph = CallGraph::build_from_files(&files).unwrap();
+
+        let [cluster] = graph.recursive_clusters.as_slice() else {
+            panic!("expected one recursive cluster: {:?}", graph.recursive_clusters);
+        };
+        assert_eq!(cluster.members.len(), 1);
+        assert_eq!(cluster.members[0].method_name, "helper");
+        assert_eq!(cluster.entry_edges.len(), 1);
+        assert_eq!(cluster.entry_edges[0].caller.method_name, "walk");
+        // The self-call is reported as recursion without becoming an edge
+        assert_eq!(graph.edges.len(), 1);
+    }
+
+    #[test]
+    fn test_detects_test_methods() {
+        let rust = "pub fn place() {}\n\n#[test]\nfn places_order() {\n    place();\n}\n";
+        let python = "def test_checkout():\n    pass\n\ndef make_cart():\n    pass\n";
+        let files = vec![
+            parsed_source(&mut RustParser::new().unwrap(), "/p/src/orders.rs", rust),
+            parsed_source(&mut PythonParser::new().unwrap(), "/p/tests/checkout.py", python),
+        ];
+
+        let graph = CallGraph::build_from_files(&files).unwrap();
+        let is_test = |name: &str| graph.nodes.values().find(|node| node.signature.method_name == name).unwrap().is_test;
+
+        assert!(is_test("places_order") && is_test("test_checkout"));
+        assert!(!is_test("place") && !is_test("make_cart"));
+    }
+}
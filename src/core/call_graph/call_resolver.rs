@@ -0,0 +1,736 @@
+// src/core/call_graph/call_resolver.rs
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use regex::Regex;
+
+use super::{CallNode, MethodSignature};
+use super::type_hierarchy::TypeHierarchy;
+use super::super::{ParsedCall, ParsedFile, ParsedModule};
+
+/// Confidence for calls on `self`/`this` or implicit receivers resolved within the caller's class
+const SAME_CLASS_CONFIDENCE: f32 = 0.95;
+/// Confidence for `Type::method()` / `Type.method()` calls
+const QUALIFIED_CONFIDENCE: f32 = 0.95;
+/// Confidence for `module::function()` / `module.function()` calls
+const MODULE_CONFIDENCE: f32 = 0.9;
+/// Confidence for calls on a receiver whose declared type was found
+const DECLARED_TYPE_CONFIDENCE: f32 = 0.85;
+/// Confidence for each possible target of a call through an interface or trait
+const DYNAMIC_DISPATCH_CONFIDENCE: f32 = 0.7;
+/// Confidence for unqualified calls resolved within the caller's file
+const SAME_FILE_CONFIDENCE: f32 = 0.8;
+/// Confidence for unqualified calls resolved through the file's imports
+const IMPORT_CONFIDENCE: f32 = 0.75;
+/// Confidence for calls whose name matches a single method in the project
+const UNIQUE_NAME_CONFIDENCE: f32 = 0.5;
+/// Confidence for calls on a receiver of unknown type with a single match
+const UNKNOWN_RECEIVER_CONFIDENCE: f32 = 0.4;
+/// Multiplier applied when argument count had to break a tie
+const ARITY_TIE_BREAK_FACTOR: f32 = 0.9;
+
+/// Declarations that give a variable, parameter or field its type, per language, in
+/// order of preference; each captures the declared `name` and its `type`
+static DECLARATION_PATTERNS: LazyLock<HashMap<&'static str, Vec<Regex>>> = LazyLock::new(|| {
+    let java_like: &[&str] = &[
+        r"(?P<type>[A-Z][\w.]*)(?:<[^;=()]*>)?\s+(?P<name>[A-Za-z_]\w*)\s*[;=,)]",
+        r"\b(?P<name>[A-Za-z_]\w*)\s*=\s*new\s+(?P<type>[A-Za-z_][\w.]*)",
+    ];
+    let patterns: [(&str, &[&str]); 5] = [
+        ("rust", &[
+            r"\b(?P<name>[A-Za-z_]\w*)\s*:\s*(?:&\s*)?(?:'\w+\s+)?(?:mut\s+)?(?:dyn\s+|impl\s+)?(?P<type>[A-Za-z_][\w:]*)",
+            r"\blet\s+(?:mut\s+)?(?P<name>[A-Za-z_]\w*)\s*=\s*&?(?:mut\s+)?(?P<type>[A-Za-z_][\w:]*?)(?:::\w+\s*\(|\s*\{)",
+        ]),
+        ("java", java_like),
+        ("csharp", java_like),
+        ("javascript", &[
+            r"\b(?P<name>[A-Za-z_]\w*)\s*=\s*new\s+(?P<type>[A-Za-z_][\w.]*)",
+        ]),
+        ("python", &[
+            r"\b(?P<name>[A-Za-z_]\w*)\s*:\s*(?P<type>[A-Z][\w.]*)",
+            r"\b(?P<name>[A-Za-z_]\w*)\s*=\s*(?P<type>[A-Z][\w.]*)\s*\(",
+        ]),
+    ];
+
+    patterns.into_iter()
+        .map(|(language, patterns)| {
+            (language, patterns.iter().map(|pattern| Regex::new(pattern).expect("valid regex")).collect())
+        })
+        .collect()
+});
+
+/// Generic parameters with a bound: `<N: Notifier>`, `<T extends Plugin>`
+static GENERIC_BOUND: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"[<,\s](?P<parameter>[A-Za-z_]\w*)\s*(?::|\s+extends\s+)\s*(?:\?Sized\s*\+\s*)?(?P<bound>[A-Za-z_][\w:.]*)")
+        .expect("valid regex")
+});
+
+/// The extended type of a C# extension method: `static string Slugify(this string value)`
+static EXTENSION_TARGET: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\(\s*this\s+([A-Za-z_][\w.]*)").expect("valid regex"));
+
+static IDENTIFIER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").expect("valid regex"));
+
+/// A typed declaration found in a file
+struct Declaration {
+    /// Index of the pattern that found it, lower is preferred
+    pattern: usize,
+    /// Line of the declared name
+    line: usize,
+    /// Declared type as written (`Store`, `crate::util::Store`, `Shop.Web.Helper`)
+    type_path: String,
+}
+
+/// Resolves call sites to methods in the graph using scope, imports and receiver types
+pub struct CallResolver<'a> {
+    /// Methods indexed by name
+    methods_by_name: HashMap<&'a str, Vec<&'a CallNode>>,
+    /// Types declared anywhere in the project (structs, classes, traits, interfaces...)
+    project_types: HashSet<String>,
+    /// Which types implement/extend which
+    hierarchy: &'a TypeHierarchy,
+    /// Typed declarations per file and declared name, ordered by preference then position
+    declarations: HashMap<PathBuf, HashMap<String, Vec<Declaration>>>,
+}
+
+/// Outcome of resolving a single call site
+#[derive(Debug, Clone)]
+pub enum Resolution {
+    /// The call refers to this method
+    Resolved {
+        target: MethodSignature,
+        confidence: f32,
+    },
+    /// The call goes through an interface/trait/base class and may reach any of these
+    Dispatch {
+        targets: Vec<MethodSignature>,
+        confidence: f32,
+    },
+    /// The call could not be pinned to one method; `candidates` lists project methods
+    /// it may refer to (empty for calls into external code)
+    Unresolved {
+        candidates: Vec<MethodSignature>,
+    },
+}
+
+/// How the receiver of a call was written
+enum Receiver<'r> {
+    /// `foo()`
+    Implicit,
+    /// `self.foo()`, `this.foo()`, `Self::foo()`
+    SelfRef,
+    /// `Type::foo()`, `module::foo()`, `Type.foo()`
+    Path(&'r str),
+    /// `value.foo()`, `self.field.foo()`
+    Value(&'r str),
+    /// Call chains and other expressions
+    Expression,
+}
+
+impl<'a> CallResolver<'a> {
+    pub fn new(nodes: &'a HashMap<MethodSignature, CallNode>, hierarchy: &'a TypeHierarchy, files: &[ParsedFile]) -> Self {
+        let mut methods_by_name: HashMap<&str, Vec<&CallNode>> = HashMap::new();
+        for node in nodes.values() {
+            methods_by_name.entry(node.signature.method_name.as_str()).or_default().push(node);
+        }
+
+        // Deterministic candidate order regardless of HashMap iteration
+        for candidates in methods_by_name.values_mut() {
+            candidates.sort_by_key(|node| node.signature.to_unique_string());
+        }
+
+        let mut project_types = HashSet::new();
+        for file in files {
+            collect_type_names(&file.modules, &mut project_types);
+        }
+
+        let declarations = files.iter()
+            .map(|file| (file.path.clone(), index_declarations(file)))
+            .collect();
+
+        Self {
+            methods_by_name,
+            project_types,
+            hierarchy,
+            declarations,
+        }
+    }
+
+    /// Resolve a call made from `caller`, preferring (in order) the caller's class for
+    /// self/implicit receivers, qualified paths and declared receiver types when the
+    /// receiver says where to look, then the same file, then imported symbols
+    pub fn resolve(&self, file: &ParsedFile, imports: &HashSet<String>, caller: &CallNode, call: &ParsedCall) -> Resolution {
+        let Some(candidates) = self.methods_by_name.get(call.callee.as_str()) else {
+            return Resolution::Unresolved { candidates: Vec::new() };
+        };
+
+        match self.classify_receiver(call.receiver.as_deref()) {
+            Receiver::SelfRef => {
+                let Some(class_name) = caller.signature.class_name.as_deref() else {
+                    return self.unresolved(candidates);
+                };
+
+                // A Rust `self` is always the concrete impl type; elsewhere subclasses may override
+                if file.language != "rust" {
+                    if let Some(dispatch) = self.dispatch(candidates, class_name) {
+                        return dispatch;
+                    }
+                }
+
+                self.pick_in_type(candidates, call, class_name, SAME_CLASS_CONFIDENCE)
+                    .unwrap_or_else(|| self.unresolved(candidates))
+            }
+            Receiver::Implicit => {
+                // Java and C# methods call siblings and inherited methods without `this`
+                if let Some(class_name) = caller.signature.class_name.as_deref()
+                    .filter(|_| matches!(file.language.as_str(), "java" | "csharp")) {
+                    if let Some(resolution) = self.pick_in_type(candidates, call, class_name, SAME_CLASS_CONFIDENCE) {
+                        return resolution;
+                    }
+                }
+
+                let same_file: Vec<_> = candidates.iter().copied()
+                    .filter(|node| node.signature.file_path == file.path)
+                    .collect();
+                if let Some(resolution) = self.pick(&same_file, call, SAME_FILE_CONFIDENCE) {
+                    return resolution;
+                }
+
+                let imported: Vec<_> = candidates.iter().copied()
+                    .filter(|node| self.is_imported(node, imports))
+                    .collect();
+                if let Some(resolution) = self.pick(&imported, call, IMPORT_CONFIDENCE) {
+                    return resolution;
+                }
+
+                self.pick(candidates, call, UNIQUE_NAME_CONFIDENCE)
+                    .unwrap_or_else(|| self.unresolved(candidates))
+            }
+            Receiver::Path(path) => {
+                let last = last_path_segment(path);
+                if last == "Self" {
+                    let same_class = self.in_class(candidates, caller.signature.class_name.as_deref());
+                    return self.pick(&same_class, call, QUALIFIED_CONFIDENCE)
+                        .unwrap_or_else(|| self.unresolved(candidates));
+                }
+
+                if self.project_types.contains(last) {
+                    return self.pick_in_type(candidates, call, last, QUALIFIED_CONFIDENCE)
+                        .unwrap_or_else(|| self.unresolved(candidates));
+                }
+
+                let in_module = self.in_module(candidates, last);
+                self.pick(&in_module, call, MODULE_CONFIDENCE)
+                    // Neither a project type nor a project module: external code
+                    .unwrap_or(Resolution::Unresolved { candidates: Vec::new() })
+            }
+            Receiver::Value(name) => {
+                if let Some(type_name) = self.infer_receiver_type(file, caller, name) {
+                    let is_project_type = self.project_types.contains(&type_name);
+                    if is_project_type {
+                        if let Some(dispatch) = self.dispatch(candidates, &type_name) {
+                            return dispatch;
+                        }
+
+                        if let Some(resolution) = self.pick_in_type(candidates, call, &type_name, DECLARED_TYPE_CONFIDENCE) {
+                            return resolution;
+                        }
+                    }
+
+                    // Instance methods win over extension methods, which also extend external types
+                    let extensions = self.extending(candidates, &type_name);
+                    return self.pick(&extensions, call, DECLARED_TYPE_CONFIDENCE)
+                        .unwrap_or_else(|| if is_project_type {
+                            self.unresolved(candidates)
+                        } else {
+                            Resolution::Unresolved { candidates: Vec::new() }
+                        });
+                }
+
+                // `module.function()` in Python/JavaScript
+                let in_module = self.in_module(candidates, name);
+                if let Some(resolution) = self.pick(&in_module, call, MODULE_CONFIDENCE) {
+                    return resolution;
+                }
+
+                self.pick(candidates, call, UNKNOWN_RECEIVER_CONFIDENCE)
+                    .unwrap_or_else(|| self.unresolved(candidates))
+            }
+            Receiver::Expression => {
+                self.pick(candidates, call, UNKNOWN_RECEIVER_CONFIDENCE)
+                    .unwrap_or_else(|| self.unresolved(candidates))
+            }
+        }
+    }
+
+    /// Names brought into scope by a file's import/use statements, including
+    /// the module path segments they come from
+    pub fn imported_names(&self, file: &ParsedFile) -> HashSet<String> {
+        // (statement prefixes, keywords that are not imported names)
+        let (prefixes, keywords): (&[&str], &[&str]) = match file.language.as_str() {
+            "rust" => (&["use ", "pub use "], &["use", "pub", "crate", "self", "super", "as"]),
+            "java" => (&["import "], &["import", "static"]),
+            "python" => (&["import ", "from "], &["import", "from", "as"]),
+            "javascript" => (&["import "], &["import", "from", "as", "const", "let", "var", "require", "default"]),
+            "csharp" => (&["using "], &["using", "static", "global"]),
+            _ => return HashSet::new(),
+        };
+
+        let mut names = HashSet::new();
+        let mut statement = String::new();
+
+        for line in file.source_content.lines() {
+            let trimmed = line.trim();
+            let starts_import = prefixes.iter().any(|prefix| trimmed.starts_with(prefix))
+                || (file.language == "javascript" && trimmed.contains("require("));
+
+            if statement.is_empty() && !starts_import {
+                continue;
+            }
+
+            statement.push_str(trimmed);
+            statement.push(' ');
+
+            let open = statement.matches(['{', '(']).count();
+            let close = statement.matches(['}', ')']).count();
+            if open > close {
+                continue;
+            }
+
+            for token in IDENTIFIER.find_iter(&statement) {
+                if !keywords.contains(&token.as_str()) {
+                    names.insert(token.as_str().to_string());
+                }
+            }
+            statement.clear();
+        }
+
+        names
+    }
+
+    fn classify_receiver<'r>(&self, receiver: Option<&'r str>) -> Receiver<'r> {
+        let Some(receiver) = receiver.map(str::trim) else {
+            return Receiver::Implicit;
+        };
+
+        if matches!(receiver, "self" | "this" | "Self") {
+            return Receiver::SelfRef;
+        }
+
+        if receiver.contains("::") {
+            return Receiver::Path(receiver);
+        }
+
+        let field = receiver.strip_prefix("self.")
+            .or_else(|| receiver.strip_prefix("this."))
+            .unwrap_or(receiver);
+
+        if !is_identifier(field) {
+            return Receiver::Expression;
+        }
+
+        // `Type::method()` / `Type.method()` (static calls, constructors)
+        let is_type_like = field == receiver && field.chars().next().is_some_and(char::is_uppercase);
+        if is_type_like {
+            Receiver::Path(field)
+        } else {
+            Receiver::Value(field)
+        }
+    }
+
+    /// Choose the only candidate, breaking ties by argument count
+    fn pick(&self, candidates: &[&CallNode], call: &ParsedCall, confidence: f32) -> Option<Resolution> {
+        match candidates {
+            [] => None,
+            [only] => Some(Resolution::Resolved {
+                target: only.signature.clone(),
+                confidence,
+            }),
+            _ => {
+                let matching_arity: Vec<_> = candidates.iter()
+                    .filter(|node| parameter_count(&node.signature.signature) == Some(call.arg_count))
+                    .collect();
+
+                Some(match matching_arity.as_slice() {
+                    [only] => Resolution::Resolved {
+                        target: only.signature.clone(),
+                        confidence: confidence * ARITY_TIE_BREAK_FACTOR,
+                    },
+                    _ => self.unresolved(candidates),
+                })
+            }
+        }
+    }
+
+    /// Pick the method declared on `type_name`, or failing that the nearest
+    /// supertype that declares it (inherited and default methods)
+    fn pick_in_type(&self, candidates: &[&CallNode], call: &ParsedCall, type_name: &str, confidence: f32) -> Option<Resolution> {
+        std::iter::once(type_name.to_string())
+            .chain(self.hierarchy.supertypes_of(type_name))
+            .map(|owner| self.in_class(candidates, Some(&owner)))
+            .find(|in_owner| !in_owner.is_empty())
+            .and_then(|in_owner| self.pick(&in_owner, call, confidence))
+    }
+
+    /// When subtypes of `type_name` implement the method, the call may reach any of
+    /// them (plus the type's own implementation, if it has one)
+    fn dispatch(&self, candidates: &[&CallNode], type_name: &str) -> Option<Resolution> {
+        let implementations: Vec<&CallNode> = self.hierarchy.subtypes_of(type_name).iter()
+            .flat_map(|subtype| self.in_class(candidates, Some(subtype)))
+            .collect();
+        if implementations.is_empty() {
+            return None;
+        }
+
+        let mut targets: Vec<MethodSignature> = self.in_class(candidates, Some(type_name)).into_iter()
+            .chain(implementations)
+            .map(|node| node.signature.clone())
+            .collect();
+        targets.sort_by_key(|target| target.to_unique_string());
+        targets.dedup();
+
+        Some(Resolution::Dispatch {
+            targets,
+            confidence: DYNAMIC_DISPATCH_CONFIDENCE,
+        })
+    }
+
+    /// C# extension methods whose `this` parameter is `type_name` or one of its supertypes
+    fn extending<'n>(&self, candidates: &[&'n CallNode], type_name: &str) -> Vec<&'n CallNode> {
+        let supertypes = self.hierarchy.supertypes_of(type_name);
+        candidates.iter().copied()
+            .filter(|node| EXTENSION_TARGET.captures(&node.signature.signature)
+                .map(|captures| type_name_from_path(&captures[1]))
+                .is_some_and(|target| target == type_name || supertypes.contains(&target)))
+            .collect()
+    }
+
+    fn unresolved(&self, candidates: &[&CallNode]) -> Resolution {
+        Resolution::Unresolved {
+            candidates: candidates.iter().map(|node| node.signature.clone()).collect(),
+        }
+    }
+
+    fn in_class<'n>(&self, candidates: &[&'n CallNode], class_name: Option<&str>) -> Vec<&'n CallNode> {
+        let Some(class_name) = class_name else {
+            return Vec::new();
+        };
+
+        candidates.iter().copied()
+            .filter(|node| node.signature.class_name.as_deref() == Some(class_name))
+            .collect()
+    }
+
+    fn in_module<'n>(&self, candidates: &[&'n CallNode], module: &str) -> Vec<&'n CallNode> {
+        candidates.iter().copied()
+            .filter(|node| {
+                let stem = node.signature.file_path.file_stem().map(|stem| stem.to_string_lossy());
+                stem.as_deref() == Some(module) || node.signature.namespace.as_deref() == Some(module)
+            })
+            .collect()
+    }
+
+    fn is_imported(&self, node: &CallNode, imports: &HashSet<String>) -> bool {
+        let signature = &node.signature;
+        let stem = signature.file_path.file_stem().map(|stem| stem.to_string_lossy().to_string());
+
+        imports.contains(&signature.method_name)
+            || signature.class_name.as_ref().is_some_and(|class| imports.contains(class))
+            || stem.is_some_and(|stem| imports.contains(&stem))
+    }
+
+    /// Find the declared type of a variable, parameter or field, looking in the
+    /// caller's body first and then the rest of the file
+    fn infer_receiver_type(&self, file: &ParsedFile, caller: &CallNode, name: &str) -> Option<String> {
+        let declarations = self.declarations.get(&file.path)?.get(name)?;
+        let (body_start, body_end) = caller.line_range;
+
+        let declaration = declarations.iter()
+            .find(|declaration| (body_start..=body_end).contains(&declaration.line))
+            .or(declarations.first())?;
+
+        let type_name = type_name_from_path(&declaration.type_path);
+        // Generic parameters stand for their bound: `<N: Notifier>`, `<T extends Plugin>`
+        Some(self.generic_bound(&caller.signature.signature, &type_name).unwrap_or(type_name))
+    }
+
+    /// First bound of a generic parameter declared in a signature
+    fn generic_bound(&self, signature: &str, parameter: &str) -> Option<String> {
+        if self.project_types.contains(parameter) {
+            return None;
+        }
+
+        GENERIC_BOUND.captures_iter(signature)
+            .find(|captures| &captures["parameter"] == parameter)
+            .map(|captures| type_name_from_path(&captures["bound"]))
+    }
+}
+
+/// Index the typed declarations in a file by declared name
+fn index_declarations(file: &ParsedFile) -> HashMap<String, Vec<Declaration>> {
+    let mut declarations: HashMap<String, Vec<Declaration>> = HashMap::new();
+    let Some(patterns) = DECLARATION_PATTERNS.get(file.language.as_str()) else {
+        return declarations;
+    };
+
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(file.source_content.match_indices('\n').map(|(index, _)| index + 1))
+        .collect();
+
+    for (pattern_index, pattern) in patterns.iter().enumerate() {
+        for captures in pattern.captures_iter(&file.source_content) {
+            let name = &captures["name"];
+            let line = line_starts.partition_point(|&start| start <= captures.name("name").map_or(0, |m| m.start()));
+            declarations.entry(name.to_string()).or_default().push(Declaration {
+                pattern: pattern_index,
+                line,
+                type_path: captures["type"].to_string(),
+            });
+        }
+    }
+
+    for candidates in declarations.values_mut() {
+        candidates.sort_by_key(|declaration| (declaration.pattern, declaration.line));
+    }
+
+    declarations
+}
+
+/// Normalized class name for methods inside a module: `impl Foo<T>` -> `Foo`
+pub fn class_name_for_module(module: &ParsedModule) -> String {
+    let name = module.name.strip_prefix("impl ").unwrap_or(&module.name);
+    name.split('<').next().unwrap_or(name).trim().to_string()
+}
+
+fn collect_type_names(modules: &[ParsedModule], names: &mut HashSet<String>) {
+    for module in modules {
+        if matches!(module.item_type.as_str(), "struct" | "class" | "trait" | "interface" | "enum" | "impl") {
+            names.insert(class_name_for_module(module));
+        }
+        collect_type_names(&module.children, names);
+    }
+}
+
+/// `crate::util::Store` -> `Store`, `Store::new` -> `Store`, `Shop.Web.Helper` -> `Helper`
+fn type_name_from_path(path: &str) -> String {
+    let segments: Vec<&str> = path.split(['.', ':']).filter(|segment| !segment.is_empty()).collect();
+    segments.iter().rev()
+        .find(|segment| segment.chars().next().is_some_and(char::is_uppercase))
+        .or(segments.last())
+        .map(|segment| segment.to_string())
+        .unwrap_or_default()
+}
+
+fn last_path_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+fn is_identifier(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|ch| ch.is_alphanumeric() || ch == '_')
+}
+
+/// Number of declared parameters, excluding `self`/`cls` receivers and the `this` parameter of extension methods
+fn parameter_count(signature: &str) -> Option<usize> {
+    // The parameter list is the first `(` outside generic parameters (`fn map<F: Fn(T)>(f: F)`)
+    let mut depth = 0;
+    let mut previous = ' ';
+    let mut open = None;
+    for (index, ch) in signature.char_indices() {
+        let before = std::mem::replace(&mut previous, ch);
+        match ch {
+            '(' if depth == 0 => {
+                open = Some(index);
+                break;
+            }
+            '<' => depth += 1,
+            // `->` is an arrow, not a closing bracket
+            '>' if before != '-' => depth -= 1,
+            _ => {}
+        }
+    }
+    let open = open?;
+
+    let mut depth = 0;
+    let mut previous = ' ';
+    let mut close = None;
+    for (index, ch) in signature[open..].char_indices() {
+        let before = std::mem::replace(&mut previous, ch);
+        match ch {
+            '>' if before == '-' => {}
+            '(' | '<' | '[' => depth += 1,
+            ')' | '>' | ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(open + index);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let params = &signature[open + 1..close?];
+    let mut count = 0;
+    let mut depth = 0;
+    let mut previous = ' ';
+    let mut current = String::new();
+    for ch in params.chars().chain(std::iter::once(',')) {
+        let before = std::mem::replace(&mut previous, ch);
+        match ch {
+            '>' if before == '-' => {}
+            '(' | '<' | '[' | '{' => depth += 1,
+            ')' | '>' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                let param = current.trim();
+                let is_receiver = (param.ends_with("self") && !param.contains(':')) || param == "cls"
+                    || param.starts_with("this ");
+                if !param.is_empty() && !is_receiver {
+                    count += 1;
+                }
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(ch);
+    }
+
+    Some(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::parsed_source;
+    use crate::core::call_graph::CallGraph;
+    use crate::core::languages::{CSharpParser, RustParser};
+
+    fn rust_graph(files: &[(&str, &str)]) -> CallGraph {
+        let mut parser = RustParser::new().unwrap();
+        let files: Vec<ParsedFile> = files.iter().map(|(path, source)| parsed_source(&mut parser, path, source)).collect();
+        CallGraph::build_from_files(&files).unwrap()
+    }
+
+    #[test]
+    fn test_resolution_order_and_confidence() {
+        let service = "use crate::util::audit::*;\n\
+            pub struct OrderService {}\n\
+            impl OrderService {\n\
+                pub fn place(&self, store: &Store) {\n\
+                    self.validate();\n\
+                    summarize();\n\
+                    record();\n\
+                    store.save();\n\
+                    Store::open();\n\
+                    flush();\n\
+                }\n\
+                fn validate(&self) {}\n\
+            }\n\
+            fn validate() {}\n\
+            fn summarize() {}\n";
+        let graph = rust_graph(&[
+            ("/p/src/orders/service.rs", service),
+            ("/p/src/orders/report.rs", "pub fn summarize() {}\npub fn validate() {}\n"),
+            ("/p/src/util/audit.rs", "pub fn record() {}\n"),
+            ("/p/src/legacy/journal.rs", "pub fn record() {}\npub fn flush() {}\n"),
+            ("/p/src/util/queue.rs", "pub fn flush() {}\n"),
+            ("/p/src/util/store.rs", "pub struct Store {}\nimpl Store {\n    pub fn open() -> Self { Store {} }\n    pub fn save(&self) {}\n}\n"),
+            ("/p/src/util/cache.rs", "pub struct Cache {}\nimpl Cache {\n    pub fn save(&self) {}\n}\n"),
+        ]);
+
+        let resolved: Vec<(usize, String, &str, f32)> = graph.edges.iter()
+            .map(|edge| (
+                edge.call_site_line,
+                edge.callee.display_name(),
+                edge.callee.file_path.to_str().unwrap(),
+                edge.confidence,
+            ))
+            .collect();
+        let expect = |line: usize, callee: &str, file: &str, confidence: f32| {
+            assert!(resolved.contains(&(line, callee.to_string(), file, confidence)), "line {}: {:?}", line, resolved);
+        };
+
+        // Same class beats a same-file free function of the same name
+        expect(5, "OrderService::validate", "/p/src/orders/service.rs", SAME_CLASS_CONFIDENCE);
+        // Same file beats another file in the package
+        expect(6, "summarize", "/p/src/orders/service.rs", SAME_FILE_CONFIDENCE);
+        // A glob import picks between equally named functions elsewhere
+        expect(7, "record", "/p/src/util/audit.rs", IMPORT_CONFIDENCE);
+        // The declared receiver type picks between equally named methods
+        expect(8, "Store::save", "/p/src/util/store.rs", DECLARED_TYPE_CONFIDENCE);
+        expect(9, "Store::open", "/p/src/util/store.rs", QUALIFIED_CONFIDENCE);
+        assert_eq!(resolved.len(), 5);
+
+        // Nothing narrows `flush()` down, so both candidates are recorded
+        let [flush] = graph.unresolved_calls.as_slice() else {
+            panic!("expected one unresolved call: {:?}", graph.unresolved_calls);
+        };
+        assert_eq!((flush.callee_name.as_str(), flush.call_site_line), ("flush", 10));
+        let candidates: Vec<_> = flush.candidates.iter().map(|candidate| candidate.file_path.to_str().unwrap()).collect();
+        assert_eq!(candidates, vec!["/p/src/legacy/journal.rs", "/p/src/util/queue.rs"]);
+    }
+
+    #[test]
+    fn test_arity_breaks_ties() {
+        let source = "fn run() {\n    load(1, 2);\n}\n";
+        let graph = rust_graph(&[
+            ("/p/src/app/main.rs", source),
+            ("/p/src/orders/load.rs", "pub fn load(id: u32) {}\n"),
+            ("/p/src/users/load.rs", "pub fn load(id: u32, depth: u8) {}\n"),
+        ]);
+
+        let [edge] = graph.edges.as_slice() else {
+            panic!("expected one edge: {:?}", graph.edges);
+        };
+        assert_eq!(edge.callee.file_path.to_str(), Some("/p/src/users/load.rs"));
+        assert_eq!(edge.confidence, UNIQUE_NAME_CONFIDENCE * ARITY_TIE_BREAK_FACTOR);
+    }
+
+    #[test]
+    fn test_csharp_extension_methods() {
+        let source = "namespace Shop {\n\
+            public class Orders {\n\
+                public void Sync(HttpClient client, Cart cart) {\n\
+                    client.FetchOrders();\n\
+                    cart.Total();\n\
+                }\n\
+            }\n\
+            public class Cart {\n\
+                public int Total() { return 0; }\n\
+            }\n\
+            public static class Extensions {\n\
+                public static string FetchOrders(this HttpClient client) { return \"\"; }\n\
+                public static int Total(this Cart cart) { return 1; }\n\
+            }\n\
+            }\n";
+        let file = parsed_source(&mut CSharpParser::new().unwrap(), "/p/Shop/Orders.cs", source);
+        let graph = CallGraph::build_from_files(&[file]).unwrap();
+
+        let resolved: Vec<(usize, String, f32)> = graph.edges.iter()
+            .map(|edge| (edge.call_site_line, edge.callee.display_name(), edge.confidence))
+            .collect();
+        // An external receiver type reaches the extension method; a project type's own method wins
+        assert!(resolved.contains(&(4, "Extensions::FetchOrders".to_string(), DECLARED_TYPE_CONFIDENCE)), "{:?}", resolved);
+        assert!(resolved.contains(&(5, "Cart::Total".to_string(), DECLARED_TYPE_CONFIDENCE)), "{:?}", resolved);
+        assert_eq!(resolved.len(), 2);
+    }
+
+    #[test]
+    fn test_parameter_count() {
+        assert_eq!(parameter_count("pub fn place_order(&mut self, item: &str, qty: u32) -> u64"), Some(2));
+        assert_eq!(parameter_count("fn new() -> Self"), Some(0));
+        assert_eq!(parameter_count("def load(self, key)"), Some(1));
+        assert_eq!(parameter_count("public Map<String, Integer> merge(Map<String, Integer> a, int b)"), Some(2));
+        assert_eq!(parameter_count("struct Foo"), None);
+        assert_eq!(parameter_count("fn apply(f: impl Fn(u32) -> u32, x: u32) -> u32"), Some(2));
+        assert_eq!(parameter_count("fn map<F: Fn(&T) -> U>(self, f: F, g: Box<dyn Fn() -> ()>)"), Some(2));
+        assert_eq!(parameter_count("public static string Truncate(this string value, int length)"), Some(1));
+    }
+
+    #[test]
+    fn test_type_name_from_path() {
+        assert_eq!(type_name_from_path("crate::util::store::Store"), "Store");
+        assert_eq!(type_name_from_path("Store::new"), "Store");
+        assert_eq!(type_name_from_path("Shop.Web.Helper"), "Helper");
+        assert_eq!(type_name_from_path("u64"), "u64");
+    }
+}
@@ -0,0 +1,246 @@
+// src/core/call_graph/centrality.rs
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use serde::{Serialize, Deserialize};
+
+use super::{CallGraph, MethodSignature};
+
+/// PageRank damping factor
+const DAMPING: f64 = 0.85;
+/// PageRank stops once the total change per iteration drops below this
+const PAGE_RANK_TOLERANCE: f64 = 1e-6;
+const PAGE_RANK_MAX_ITERATIONS: usize = 100;
+/// Betweenness is estimated from this many source functions on larger graphs
+const BETWEENNESS_MAX_SOURCES: usize = 1500;
+
+/// Ranks functions by how central they are to the call graph
+pub struct CentralityAnalyzer;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionCentrality {
+    pub method: MethodSignature,
+    /// Number of distinct callers
+    pub fan_in: usize,
+    /// Number of distinct callees
+    pub fan_out: usize,
+    /// Share of shortest call paths that pass through this function (0.0 to 1.0)
+    pub betweenness: f64,
+    /// PageRank over call edges: how much of the program eventually calls into this function
+    pub page_rank: f64,
+    /// Weighted combination of the normalized measures (0.0 to 1.0)
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Centrality {
+    /// All functions, most central first
+    pub ranked: Vec<FunctionCentrality>,
+    /// Combined score of each package's functions
+    pub package_scores: BTreeMap<String, f64>,
+    #[serde(skip)]
+    scores: HashMap<MethodSignature, f64>,
+}
+
+impl CentralityAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn analyze(&self, call_graph: &CallGraph) -> Centrality {
+        let mut methods: Vec<&MethodSignature> = call_graph.nodes.keys().collect();
+        methods.sort_by_cached_key(|method| method.to_unique_string());
+        let index_of: HashMap<&MethodSignature, usize> = methods.iter()
+            .enumerate()
+            .map(|(index, method)| (*method, index))
+            .collect();
+
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); methods.len()];
+        for edge in &call_graph.edges {
+            if let (Some(&caller), Some(&callee)) = (index_of.get(&edge.caller), index_of.get(&edge.callee)) {
+                if caller != callee {
+                    successors[caller].push(callee);
+                }
+            }
+        }
+        for targets in &mut successors {
+            targets.sort_unstable();
+            targets.dedup();
+        }
+
+        let mut fan_in = vec![0; methods.len()];
+        for targets in &successors {
+            for &target in targets {
+                fan_in[target] += 1;
+            }
+        }
+
+        let betweenness = betweenness(&successors);
+        let page_rank = page_rank(&successors);
+
+        let max_of = |values: &[f64]| values.iter().copied().fold(0.0, f64::max).max(f64::EPSILON);
+        let max_fan_in = fan_in.iter().copied().max().unwrap_or(0).max(1) as f64;
+        let max_fan_out = successors.iter().map(Vec::len).max().unwrap_or(0).max(1) as f64;
+        let max_betweenness = max_of(&betweenness);
+        let max_page_rank = max_of(&page_rank);
+
+        let mut ranked: Vec<FunctionCentrality> = methods.iter()
+            .enumerate()
+            .map(|(index, method)| {
+                let score = 0.35 * page_rank[index] / max_page_rank
+                    + 0.35 * betweenness[index] / max_betweenness
+                    + 0.2 * fan_in[index] as f64 / max_fan_in
+                    + 0.1 * successors[index].len() as f64 / max_fan_out;
+
+                FunctionCentrality {
+                    method: (*method).clone(),
+                    fan_in: fan_in[index],
+                    fan_out: successors[index].len(),
+                    betweenness: betweenness[index],
+                    page_rank: page_rank[index],
+                    score,
+                }
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.method.display_name().cmp(&b.method.display_name())));
+
+        let mut package_scores: BTreeMap<String, f64> = BTreeMap::new();
+        for function in &ranked {
+            *package_scores.entry(function.method.package_name()).or_default() += function.score;
+        }
+
+        let scores = ranked.iter().map(|function| (function.method.clone(), function.score)).collect();
+        Centrality { ranked, package_scores, scores }
+    }
+}
+
+impl Centrality {
+    /// Score of a single function (0.0 when unknown)
+    pub fn score_of(&self, method: &MethodSignature) -> f64 {
+        self.scores.get(method).copied().unwrap_or(0.0)
+    }
+
+    /// Score of a set of functions: its most central member, with the rest as a tie-breaker
+    pub fn score_of_all<'a>(&self, methods: impl IntoIterator<Item = &'a MethodSignature>) -> f64 {
+        let scores: Vec<f64> = methods.into_iter().map(|method| self.score_of(method)).collect();
+        let max = scores.iter().copied().fold(0.0, f64::max);
+        max + scores.iter().sum::<f64>() * 1e-3
+    }
+
+    pub fn package_score(&self, package: &str) -> f64 {
+        self.package_scores.get(package).copied().unwrap_or(0.0)
+    }
+}
+
+/// Brandes' algorithm on the unweighted call graph, normalized to 0.0..1.0.
+/// Large graphs use an evenly spaced sample of source functions.
+fn betweenness(successors: &[Vec<usize>]) -> Vec<f64> {
+    let count = successors.len();
+    let mut centrality = vec![0.0; count];
+    if count < 3 {
+        return centrality;
+    }
+
+    let step = count.div_ceil(BETWEENNESS_MAX_SOURCES).max(1);
+    let sources: Vec<usize> = (0..count).step_by(step).collect();
+
+    for &source in &sources {
+        let mut stack = Vec::new();
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); count];
+        let mut paths = vec![0.0; count];
+        let mut distance = vec![usize::MAX; count];
+        paths[source] = 1.0;
+        distance[source] = 0;
+
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            stack.push(node);
+            for &next in &successors[node] {
+                if distance[next] == usize::MAX {
+                    distance[next] = distance[node] + 1;
+                    queue.push_back(next);
+                }
+                if distance[next] == distance[node] + 1 {
+                    paths[next] += paths[node];
+                    predecessors[next].push(node);
+                }
+            }
+        }
+
+        let mut dependency = vec![0.0; count];
+        while let Some(node) = stack.pop() {
+            for &previous in &predecessors[node] {
+                dependency[previous] += paths[previous] / paths[node] * (1.0 + dependency[node]);
+            }
+            if node != source {
+                centrality[node] += dependency[node];
+            }
+        }
+    }
+
+    let scale = step as f64 / ((count - 1) * (count - 2)) as f64;
+    centrality.iter().map(|value| value * scale).collect()
+}
+
+/// PageRank with dangling functions (no callees) spreading their rank evenly
+fn page_rank(successors: &[Vec<usize>]) -> Vec<f64> {
+    let count = successors.len();
+    if count == 0 {
+        return Vec::new();
+    }
+
+    let base = (1.0 - DAMPING) / count as f64;
+    let mut rank = vec![1.0 / count as f64; count];
+
+    for _ in 0..PAGE_RANK_MAX_ITERATIONS {
+        let dangling: f64 = (0..count).filter(|&node| successors[node].is_empty()).map(|node| rank[node]).sum();
+        let mut next = vec![base + DAMPING * dangling / count as f64; count];
+
+        for (node, targets) in successors.iter().enumerate() {
+            if targets.is_empty() {
+                continue;
+            }
+            let share = DAMPING * rank[node] / targets.len() as f64;
+            for &target in targets {
+                next[target] += share;
+            }
+        }
+
+        let change: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if change < PAGE_RANK_TOLERANCE {
+            break;
+        }
+    }
+
+    rank
+}
+
+impl Default for CentralityAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_betweenness_of_bridge() {
+        // 0 -> 2, 1 -> 2, 2 -> 3, 2 -> 4: everything passes through 2
+        let successors = vec![vec![2], vec![2], vec![3, 4], vec![], vec![]];
+        let betweenness = betweenness(&successors);
+
+        assert!(betweenness[2] > 0.0);
+        assert!(betweenness.iter().enumerate().filter(|(node, _)| *node != 2).all(|(_, value)| *value == 0.0));
+    }
+
+    #[test]
+    fn test_page_rank_favours_shared_callees() {
+        // 0, 1 and 2 all call 3
+        let successors = vec![vec![3], vec![3], vec![3], vec![]];
+        let rank = page_rank(&successors);
+
+        assert!((rank.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+        assert!(rank[3] > rank[0] * 2.0);
+    }
+}
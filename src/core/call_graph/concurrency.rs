@@ -0,0 +1,544 @@
+// src/core/call_graph/concurrency.rs
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::LazyLock;
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+
+use super::super::{ParsedCall, ParsedFile};
+use super::call_graph::signature_map;
+use super::side_effects::receiver_name;
+use super::{CallChain, CallChainGroup, CallGraph, CallType, MethodSignature};
+
+/// What a concurrency construct does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ConcurrencyKind {
+    /// Starts a task, thread or process that runs alongside the caller
+    Spawn,
+    /// Hands work to a thread pool or executor
+    ThreadPool,
+    /// Waits on several concurrent operations at once (`join_all`, `gather`, `Promise.all`)
+    Join,
+    /// Creates or uses a channel or queue between concurrent workers
+    Channel,
+    /// Acquires a lock, mutex, semaphore or monitor
+    Lock,
+    /// Crosses between synchronous and asynchronous code
+    AsyncBoundary,
+}
+
+impl ConcurrencyKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConcurrencyKind::Spawn => "spawns",
+            ConcurrencyKind::ThreadPool => "submits to a pool",
+            ConcurrencyKind::Join => "joins",
+            ConcurrencyKind::Channel => "channel",
+            ConcurrencyKind::Lock => "locks",
+            ConcurrencyKind::AsyncBoundary => "async boundary",
+        }
+    }
+
+    /// Whether the construct makes the workflow run several things at once
+    pub fn fans_out(&self) -> bool {
+        matches!(self, ConcurrencyKind::Spawn | ConcurrencyKind::ThreadPool | ConcurrencyKind::Join)
+    }
+}
+
+/// A concurrency construct used in a function
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConcurrencySite {
+    pub kind: ConcurrencyKind,
+    /// The construct as written, e.g. `tokio::spawn` or `synchronized (this)`
+    pub construct: String,
+    /// The lock or channel involved (`cache`, `this`), when there is one
+    pub target: Option<String>,
+    pub line: usize,
+    /// A blocking lock whose guard is still held at a later `await`
+    #[serde(default)]
+    pub held_across_await: bool,
+}
+
+/// Shared state locked by more than one function
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contention {
+    /// The lock, qualified by its owner, e.g. `OrderCache.entries`
+    pub state: String,
+    pub holders: Vec<MethodSignature>,
+}
+
+/// Concurrency constructs each function uses, and the shared state they contend on
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Concurrency {
+    #[serde(with = "signature_map")]
+    pub sites: HashMap<MethodSignature, Vec<ConcurrencySite>>,
+    pub contention: Vec<Contention>,
+}
+
+/// A library API or statement that starts, coordinates or synchronizes concurrent work
+struct ConcurrencyRule {
+    /// Languages the rule applies to (all when empty)
+    languages: &'static [&'static str],
+    /// Last segment of the receiver, lower-cased; a receiver whose last segment is one of them matches,
+    /// an empty list matches calls without a receiver and `ANY_OR_NO_RECEIVER` matches calls with or without one
+    receivers: &'static [&'static str],
+    /// Called names, lower-cased
+    callees: &'static [&'static str],
+    kind: ConcurrencyKind,
+    /// Text the file must contain, for calls too generic to trust otherwise
+    requires: Option<&'static str>,
+    /// Only calls without arguments (`mutex.lock()`, not `file.lock(path)`)
+    bare: bool,
+}
+
+const fn rule(
+    languages: &'static [&'static str],
+    receivers: &'static [&'static str],
+    callees: &'static [&'static str],
+    kind: ConcurrencyKind,
+) -> ConcurrencyRule {
+    ConcurrencyRule { languages, receivers, callees, kind, requires: None, bare: false }
+}
+
+impl ConcurrencyRule {
+    const fn requires(self, text: &'static str) -> Self {
+        Self { requires: Some(text), ..self }
+    }
+
+    const fn bare(self) -> Self {
+        Self { bare: true, ..self }
+    }
+}
+
+use ConcurrencyKind::{AsyncBoundary, Channel, Join, Lock, Spawn, ThreadPool};
+
+const RUST: &[&str] = &["rust"];
+const JAVA: &[&str] = &["java"];
+const PYTHON: &[&str] = &["python"];
+const CSHARP: &[&str] = &["csharp"];
+const JAVASCRIPT: &[&str] = &["javascript"];
+/// Matches calls on any receiver, or none
+const ANY_OR_NO_RECEIVER: &[&str] = &[""];
+/// Receivers whose `lock()` takes a standard stream rather than shared state
+const STREAMS: &[&str] = &["stdin", "stdout", "stderr"];
+
+const LOCK_NAMES: &[&str] = &["lock", "mutex", "semaphore", "sem", "condition", "cond"];
+const POOL_NAMES: &[&str] = &["executor", "executorservice", "pool", "threadpool", "taskexecutor", "scheduler"];
+
+const RULES: &[ConcurrencyRule] = &[
+    // Rust
+    rule(RUST, &["tokio", "task", "thread", "async_std", "smol", "rayon"], &["spawn", "spawn_local", "scope"], Spawn),
+    rule(RUST, &["tokio", "task", "runtime", "handle"], &["spawn_blocking", "block_in_place"], ThreadPool),
+    rule(RUST, &["pool", "threadpool", "executor", "runtime", "rt", "handle"], &["execute", "spawn", "install"], ThreadPool),
+    rule(RUST, ANY_OR_NO_RECEIVER, &["par_iter", "par_iter_mut", "into_par_iter", "par_chunks", "par_bridge"], ThreadPool).requires("rayon"),
+    rule(RUST, ANY_OR_NO_RECEIVER, &["join_all", "try_join_all", "select_all", "join_next"], Join),
+    rule(RUST, &["mpsc", "oneshot", "broadcast", "watch", "crossbeam_channel", "flume", "async_channel"], &["channel", "unbounded_channel", "sync_channel", "bounded", "unbounded"], Channel),
+    rule(RUST, &["tx", "sender", "rx", "receiver"], &["send", "try_send", "blocking_send", "send_timeout", "recv", "try_recv", "blocking_recv", "recv_timeout"], Channel),
+    rule(RUST, ANY_OR_NO_RECEIVER, &["lock", "try_lock", "blocking_lock"], Lock).bare(),
+    rule(RUST, ANY_OR_NO_RECEIVER, &["read", "write", "try_read", "try_write"], Lock).bare().requires("RwLock"),
+    rule(RUST, &["semaphore", "permits"], &["acquire", "acquire_owned", "try_acquire"], Lock),
+    rule(RUST, &["runtime", "rt", "handle", "executor", "futures"], &["block_on"], AsyncBoundary),
+    // Java
+    rule(JAVA, ANY_OR_NO_RECEIVER, &["synchronized"], Lock),
+    rule(JAVA, ANY_OR_NO_RECEIVER, &["lock", "lockinterruptibly", "trylock"], Lock).bare(),
+    rule(JAVA, &["semaphore"], &["acquire", "tryacquire"], Lock),
+    rule(JAVA, &["thread"], &["thread"], Spawn),
+    rule(JAVA, &["thread"], &["start", "startvirtualthread", "ofvirtual"], Spawn),
+    rule(JAVA, &["executors"], &["newfixedthreadpool", "newcachedthreadpool", "newsinglethreadexecutor", "newscheduledthreadpool", "newworkstealingpool", "newvirtualthreadpertaskexecutor"], ThreadPool),
+    rule(JAVA, POOL_NAMES, &["submit", "execute", "invokeall", "invokeany", "schedule", "scheduleatfixedrate", "schedulewithfixeddelay"], ThreadPool),
+    rule(JAVA, &["completablefuture"], &["supplyasync", "runasync"], ThreadPool),
+    rule(JAVA, &["completablefuture"], &["allof", "anyof"], Join),
+    rule(JAVA, &["future"], &["get", "join"], AsyncBoundary).bare(),
+    rule(JAVA, &["queue"], &["put", "take", "offer", "poll"], Channel).requires("BlockingQueue"),
+    // Python
+    rule(PYTHON, LOCK_NAMES, &["with", "acquire"], Lock),
+    rule(PYTHON, &["threading", "multiprocessing"], &["thread", "process"], Spawn),
+    rule(PYTHON, &[], &["thread"], Spawn).requires("threading"),
+    rule(PYTHON, &["thread", "process", "worker"], &["start"], Spawn),
+    rule(PYTHON, &["asyncio", "loop", "taskgroup", "tg"], &["create_task", "ensure_future"], Spawn),
+    rule(PYTHON, ANY_OR_NO_RECEIVER, &["threadpoolexecutor", "processpoolexecutor", "pool"], ThreadPool),
+    rule(PYTHON, POOL_NAMES, &["submit", "map", "apply_async", "map_async", "imap", "starmap"], ThreadPool),
+    rule(PYTHON, &["loop", "asyncio"], &["run_in_executor", "to_thread"], ThreadPool),
+    rule(PYTHON, &["asyncio"], &["gather", "wait", "as_completed"], Join),
+    rule(PYTHON, &["queue", "asyncio", "multiprocessing"], &["queue", "simplequeue", "priorityqueue", "lifoqueue", "pipe"], Channel),
+    rule(PYTHON, &["queue", "q"], &["put", "get", "put_nowait", "get_nowait"], Channel).requires("Queue"),
+    rule(PYTHON, &["asyncio", "loop"], &["run", "run_until_complete", "run_coroutine_threadsafe"], AsyncBoundary),
+    // C#
+    rule(CSHARP, ANY_OR_NO_RECEIVER, &["lock"], Lock),
+    rule(CSHARP, &["monitor"], &["enter", "tryenter"], Lock),
+    rule(CSHARP, LOCK_NAMES, &["wait", "waitasync", "waitone"], Lock),
+    rule(CSHARP, &["task", "factory"], &["run", "startnew"], Spawn),
+    rule(CSHARP, &["thread"], &["thread"], Spawn),
+    rule(CSHARP, &["thread"], &["start"], Spawn),
+    rule(CSHARP, &["threadpool"], &["queueuserworkitem", "unsafequeueuserworkitem"], ThreadPool),
+    rule(CSHARP, &["parallel"], &["for", "foreach", "foreachasync", "invoke"], ThreadPool),
+    rule(CSHARP, &["task"], &["whenall", "whenany", "waitall", "waitany"], Join),
+    rule(CSHARP, &["channel"], &["createbounded", "createunbounded"], Channel),
+    rule(CSHARP, &["writer", "reader"], &["writeasync", "trywrite", "readasync", "tryread", "readallasync"], Channel).requires("Channel"),
+    rule(CSHARP, ANY_OR_NO_RECEIVER, &["getresult"], AsyncBoundary),
+    rule(CSHARP, &["task"], &["wait"], AsyncBoundary),
+    // JavaScript
+    rule(JAVASCRIPT, &["worker"], &["constructor"], Spawn),
+    rule(JAVASCRIPT, &["cluster"], &["fork"], Spawn),
+    rule(JAVASCRIPT, &["promise"], &["all", "allsettled", "race", "any"], Join),
+    rule(JAVASCRIPT, &["messagechannel"], &["constructor"], Channel),
+    rule(JAVASCRIPT, ANY_OR_NO_RECEIVER, &["postmessage"], Channel),
+    rule(JAVASCRIPT, &["locks"], &["request"], Lock),
+    rule(JAVASCRIPT, LOCK_NAMES, &["acquire", "runexclusive"], Lock).requires("async-mutex"),
+];
+
+/// Statements recorded as a call to their keyword on the locked object
+const LOCK_BLOCKS: &[(&str, &str)] = &[("java", "synchronized"), ("csharp", "lock"), ("python", "with")];
+
+/// Finds where workflows start concurrent work, hand it between workers and lock shared state
+pub struct ConcurrencyAnalyzer;
+
+impl ConcurrencyAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn analyze(&self, call_graph: &CallGraph, files: &[ParsedFile]) -> Concurrency {
+        let mut sites: HashMap<MethodSignature, Vec<ConcurrencySite>> = HashMap::new();
+
+        for file in files {
+            let held = locks_held_across_await(file);
+            for call in &file.calls {
+                let Some(mut site) = detect_call(file, call) else {
+                    continue;
+                };
+                let Some(method) = call_graph.find_containing_method(file, call.line) else {
+                    continue;
+                };
+                site.held_across_await = held.contains(&call.line);
+                sites.entry(method).or_default().push(site);
+            }
+        }
+
+        for node in call_graph.nodes.values() {
+            // A `synchronized` method holds its object's monitor, like `synchronized (this)`
+            if node.signature.file_path.extension().is_some_and(|ext| ext == "java")
+                && node.signature.signature.split('(').next().unwrap_or_default().split_whitespace().any(|word| word == "synchronized") {
+                sites.entry(node.signature.clone()).or_default().push(ConcurrencySite {
+                    kind: Lock,
+                    construct: "synchronized method".to_string(),
+                    target: Some("this".to_string()),
+                    line: node.line_range.0,
+                    held_across_await: false,
+                });
+            }
+        }
+
+        // Synchronous code calling an async function starts (or drops) a future it never awaits
+        for edge in &call_graph.edges {
+            let (Some(caller), Some(callee)) = (call_graph.nodes.get(&edge.caller), call_graph.nodes.get(&edge.callee)) else {
+                continue;
+            };
+            if caller.is_async || !callee.is_async || matches!(edge.call_type, CallType::Async) {
+                continue;
+            }
+            let existing = sites.entry(edge.caller.clone()).or_default();
+            if existing.iter().any(|site| site.line == edge.call_site_line && site.kind != Lock) {
+                continue;
+            }
+            existing.push(ConcurrencySite {
+                kind: AsyncBoundary,
+                construct: format!("{}() (async, not awaited)", edge.callee.method_name),
+                target: None,
+                line: edge.call_site_line,
+                held_across_await: false,
+            });
+        }
+
+        sites.retain(|_, sites| !sites.is_empty());
+        for method_sites in sites.values_mut() {
+            method_sites.sort_by(|a, b| a.line.cmp(&b.line).then(a.kind.cmp(&b.kind)));
+        }
+
+        let contention = find_contention(&sites);
+        Concurrency { sites, contention }
+    }
+}
+
+impl Default for ConcurrencyAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Concurrency {
+    /// Constructs used along a traced path, in the order its functions are called
+    pub fn sites_on_chain<'a>(&'a self, chain: &'a CallChain) -> Vec<(&'a MethodSignature, &'a ConcurrencySite)> {
+        let mut seen = HashSet::new();
+        chain.steps.iter()
+            .filter(|step| seen.insert(&step.method))
+            .filter_map(|step| self.sites.get_key_value(&step.method))
+            .flat_map(|(method, sites)| sites.iter().map(move |site| (method, site)))
+            .collect()
+    }
+
+    /// Shared state locked by one of the group's functions and by some other function
+    pub fn contention_in_group<'a>(&'a self, group: &CallChainGroup) -> Vec<&'a Contention> {
+        self.contention.iter()
+            .filter(|contention| contention.holders.iter().any(|holder| group.all_methods.contains(holder)))
+            .collect()
+    }
+
+    /// Whether any of the group's functions use a concurrency construct
+    pub fn touches_group(&self, group: &CallChainGroup) -> bool {
+        group.all_methods.iter().any(|method| self.sites.contains_key(method))
+    }
+}
+
+/// Match a parsed call against the rules for its file's language
+fn detect_call(file: &ParsedFile, call: &ParsedCall) -> Option<ConcurrencySite> {
+    let callee = call.callee.to_lowercase();
+    let receiver = call.receiver.as_deref().map(receiver_name);
+
+    let rule = RULES.iter().find(|rule| {
+        (rule.languages.is_empty() || rule.languages.contains(&file.language.as_str()))
+            && rule.callees.contains(&callee.as_str())
+            && match &receiver {
+                Some(receiver) => (rule.receivers == ANY_OR_NO_RECEIVER || rule.receivers.contains(&receiver.as_str()))
+                    && !(rule.kind == Lock && STREAMS.contains(&receiver.as_str())),
+                None => rule.receivers.is_empty() || rule.receivers == ANY_OR_NO_RECEIVER,
+            }
+            && (!rule.bare || call.arg_count == 0)
+            && rule.requires.is_none_or(|text| file.source_content.contains(text))
+    })?;
+
+    let is_block = LOCK_BLOCKS.contains(&(file.language.as_str(), call.callee.as_str()));
+    let construct = match &call.receiver {
+        Some(written) if is_block => format!("{} ({})", call.callee, written),
+        Some(written) if !written.contains(['(', '\n']) => {
+            let path = format!("{}::{}", written, call.callee);
+            let line = file.source_content.lines().nth(call.line.saturating_sub(1)).unwrap_or_default();
+            if line.contains(&path) { path } else { format!("{}.{}", written, call.callee) }
+        }
+        Some(_) => format!("{}.{}", receiver.clone().unwrap_or_default(), call.callee),
+        None => call.callee.clone(),
+    };
+
+    let target = match rule.kind {
+        Lock | Channel => call.receiver.as_deref().map(lock_target),
+        _ => None,
+    };
+
+    Some(ConcurrencySite { kind: rule.kind, construct, target, line: call.line, held_across_await: false })
+}
+
+/// The locked object without `self.`/`this.` or borrow markers (`&self.state.inner` → `state.inner`)
+fn lock_target(receiver: &str) -> String {
+    let target = receiver.trim().trim_start_matches(['&', '*']);
+    let target = target.strip_prefix("self.").or_else(|| target.strip_prefix("this.")).unwrap_or(target);
+    target.trim_start_matches('_').to_string()
+}
+
+/// Locks acquired by more than one function, keyed by the class (or file) owning the locked state
+fn find_contention(sites: &HashMap<MethodSignature, Vec<ConcurrencySite>>) -> Vec<Contention> {
+    let mut holders: BTreeMap<String, Vec<&MethodSignature>> = BTreeMap::new();
+    for (method, method_sites) in sites {
+        for site in method_sites.iter().filter(|site| site.kind == Lock) {
+            let Some(target) = &site.target else {
+                continue;
+            };
+            let owner = method.class_name.clone().unwrap_or_else(|| {
+                method.file_path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default()
+            });
+            let state = if target == "this" { owner } else { format!("{}.{}", owner, target) };
+            let methods = holders.entry(state).or_default();
+            if !methods.contains(&method) {
+                methods.push(method);
+            }
+        }
+    }
+
+    holders.into_iter()
+        .filter(|(_, methods)| methods.len() > 1)
+        .map(|(state, mut methods)| {
+            methods.sort_by_key(|method| method.to_unique_string());
+            Contention { state, holders: methods.into_iter().cloned().collect() }
+        })
+        .collect()
+}
+
+/// A Rust lock guard bound with `let`, capturing the binding
+static RUST_GUARD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*let\s+(?:mut\s+)?(\w+)[^=]*=.*\.(?:lock|try_lock|read|write)\(\)(?:\.unwrap\(\)|\.expect\([^)]*\)|\?)?\s*;")
+        .expect("valid regex")
+});
+
+/// The opening of an `async` block or closure body, which runs apart from the enclosing guard
+static NESTED_BODY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\basync\s+(?:move\s+)?\{|\|[^|]*\|\s*(?:async\s+(?:move\s+)?)?\{").expect("valid regex")
+});
+
+/// Lines of blocking locks still held when the code awaits: a Rust guard bound with `let`
+/// before an `.await` in the same block, or a Python `with lock:` block containing an `await`
+pub fn locks_held_across_await(file: &ParsedFile) -> Vec<usize> {
+    let lines: Vec<&str> = file.source_content.lines().collect();
+    let awaits: Vec<usize> = file.calls.iter()
+        .filter(|call| matches!(call.call_type, CallType::Async))
+        .map(|call| call.line)
+        .collect();
+
+    let mut held = Vec::new();
+    for call in &file.calls {
+        // `.lock().await` and `async with` take async-aware locks, which may be held across awaits
+        if matches!(call.call_type, CallType::Async) || detect_call(file, call).is_none_or(|site| site.kind != Lock) {
+            continue;
+        }
+        let Some(text) = lines.get(call.line.saturating_sub(1)) else {
+            continue;
+        };
+        let indent = indentation(text);
+
+        let scope: Vec<(usize, &str)> = match file.language.as_str() {
+            "rust" => {
+                let Some(binding) = RUST_GUARD.captures(text).map(|captures| captures[1].to_string()) else {
+                    continue;
+                };
+                let release = format!("drop({})", binding);
+                // Awaits inside a spawned `async` block or a closure do not run while this guard is held
+                let mut nested_depth = 0;
+                lines.iter().enumerate().skip(call.line)
+                    .take_while(|(_, line)| line.trim().is_empty() || indentation(line) >= indent)
+                    .take_while(|(_, line)| !line.contains(&release))
+                    .filter(|(_, line)| {
+                        let body = match NESTED_BODY.find(line) {
+                            _ if nested_depth > 0 => *line,
+                            Some(opening) => &line[opening.start()..],
+                            None => return true,
+                        };
+                        nested_depth = (nested_depth + brace_balance(body)).max(0);
+                        false
+                    })
+                    .map(|(index, line)| (index + 1, *line))
+                    .collect()
+            }
+            "python" if call.callee == "with" => lines.iter().enumerate().skip(call.line)
+                .take_while(|(_, line)| line.trim().is_empty() || indentation(line) > indent)
+                .map(|(index, line)| (index + 1, *line))
+                .collect(),
+            _ => continue,
+        };
+
+        if scope.iter().any(|(line, _)| awaits.contains(line)) {
+            held.push(call.line);
+        }
+    }
+    held
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Opening minus closing braces in `text`
+fn brace_balance(text: &str) -> i32 {
+    text.chars().map(|c| match c {
+        '{' => 1,
+        '}' => -1,
+        _ => 0,
+    }).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::{method, node, parsed_file};
+    use crate::core::languages::{LanguageParser, PythonParser, RustParser};
+
+    #[test]
+    fn test_finds_fan_out_and_contention() {
+        let source = r#"
+impl Cache {
+    async fn refresh(&self, keys: Vec<String>) {
+        let handles: Vec<_> = keys.into_iter().map(|key| tokio::spawn(load(key))).collect();
+        let guard = self.entries.lock().unwrap();
+        notify(&guard).await;
+    }
+
+    fn evict(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+"#;
+        let calls = RustParser::new().unwrap().extract_calls(source).unwrap();
+        let file = ParsedFile { calls, ..parsed_file("/p/src/cache.rs", "rust", source) };
+
+        let mut graph = CallGraph::new();
+        graph.add_node(node(method("/p/src/cache.rs", Some("Cache"), "refresh"), (3, 7)));
+        graph.add_node(node(method("/p/src/cache.rs", Some("Cache"), "evict"), (9, 11)));
+
+        let concurrency = ConcurrencyAnalyzer::new().analyze(&graph, &[file]);
+        let refresh = &concurrency.sites[&graph.nodes.keys().find(|m| m.method_name == "refresh").unwrap().clone()];
+
+        assert_eq!(refresh[0].kind, Spawn);
+        assert_eq!(refresh[0].construct, "tokio::spawn");
+        assert_eq!(refresh[1].kind, Lock);
+        assert_eq!(refresh[1].target.as_deref(), Some("entries"));
+        assert!(refresh[1].held_across_await);
+
+        assert_eq!(concurrency.contention.len(), 1);
+        assert_eq!(concurrency.contention[0].state, "Cache.entries");
+        assert_eq!(concurrency.contention[0].holders.len(), 2);
+    }
+
+    #[test]
+    fn test_receivers_match_on_their_last_segment() {
+        let python = "with block:\n    pass\nwith self._lock:\n    pass\n";
+        let calls = PythonParser::new().unwrap().extract_calls(python).unwrap();
+        let file = ParsedFile { calls, ..parsed_file("/p/app/jobs.py", "python", python) };
+        let locks: Vec<usize> = file.calls.iter().filter_map(|call| detect_call(&file, call)).map(|site| site.line).collect();
+        assert_eq!(locks, vec![3]);
+
+        let rust = "fn report(&self) {\n    let out = io::stdout().lock();\n    let jobs = self.jobs.lock();\n}\n";
+        let calls = RustParser::new().unwrap().extract_calls(rust).unwrap();
+        let file = ParsedFile { calls, ..parsed_file("/p/src/jobs.rs", "rust", rust) };
+        let locks: Vec<usize> = file.calls.iter().filter_map(|call| detect_call(&file, call)).map(|site| site.line).collect();
+        assert_eq!(locks, vec![3]);
+    }
+
+    #[test]
+    fn test_awaits_in_spawned_blocks_do_not_hold_the_guard() {
+        let source = r#"
+async fn place(&self, order: Order) {
+    let guard = self.orders.lock().unwrap();
+    tokio::spawn(async move { audit(order).await });
+    tokio::spawn(async move {
+        notify().await;
+    });
+    let callback = |id| {
+        send(id).await;
+    };
+}
+
+async fn cancel(&self, id: u64) {
+    let guard = self.orders.lock().unwrap();
+    tokio::spawn(async move { audit(id).await });
+    refund(id).await;
+}
+"#;
+        let calls = RustParser::new().unwrap().extract_calls(source).unwrap();
+        let file = ParsedFile { calls, ..parsed_file("/p/src/orders.rs", "rust", source) };
+
+        assert_eq!(locks_held_across_await(&file), vec![14]);
+    }
+
+    #[test]
+    fn test_python_lock_held_across_await() {
+        let source = r#"
+async def store(self, user):
+    async with self.pool_lock:
+        await self.db.save(user)
+    with self._lock:
+        self.cache[user.id] = user
+    with self._lock:
+        await self.flush()
+"#;
+        let calls = PythonParser::new().unwrap().extract_calls(source).unwrap();
+        let file = ParsedFile { calls, ..parsed_file("/p/app/store.py", "python", source) };
+
+        assert_eq!(locks_held_across_await(&file), vec![7]);
+    }
+}
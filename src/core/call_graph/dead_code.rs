@@ -0,0 +1,241 @@
+// src/core/call_graph/dead_code.rs
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use serde::{Serialize, Deserialize};
+
+use super::{CallGraph, CallNode, EntryPoint, EntryPointType, MethodSignature, TestCoverage};
+
+/// Names the runtime, a framework or reflection calls without a visible call site
+const RUNTIME_CALLED_NAMES: &[&str] = &[
+    "new", "default", "drop", "fmt", "from", "clone", "eq", "hash", "deref",
+    "constructor", "toString", "equals", "hashCode", "compareTo", "close", "run", "call",
+    "Dispose", "ToString", "Equals", "GetHashCode", "Main",
+];
+
+/// Finds functions that no production entry point can reach
+pub struct DeadCodeAnalyzer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DeadCodeKind {
+    /// Private function nothing calls
+    UncalledPrivate,
+    /// Public function that only tests reach
+    TestOnly,
+    /// Function no production entry point reaches
+    Unreachable,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DeadCodeConfidence {
+    High,
+    Medium,
+    Low,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadCodeFinding {
+    pub method: MethodSignature,
+    pub line: usize,
+    pub kind: DeadCodeKind,
+    pub confidence: DeadCodeConfidence,
+    /// Why the function looks dead, and why it might not be
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeadCodeReport {
+    /// Findings keyed by package, most confident first
+    pub by_package: BTreeMap<String, Vec<DeadCodeFinding>>,
+}
+
+impl DeadCodeAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Classify every non-test function production code cannot reach
+    pub fn analyze(&self, call_graph: &CallGraph, entry_points: &[EntryPoint], coverage: &TestCoverage) -> DeadCodeReport {
+        let live = self.reachable_from_production(call_graph, entry_points);
+        let unresolved_names: HashSet<&str> = call_graph.unresolved_calls.iter()
+            .map(|call| call.callee_name.as_str())
+            .collect();
+
+        let mut by_package: BTreeMap<String, Vec<DeadCodeFinding>> = BTreeMap::new();
+        for node in call_graph.nodes.values() {
+            if node.is_test || live.contains(&node.signature) {
+                continue;
+            }
+
+            let signature = &node.signature;
+            let has_callers = call_graph.in_degree(signature) > 0;
+            let is_public = node.visibility == "public";
+            let tested = !coverage.tests_for(signature).is_empty();
+
+            let (kind, reason) = if !is_public && !has_callers {
+                (DeadCodeKind::UncalledPrivate, "Private and never called".to_string())
+            } else if is_public && tested {
+                (DeadCodeKind::TestOnly, "Public, but only reached from tests".to_string())
+            } else if tested {
+                (DeadCodeKind::Unreachable, "Only reached from tests".to_string())
+            } else if has_callers {
+                (DeadCodeKind::Unreachable, "Only called from other unreachable code".to_string())
+            } else {
+                (DeadCodeKind::Unreachable, "Never called and not an entry point".to_string())
+            };
+
+            let mut reasons = vec![reason];
+            let confidence = self.confidence(call_graph, node, kind, &unresolved_names, &mut reasons);
+
+            by_package.entry(signature.package_name()).or_default().push(DeadCodeFinding {
+                method: signature.clone(),
+                line: node.line_range.0,
+                kind,
+                confidence,
+                reasons,
+            });
+        }
+
+        for findings in by_package.values_mut() {
+            findings.sort_by(|a, b| {
+                a.confidence.cmp(&b.confidence)
+                    .then(a.method.file_path.cmp(&b.method.file_path))
+                    .then(a.line.cmp(&b.line))
+            });
+        }
+
+        DeadCodeReport { by_package }
+    }
+
+    /// Everything reachable from `main` and from public functions that nothing in the project calls
+    fn reachable_from_production<'a>(&self, call_graph: &'a CallGraph, entry_points: &'a [EntryPoint]) -> HashSet<&'a MethodSignature> {
+        let mut roots: Vec<&MethodSignature> = entry_points.iter()
+            .filter(|ep| matches!(ep.entry_type, EntryPointType::Main | EntryPointType::ExternalInterface))
+            .map(|ep| &ep.signature)
+            .collect();
+        roots.extend(call_graph.nodes.values()
+            .filter(|node| !node.is_test && node.visibility == "public" && call_graph.in_degree(&node.signature) == 0)
+            .map(|node| &node.signature));
+
+        let mut live: HashSet<&MethodSignature> = roots.iter().copied().collect();
+        let mut queue: VecDeque<&MethodSignature> = roots.into_iter().collect();
+        while let Some(current) = queue.pop_front() {
+            for callee in call_graph.get_callees(current) {
+                if live.insert(callee) {
+                    queue.push_back(callee);
+                }
+            }
+        }
+
+        live
+    }
+
+    /// Lower confidence when the function could be reached in ways the call graph cannot see
+    fn confidence(
+        &self,
+        call_graph: &CallGraph,
+        node: &CallNode,
+        kind: DeadCodeKind,
+        unresolved_names: &HashSet<&str>,
+        reasons: &mut Vec<String>,
+    ) -> DeadCodeConfidence {
+        let signature = &node.signature;
+        let mut confidence = DeadCodeConfidence::High;
+        let mut lower_to = |level: DeadCodeConfidence, reason: String, reasons: &mut Vec<String>| {
+            confidence = confidence.max(level);
+            reasons.push(reason);
+        };
+
+        if RUNTIME_CALLED_NAMES.contains(&signature.method_name.as_str())
+            || (signature.method_name.starts_with("__") && signature.method_name.ends_with("__"))
+        {
+            lower_to(DeadCodeConfidence::Low, "Name suggests the runtime or a framework calls it".to_string(), reasons);
+        }
+
+        if let Some(class_name) = &signature.class_name {
+            let overridden = call_graph.type_hierarchy.supertypes_of(class_name).into_iter().find(|supertype| {
+                let declared_in_project = call_graph.nodes.keys().any(|other| other.class_name.as_deref() == Some(supertype));
+                !declared_in_project || call_graph.nodes.keys().any(|other| {
+                    other.class_name.as_deref() == Some(supertype) && other.method_name == signature.method_name
+                })
+            });
+            if let Some(supertype) = overridden {
+                lower_to(DeadCodeConfidence::Low, format!("May be called through `{}` (dynamic dispatch)", supertype), reasons);
+            }
+        }
+
+        if unresolved_names.contains(signature.method_name.as_str()) {
+            lower_to(DeadCodeConfidence::Medium, "An unresolved call uses the same name".to_string(), reasons);
+        }
+
+        let language = signature.file_path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+        if matches!(language, "py" | "js" | "ts") {
+            lower_to(DeadCodeConfidence::Medium, "Dynamic language; reflection or string dispatch is common".to_string(), reasons);
+        }
+
+        if node.visibility == "public" && kind != DeadCodeKind::UncalledPrivate {
+            lower_to(DeadCodeConfidence::Medium, "Public, so code outside this project may call it".to_string(), reasons);
+        }
+
+        confidence
+    }
+}
+
+impl DeadCodeReport {
+    pub fn total(&self) -> usize {
+        self.by_package.values().map(Vec::len).sum()
+    }
+
+    /// Findings at or above the given confidence
+    pub fn at_least(&self, confidence: DeadCodeConfidence) -> impl Iterator<Item = &DeadCodeFinding> {
+        self.by_package.values().flatten().filter(move |finding| finding.confidence <= confidence)
+    }
+}
+
+impl Default for DeadCodeAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::{self, method};
+    use crate::core::call_graph::{EntryPointDetector, TestCoverageAnalyzer};
+
+    fn node(name: &str, visibility: &str, is_test: bool) -> CallNode {
+        CallNode {
+            visibility: visibility.to_string(),
+            is_test,
+            ..test_support::node(method("/p/src/app/lib.rs", None, name), (1, 2))
+        }
+    }
+
+    #[test]
+    fn test_classifies_dead_code() {
+        let nodes = [
+            node("main", "private", false),
+            node("used", "private", false),
+            node("orphan", "private", false),
+            node("helper", "private", false),
+            node("only_tested", "public", false),
+            node("test_only_tested", "private", true),
+        ];
+        let calls: Vec<_> = [(0, 1), (2, 3), (5, 4)].into_iter()
+            .map(|(caller, callee)| (&nodes[caller].signature, &nodes[callee].signature, 1))
+            .collect();
+        let graph = test_support::graph(&nodes, &calls);
+
+        let entry_points = EntryPointDetector::new().detect_entry_points(&graph).unwrap();
+        let coverage = TestCoverageAnalyzer::new().analyze(&graph, &entry_points);
+        let report = DeadCodeAnalyzer::new().analyze(&graph, &entry_points, &coverage);
+
+        let kinds: BTreeMap<&str, DeadCodeKind> = report.by_package["app"].iter()
+            .map(|finding| (finding.method.method_name.as_str(), finding.kind))
+            .collect();
+        assert_eq!(kinds, BTreeMap::from([
+            ("helper", DeadCodeKind::Unreachable),
+            ("only_tested", DeadCodeKind::TestOnly),
+            ("orphan", DeadCodeKind::UncalledPrivate),
+        ]));
+    }
+}
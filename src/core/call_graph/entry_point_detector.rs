@@ -1,11 +1,11 @@
 // src/core/call_graph/entry_point_detector.rs - Simple universal approach
-use std::collections::HashSet;
 use serde::{Serialize, Deserialize};
+use tracing::debug;
 
 use crate::error::Result;
-use super::{CallGraph, MethodSignature, CallNode};
+use super::{CallGraph, MethodSignature, HttpRoute};
 
-/// Universal entry point detector - simple and framework-agnostic
+/// Universal entry point detector: call graph roots, `main` and framework route handlers
 pub struct EntryPointDetector;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,12 +14,17 @@ pub struct EntryPoint {
     pub entry_type: EntryPointType,
     pub confidence: f32,
     pub reasoning: String,
+    /// HTTP routes the entry point handles, for framework route handlers
+    #[serde(default)]
+    pub routes: Vec<HttpRoute>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EntryPointType {
     /// Main application entry point
     Main,
+    /// Handler of an HTTP route declared with a web framework
+    HttpHandler,
     /// External interface (no callers, has callees)
     ExternalInterface,
     /// Test entry point
@@ -34,14 +39,14 @@ impl EntryPointDetector {
     }
 
     /// Detect all entry points in the call graph
-    /// Simple rule: methods with no callers that call other methods
+    /// Simple rule: methods with no callers that call other methods, plus `main` and route handlers
     pub fn detect_entry_points(&self, call_graph: &CallGraph) -> Result<Vec<EntryPoint>> {
         let mut entry_points = Vec::new();
 
         // Primary strategy: Call graph analysis (in-degree=0, out-degree>0)
         let candidates = call_graph.get_entry_point_candidates();
 
-        println!("Found {} entry point candidates", candidates.len());
+        debug!("Found {} entry point candidates", candidates.len());
 
         for candidate in candidates {
             let entry_point = self.analyze_entry_point(call_graph, candidate)?;
@@ -56,11 +61,37 @@ impl EntryPointDetector {
                     entry_type: EntryPointType::Main,
                     confidence: 0.95,
                     reasoning: "Main method".to_string(),
+                    routes: Vec::new(),
                 };
                 entry_points.push(entry_point);
             }
         }
 
+        // Route handlers are called by the framework, whatever the call graph says about their callers
+        for route in &call_graph.http_routes {
+            let index = match entry_points.iter().position(|ep| ep.signature == route.handler) {
+                Some(index) => index,
+                None => {
+                    entry_points.push(EntryPoint {
+                        signature: route.handler.clone(),
+                        entry_type: EntryPointType::HttpHandler,
+                        confidence: 0.95,
+                        reasoning: String::new(),
+                        routes: Vec::new(),
+                    });
+                    entry_points.len() - 1
+                }
+            };
+
+            let entry_point = &mut entry_points[index];
+            if entry_point.routes.is_empty() {
+                entry_point.entry_type = EntryPointType::HttpHandler;
+                entry_point.confidence = entry_point.confidence.max(0.95);
+                entry_point.reasoning = format!("{} route handler", route.framework);
+            }
+            entry_point.routes.push(route.clone());
+        }
+
         // Sort by confidence
         entry_points.sort_by(|a, b| b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal));
 
@@ -83,6 +114,10 @@ impl EntryPointDetector {
             confidence = 0.95;
             reasoning_parts.push("Main application entry point".to_string());
             EntryPointType::Main
+        } else if node.is_test {
+            confidence = 0.9;
+            reasoning_parts.push("Test attribute, file or naming convention".to_string());
+            EntryPointType::Test
         } else if candidate.method_name.contains("test") || candidate.method_name.starts_with("test") {
             confidence = 0.8;
             reasoning_parts.push("Test method pattern".to_string());
@@ -115,6 +150,7 @@ impl EntryPointDetector {
             entry_type,
             confidence: confidence.min(1.0),
             reasoning: reasoning_parts.join(", "),
+            routes: Vec::new(),
         })
     }
 }
@@ -0,0 +1,348 @@
+// src/core/call_graph/example_miner.rs
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use serde::{Serialize, Deserialize};
+
+use super::{CallGraph, CallNode, MethodSignature};
+use super::super::ParsedFile;
+
+/// Mines usage examples for public API from real call sites (tests and other packages)
+///
+/// Functions get examples from the calls made to them; types get examples from the
+/// calls that construct them.
+///
+/// This is fully deterministic and does not need an LLM: snippets are cut from the
+/// caller's source around the call site found in the call graph.
+pub struct ExampleMiner {
+    /// Maximum number of examples kept per target method
+    max_examples_per_target: usize,
+    /// Maximum number of lines in a single snippet
+    max_snippet_lines: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageExample {
+    /// Method being demonstrated
+    pub target: MethodSignature,
+    /// Method containing the call site
+    pub caller: MethodSignature,
+    /// File containing the call site
+    pub file_path: PathBuf,
+    /// Line of the call site
+    pub line: usize,
+    /// Language of the snippet (for code fences)
+    pub language: String,
+    /// Dedented source snippet around the call site
+    pub snippet: String,
+    /// Whether the example was taken from a test
+    pub from_test: bool,
+    /// Type whose construction the example shows, for type-level examples
+    #[serde(default)]
+    pub type_name: Option<String>,
+}
+
+impl ExampleMiner {
+    pub fn new(max_examples_per_target: usize, max_snippet_lines: usize) -> Self {
+        Self {
+            max_examples_per_target,
+            max_snippet_lines,
+        }
+    }
+
+    /// Collect usage examples for every public, non-test method in the graph, and
+    /// for every public type that is constructed from outside its package
+    pub fn mine_examples(&self, call_graph: &CallGraph, files: &[ParsedFile]) -> Vec<UsageExample> {
+        let files_by_path: HashMap<&PathBuf, &ParsedFile> = files.iter()
+            .map(|file| (&file.path, file))
+            .collect();
+
+        let mut candidates: HashMap<&MethodSignature, Vec<UsageExample>> = HashMap::new();
+        let mut type_candidates: HashMap<(&PathBuf, &str), Vec<UsageExample>> = HashMap::new();
+
+        for edge in &call_graph.edges {
+            let (Some(target), Some(caller)) = (call_graph.nodes.get(&edge.callee), call_graph.nodes.get(&edge.caller)) else {
+                continue;
+            };
+
+            if !self.is_documented_api(target) {
+                continue;
+            }
+
+            // Only tests and callers from other packages show how the API is used from outside
+            let from_test = caller.is_test;
+            if !from_test && edge.caller.package_name() == edge.callee.package_name() {
+                continue;
+            }
+
+            let Some(file) = files_by_path.get(&edge.caller.file_path) else {
+                continue;
+            };
+
+            let Some(snippet) = self.extract_snippet(file, caller, edge.call_site_line) else {
+                continue;
+            };
+            let example = UsageExample {
+                target: edge.callee.clone(),
+                caller: edge.caller.clone(),
+                file_path: edge.caller.file_path.clone(),
+                line: edge.call_site_line,
+                language: file.language.clone(),
+                snippet,
+                from_test,
+                type_name: None,
+            };
+
+            if let Some(type_name) = self.constructed_type(target) {
+                type_candidates.entry((&edge.callee.file_path, type_name)).or_default().push(UsageExample {
+                    type_name: Some(type_name.to_string()),
+                    ..example.clone()
+                });
+            }
+            candidates.entry(&edge.callee).or_default().push(example);
+        }
+
+        let mut examples: Vec<UsageExample> = candidates.into_values()
+            .chain(type_candidates.into_values())
+            .flat_map(|target_examples| self.select_examples(target_examples))
+            .collect();
+
+        // Stable sort keeps the per-target ranking
+        examples.sort_by_key(|example| example.target.to_unique_string());
+
+        examples
+    }
+
+    /// Keep the most representative examples of one target: shortest snippets first,
+    /// tests before other callers, then by location for stability, without repeats
+    fn select_examples(&self, mut examples: Vec<UsageExample>) -> Vec<UsageExample> {
+        examples.sort_by(|a, b| {
+            a.snippet.lines().count().cmp(&b.snippet.lines().count())
+                .then(b.from_test.cmp(&a.from_test))
+                .then(a.file_path.cmp(&b.file_path))
+                .then(a.line.cmp(&b.line))
+        });
+
+        let mut seen_snippets = HashSet::new();
+        examples.into_iter()
+            .filter(|example| seen_snippets.insert(example.snippet.clone()))
+            .take(self.max_examples_per_target)
+            .collect()
+    }
+
+    /// Type a call to `node` constructs: constructors, and associated functions
+    /// returning `Self` or the type itself
+    fn constructed_type<'a>(&self, node: &'a CallNode) -> Option<&'a str> {
+        let class = node.signature.class_name.as_deref()?;
+        let name = node.signature.method_name.as_str();
+        let returns_type = node.signature.signature.split_once("->").is_some_and(|(_, returns)| {
+            returns.split(|ch: char| !ch.is_alphanumeric() && ch != '_')
+                .any(|word| word == "Self" || word == class)
+        });
+
+        (matches!(name, "new" | "__init__" | "constructor") || name == class || returns_type).then_some(class)
+    }
+
+    /// Public, non-test methods are the ones that get API documentation
+    fn is_documented_api(&self, node: &CallNode) -> bool {
+        node.visibility == "public" && !node.is_test
+    }
+
+    /// Cut the statement containing the call, preceded by as much of the enclosing
+    /// block as fits in the snippet budget
+    fn extract_snippet(&self, file: &ParsedFile, caller: &CallNode, call_line: usize) -> Option<String> {
+        let lines: Vec<&str> = file.source_content.lines().collect();
+        let call_index = call_line.checked_sub(1)?;
+        if call_index >= lines.len() {
+            return None;
+        }
+
+        let body_start = caller.line_range.0.min(lines.len());
+        let body_end = caller.line_range.1.saturating_sub(1).min(lines.len() - 1);
+
+        // Extend backwards over continuation lines of the same statement
+        let mut statement_start = call_index;
+        while statement_start > body_start
+            && (self.continues_on_next_line(lines[statement_start - 1])
+                || lines[statement_start].trim_start().starts_with('.')) {
+            statement_start -= 1;
+        }
+
+        // Extend forwards until brackets balance
+        let mut statement_end = call_index;
+        while statement_end < body_end
+            && (self.bracket_balance(&lines[statement_start..=statement_end]) > 0
+                || self.continues_on_next_line(lines[statement_end])) {
+            statement_end += 1;
+        }
+
+        let block_start = self.enclosing_block_start(&lines, body_start, statement_start, &file.language);
+
+        let statement_len = statement_end - statement_start + 1;
+        let context_budget = self.max_snippet_lines.saturating_sub(statement_len);
+        let context_start = statement_start.saturating_sub(context_budget).max(block_start);
+
+        let mut snippet_lines: Vec<&str> = lines[context_start..=statement_end].to_vec();
+        let mut truncated = false;
+        if snippet_lines.len() > self.max_snippet_lines {
+            snippet_lines.truncate(self.max_snippet_lines);
+            truncated = true;
+        }
+
+        // Drop leading blank lines left over from the block context
+        while snippet_lines.first().is_some_and(|line| line.trim().is_empty()) {
+            snippet_lines.remove(0);
+        }
+
+        let mut snippet = self.dedent(&snippet_lines);
+        if truncated {
+            snippet.push_str("\n...");
+        }
+
+        if snippet.trim().is_empty() {
+            None
+        } else {
+            Some(snippet)
+        }
+    }
+
+    /// Find the first line inside the innermost block enclosing `statement_start`
+    fn enclosing_block_start(&self, lines: &[&str], body_start: usize, statement_start: usize, language: &str) -> usize {
+        if language == "python" {
+            let indent = self.indentation(lines[statement_start]);
+            for index in (body_start..statement_start).rev() {
+                let line = lines[index];
+                if !line.trim().is_empty() && self.indentation(line) < indent && line.trim_end().ends_with(':') {
+                    return index + 1;
+                }
+            }
+            return body_start;
+        }
+
+        let mut depth = 0i32;
+        for index in (body_start.saturating_sub(1)..statement_start).rev() {
+            for ch in lines[index].chars().rev() {
+                match ch {
+                    '}' => depth += 1,
+                    '{' => {
+                        if depth == 0 {
+                            return index + 1;
+                        }
+                        depth -= 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        body_start
+    }
+
+    /// Net number of unclosed parentheses/brackets/braces in a range of lines
+    fn bracket_balance(&self, lines: &[&str]) -> i32 {
+        lines.iter()
+            .flat_map(|line| line.chars())
+            .map(|ch| match ch {
+                '(' | '[' | '{' => 1,
+                ')' | ']' | '}' => -1,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Whether a line syntactically continues on the next one
+    fn continues_on_next_line(&self, line: &str) -> bool {
+        let trimmed = line.trim_end();
+        [",", "(", "[", "=", ".", "+", "-", "&&", "||", "\\", "=>", "->"]
+            .iter()
+            .any(|marker| trimmed.ends_with(marker))
+    }
+
+    fn indentation(&self, line: &str) -> usize {
+        line.len() - line.trim_start().len()
+    }
+
+    /// Remove the common leading indentation
+    fn dedent(&self, lines: &[&str]) -> String {
+        let common = lines.iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| self.indentation(line))
+            .min()
+            .unwrap_or(0);
+
+        lines.iter()
+            .map(|line| if line.len() >= common { &line[common..] } else { line.trim_start() })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim_end()
+            .to_string()
+    }
+}
+
+impl Default for ExampleMiner {
+    fn default() -> Self {
+        Self::new(3, 8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::{graph, method, node, parsed_file};
+
+    fn test_node(signature: MethodSignature, lines: (usize, usize)) -> CallNode {
+        CallNode { is_test: true, ..node(signature, lines) }
+    }
+
+    #[test]
+    fn test_snippets_cover_the_enclosing_statement_and_types() {
+        let source = "fn test_place() {\n    let store = Store::new();\n    let order = place(\n        &store,\n        42,\n    );\n    assert!(order.is_ok());\n}\n";
+        let file = parsed_file("/p/tests/orders_test.rs", "rust", source);
+
+        let test_place = test_node(method("/p/tests/orders_test.rs", None, "test_place"), (1, 8));
+        let place = method("/p/src/orders/service.rs", None, "place");
+        let store_new = method("/p/src/orders/store.rs", Some("Store"), "new");
+        let graph = graph(std::slice::from_ref(&test_place), &[(&test_place.signature, &store_new, 2), (&test_place.signature, &place, 3)]);
+
+        let examples = ExampleMiner::default().mine_examples(&graph, &[file]);
+        let snippets: Vec<(&str, Option<&str>, &str)> = examples.iter()
+            .map(|example| (example.target.method_name.as_str(), example.type_name.as_deref(), example.snippet.as_str()))
+            .collect();
+
+        assert_eq!(snippets, vec![
+            ("new", None, "let store = Store::new();"),
+            ("new", Some("Store"), "let store = Store::new();"),
+            ("place", None, "let store = Store::new();\nlet order = place(\n    &store,\n    42,\n);"),
+        ]);
+        assert!(examples.iter().all(|example| example.from_test));
+    }
+
+    #[test]
+    fn test_prefers_short_deduplicated_snippets_from_tests() {
+        let load = method("/p/src/orders/service.rs", None, "load");
+        let callers = [
+            ("/p/src/orders/api.rs", "handle", "fn handle() {\n    load(0);\n}\n", false),
+            ("/p/src/web/routes.rs", "route", "fn route() {\n    load(1);\n}\n", false),
+            ("/p/tests/a_test.rs", "test_a", "fn test_a() {\n    load(1);\n}\n", true),
+            ("/p/tests/b_test.rs", "test_b", "fn test_b() {\n    let id = 2;\n    load(id);\n}\n", true),
+            ("/p/src/web/admin.rs", "admin", "fn admin() {\n    load(3);\n}\n", false),
+        ];
+
+        let files: Vec<ParsedFile> = callers.iter().map(|(path, _, source, _)| parsed_file(path, "rust", source)).collect();
+        let nodes: Vec<CallNode> = callers.iter()
+            .map(|(path, name, source, is_test)| CallNode {
+                is_test: *is_test,
+                ..node(method(path, None, name), (1, source.lines().count()))
+            })
+            .collect();
+        let calls: Vec<_> = nodes.iter().map(|caller| (&caller.signature, &load, caller.line_range.1 - 1)).collect();
+        let graph = graph(&nodes, &calls);
+
+        let examples = ExampleMiner::new(2, 8).mine_examples(&graph, &files);
+        let chosen: Vec<(&str, &str, bool)> = examples.iter()
+            .map(|example| (example.caller.method_name.as_str(), example.snippet.as_str(), example.from_test))
+            .collect();
+
+        // Same-package callers are skipped, equal snippets are kept once and the test wins the tie
+        assert_eq!(chosen, vec![("test_a", "load(1);", true), ("admin", "load(3);", false)]);
+    }
+}
@@ -0,0 +1,704 @@
+// src/core/call_graph/failure_modes.rs
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+
+use super::super::ParsedFile;
+use super::call_graph::signature_map;
+use super::type_hierarchy::{simple_type_name, TypeHierarchy};
+use super::{CallChainGroup, CallGraph, CallNode, CallType, EntryPoint, EntryPointType, MethodSignature};
+
+/// Error type of Rust panics
+const PANIC: &str = "panic";
+
+/// Catch clauses that catch everything
+const CATCH_ALL: &[&str] = &["", "Exception", "Throwable", "BaseException", "System.Exception"];
+
+/// Built-in Python exceptions as (supertype, direct subtypes)
+const PYTHON_EXCEPTIONS: &[(&str, &[&str])] = &[
+    ("BaseException", &["Exception", "KeyboardInterrupt", "SystemExit", "GeneratorExit"]),
+    ("Exception", &[
+        "ArithmeticError", "AssertionError", "AttributeError", "BufferError", "EOFError", "ImportError",
+        "LookupError", "MemoryError", "NameError", "OSError", "ReferenceError", "RuntimeError",
+        "StopIteration", "StopAsyncIteration", "SyntaxError", "SystemError", "TypeError", "ValueError", "Warning",
+    ]),
+    ("ArithmeticError", &["FloatingPointError", "OverflowError", "ZeroDivisionError"]),
+    ("ImportError", &["ModuleNotFoundError"]),
+    ("LookupError", &["IndexError", "KeyError"]),
+    ("NameError", &["UnboundLocalError"]),
+    ("OSError", &[
+        "IOError", "EnvironmentError", "BlockingIOError", "ChildProcessError", "ConnectionError", "FileExistsError",
+        "FileNotFoundError", "InterruptedError", "IsADirectoryError", "NotADirectoryError", "PermissionError",
+        "ProcessLookupError", "TimeoutError",
+    ]),
+    ("ConnectionError", &["BrokenPipeError", "ConnectionAbortedError", "ConnectionRefusedError", "ConnectionResetError"]),
+    ("RuntimeError", &["NotImplementedError", "RecursionError"]),
+    ("SyntaxError", &["IndentationError"]),
+    ("IndentationError", &["TabError"]),
+    ("ValueError", &["UnicodeError", "JSONDecodeError"]),
+    ("UnicodeError", &["UnicodeDecodeError", "UnicodeEncodeError", "UnicodeTranslateError"]),
+];
+
+/// Java standard library exceptions as (supertype, direct subtypes)
+const JAVA_EXCEPTIONS: &[(&str, &[&str])] = &[
+    ("Throwable", &["Exception", "Error"]),
+    ("Exception", &[
+        "RuntimeException", "IOException", "ReflectiveOperationException", "InterruptedException",
+        "TimeoutException", "ExecutionException", "SQLException", "CloneNotSupportedException",
+        "GeneralSecurityException", "URISyntaxException", "ParseException",
+    ]),
+    ("RuntimeException", &[
+        "IllegalArgumentException", "IllegalStateException", "NullPointerException", "ArithmeticException",
+        "ClassCastException", "IndexOutOfBoundsException", "UnsupportedOperationException",
+        "ConcurrentModificationException", "NoSuchElementException", "SecurityException", "DateTimeException",
+        "UncheckedIOException",
+    ]),
+    ("IllegalArgumentException", &["NumberFormatException"]),
+    ("IndexOutOfBoundsException", &["ArrayIndexOutOfBoundsException", "StringIndexOutOfBoundsException"]),
+    ("IOException", &[
+        "FileNotFoundException", "EOFException", "UnsupportedEncodingException", "MalformedURLException",
+        "UnknownHostException", "SocketException", "InterruptedIOException", "FileSystemException",
+    ]),
+    ("FileSystemException", &["NoSuchFileException", "AccessDeniedException", "FileAlreadyExistsException", "DirectoryNotEmptyException"]),
+    ("SocketException", &["ConnectException"]),
+    ("InterruptedIOException", &["SocketTimeoutException"]),
+    ("ReflectiveOperationException", &[
+        "ClassNotFoundException", "NoSuchMethodException", "NoSuchFieldException", "InstantiationException",
+        "IllegalAccessException", "InvocationTargetException",
+    ]),
+    ("GeneralSecurityException", &["NoSuchAlgorithmException", "InvalidKeyException"]),
+    ("Error", &["AssertionError", "VirtualMachineError", "LinkageError"]),
+    ("VirtualMachineError", &["OutOfMemoryError", "StackOverflowError"]),
+];
+
+/// .NET base class library exceptions as (supertype, direct subtypes)
+const CSHARP_EXCEPTIONS: &[(&str, &[&str])] = &[
+    ("Exception", &["SystemException", "ApplicationException", "AggregateException", "HttpRequestException", "JsonException"]),
+    ("SystemException", &[
+        "ArgumentException", "InvalidOperationException", "NotSupportedException", "NotImplementedException",
+        "IOException", "ArithmeticException", "FormatException", "IndexOutOfRangeException",
+        "NullReferenceException", "InvalidCastException", "KeyNotFoundException", "UnauthorizedAccessException",
+        "TimeoutException", "OutOfMemoryException", "OperationCanceledException",
+    ]),
+    ("ArgumentException", &["ArgumentNullException", "ArgumentOutOfRangeException"]),
+    ("InvalidOperationException", &["ObjectDisposedException"]),
+    ("IOException", &["FileNotFoundException", "DirectoryNotFoundException", "EndOfStreamException", "PathTooLongException"]),
+    ("ArithmeticException", &["DivideByZeroException", "OverflowException"]),
+    ("OperationCanceledException", &["TaskCanceledException"]),
+];
+
+/// JavaScript built-in errors as (supertype, direct subtypes)
+const JAVASCRIPT_EXCEPTIONS: &[(&str, &[&str])] = &[
+    ("Error", &["EvalError", "RangeError", "ReferenceError", "SyntaxError", "TypeError", "URIError", "AggregateError"]),
+];
+
+/// An error that can reach an entry point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailureMode {
+    pub error_type: String,
+    /// Function the error starts in
+    pub origin: MethodSignature,
+    pub line: usize,
+    /// How it starts, e.g. `throw new IOException(...)` or `fs::read(...)?`
+    pub raised_by: String,
+    /// Functions between the origin and the entry point that catch it
+    pub handled_in: Vec<MethodSignature>,
+    /// Whether it can propagate all the way out of the entry point
+    pub reaches_entry_point: bool,
+}
+
+/// Errors that can reach each production entry point, from error propagation through the call graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FailureModes {
+    #[serde(with = "signature_map")]
+    pub by_entry_point: HashMap<MethodSignature, Vec<FailureMode>>,
+}
+
+/// Where an error starts
+struct ErrorOrigin {
+    error_type: String,
+    method: MethodSignature,
+    line: usize,
+    raised_by: String,
+}
+
+/// How a call site treats the errors of the function it calls
+enum CallHandling {
+    /// Errors pass through (`?`, or a call outside any try block)
+    Propagates,
+    /// Rust call whose `Result` is inspected rather than propagated; panics still pass through
+    Inspected,
+    /// Inside a try block with these catch clauses (empty string for a catch-all)
+    Caught(Vec<String>),
+    /// Calls to other services return errors as responses
+    Boundary,
+}
+
+/// Finds where errors start and follows them up the call graph to the entry points
+pub struct FailureModeAnalyzer {
+    rust_err: Regex,
+    rust_macro_error: Regex,
+    rust_panic: Regex,
+    thrown: Regex,
+    raised: Regex,
+    java_catch: Regex,
+    csharp_catch: Regex,
+    python_except: Regex,
+    try_start: Regex,
+}
+
+impl FailureModeAnalyzer {
+    pub fn new() -> Self {
+        let regex = |pattern: &str| Regex::new(pattern).expect("valid regex");
+        Self {
+            rust_err: regex(r#"\bErr\(\s*([A-Za-z_][\w:]*!?|")"#),
+            rust_macro_error: regex(r"\b(bail|anyhow|ensure)!"),
+            rust_panic: regex(r"\b(panic|unreachable|todo|unimplemented)!"),
+            thrown: regex(r"\b(?:throw\s+new|reject\(\s*new)\s+([A-Za-z_][\w.]*)"),
+            raised: regex(r"\braise\s+([A-Za-z_][\w.]*)"),
+            java_catch: regex(r"\bcatch\s*\(\s*(?:final\s+)?([\w.|\s]+?)\s+\w+\s*\)"),
+            csharp_catch: regex(r"\bcatch\b\s*(?:\(\s*([\w.]+)[^)]*\))?"),
+            python_except: regex(r"^\s*except\b\s*([^:]*):"),
+            try_start: regex(r"^\s*try\b"),
+        }
+    }
+
+    pub fn analyze(&self, call_graph: &CallGraph, files: &[ParsedFile], entry_points: &[EntryPoint]) -> FailureModes {
+        let files_by_path: HashMap<&PathBuf, &ParsedFile> = files.iter().map(|file| (&file.path, file)).collect();
+        let mut nodes_by_file: HashMap<&PathBuf, Vec<&CallNode>> = HashMap::new();
+        for node in call_graph.nodes.values() {
+            nodes_by_file.entry(&node.signature.file_path).or_default().push(node);
+        }
+
+        let origins = self.find_origins(call_graph, &files_by_path, &nodes_by_file);
+        // The project's types plus the standard exceptions of each language, so `except LookupError` catches `KeyError`
+        let hierarchies: HashMap<&str, TypeHierarchy> = files.iter()
+            .map(|file| file.language.as_str())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .map(|language| (language, exception_hierarchy(&call_graph.type_hierarchy, language)))
+            .collect();
+        let edge_handling: Vec<CallHandling> = call_graph.edges.iter()
+            .map(|edge| match (files_by_path.get(&edge.caller.file_path), call_graph.nodes.get(&edge.caller)) {
+                _ if matches!(edge.call_type, CallType::CrossService) => CallHandling::Boundary,
+                (Some(file), Some(caller)) => self.call_handling(file, caller, &edge.callee.method_name, edge.call_site_line),
+                _ => CallHandling::Propagates,
+            })
+            .collect();
+
+        // Errors escaping each function: its own, plus its callees' that its call sites do not stop
+        let mut escaping: HashMap<&MethodSignature, BTreeSet<usize>> = HashMap::new();
+        for (index, origin) in origins.iter().enumerate() {
+            escaping.entry(&origin.method).or_default().insert(index);
+        }
+        // Where each error is stopped: (function, error)
+        let mut handled: HashSet<(&MethodSignature, usize)> = HashSet::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (edge, handling) in call_graph.edges.iter().zip(&edge_handling) {
+                let Some(from_callee) = escaping.get(&edge.callee).cloned() else {
+                    continue;
+                };
+                for error in from_callee {
+                    let passes = match handling {
+                        CallHandling::Propagates => true,
+                        CallHandling::Inspected => origins[error].error_type == PANIC,
+                        CallHandling::Caught(catches) => {
+                            let hierarchy = files_by_path.get(&edge.caller.file_path)
+                                .and_then(|file| hierarchies.get(file.language.as_str()))
+                                .unwrap_or(&call_graph.type_hierarchy);
+                            !self.catches(hierarchy, catches, &origins[error].error_type)
+                        }
+                        CallHandling::Boundary => continue,
+                    };
+                    if passes {
+                        changed |= escaping.entry(&edge.caller).or_default().insert(error);
+                    } else {
+                        handled.insert((&edge.caller, error));
+                    }
+                }
+            }
+        }
+
+        let mut callees: HashMap<&MethodSignature, Vec<&MethodSignature>> = HashMap::new();
+        for edge in call_graph.edges.iter().filter(|edge| !matches!(edge.call_type, CallType::CrossService)) {
+            callees.entry(&edge.caller).or_default().push(&edge.callee);
+        }
+
+        let mut by_entry_point = HashMap::new();
+        for entry_point in entry_points.iter().filter(|ep| !matches!(ep.entry_type, EntryPointType::Test)) {
+            let reachable = reachable_from(&callees, &entry_point.signature);
+            let reaching = escaping.get(&entry_point.signature).cloned().unwrap_or_default();
+
+            let mut failure_modes: Vec<FailureMode> = origins.iter()
+                .enumerate()
+                .filter(|(_, origin)| reachable.contains(&origin.method))
+                .filter_map(|(index, origin)| {
+                    let mut handled_in: Vec<MethodSignature> = reachable.iter()
+                        .filter(|method| handled.contains(&(*method, index)))
+                        .map(|method| (*method).clone())
+                        .collect();
+                    handled_in.sort_by_key(|method| method.to_unique_string());
+                    let reaches_entry_point = reaching.contains(&index);
+
+                    (reaches_entry_point || !handled_in.is_empty()).then(|| FailureMode {
+                        error_type: origin.error_type.clone(),
+                        origin: origin.method.clone(),
+                        line: origin.line,
+                        raised_by: origin.raised_by.clone(),
+                        handled_in,
+                        reaches_entry_point,
+                    })
+                })
+                .collect();
+            if failure_modes.is_empty() {
+                continue;
+            }
+            failure_modes.sort_by(|a, b| b.reaches_entry_point.cmp(&a.reaches_entry_point)
+                .then_with(|| a.error_type.cmp(&b.error_type))
+                .then_with(|| a.origin.file_path.cmp(&b.origin.file_path))
+                .then(a.line.cmp(&b.line)));
+            by_entry_point.insert(entry_point.signature.clone(), failure_modes);
+        }
+
+        FailureModes { by_entry_point }
+    }
+
+    /// Every `throw`, `raise`, `Err(...)`, panic, declared `throws` and `?` on a library call
+    fn find_origins(
+        &self,
+        call_graph: &CallGraph,
+        files_by_path: &HashMap<&PathBuf, &ParsedFile>,
+        nodes_by_file: &HashMap<&PathBuf, Vec<&CallNode>>,
+    ) -> Vec<ErrorOrigin> {
+        let mut origins = Vec::new();
+
+        for (path, nodes) in nodes_by_file {
+            let Some(file) = files_by_path.get(path) else {
+                continue;
+            };
+            let lines: Vec<&str> = file.source_content.lines().collect();
+
+            for node in nodes {
+                let declared = declared_error(&file.language, &node.signature.signature);
+                let mut thrown_types = BTreeSet::new();
+
+                for line_number in node.line_range.0.max(1)..=node.line_range.1.min(lines.len()) {
+                    let line = lines[line_number - 1];
+                    if is_comment(line) || innermost(nodes, line_number).is_some_and(|inner| inner.signature != node.signature) {
+                        continue;
+                    }
+
+                    for (error_type, raised_by) in self.raised_on_line(&file.language, line, declared.first()) {
+                        thrown_types.insert(error_type.clone());
+                        origins.push(ErrorOrigin {
+                            error_type,
+                            method: node.signature.clone(),
+                            line: line_number,
+                            raised_by,
+                        });
+                    }
+                }
+
+                // Declared exceptions not thrown here come from the library calls the function makes
+                if matches!(file.language.as_str(), "java" | "csharp") {
+                    for error_type in declared.iter().filter(|error_type| !thrown_types.contains(*error_type)) {
+                        origins.push(ErrorOrigin {
+                            error_type: error_type.clone(),
+                            method: node.signature.clone(),
+                            line: node.line_range.0,
+                            raised_by: format!("declared `throws {}`", error_type),
+                        });
+                    }
+                }
+            }
+        }
+
+        // `?` on a library call passes the library's error on; the first call in a chain
+        // (`fs::read(path).map_err(...)?`) is the one that fails
+        let mut library_calls: HashMap<(&MethodSignature, usize), (usize, ErrorOrigin)> = HashMap::new();
+        for call in &call_graph.unresolved_calls {
+            let Some(file) = files_by_path.get(&call.caller.file_path).filter(|file| file.language == "rust") else {
+                continue;
+            };
+            let Some(line) = file.source_content.lines().nth(call.call_site_line.saturating_sub(1)) else {
+                continue;
+            };
+            if is_comment(line) {
+                continue;
+            }
+            let written = match &call.receiver {
+                Some(receiver) if !receiver.contains('(') && line.contains(&format!("{}::{}", receiver, call.callee_name)) => {
+                    format!("{}::{}", receiver, call.callee_name)
+                }
+                Some(receiver) if !receiver.contains('(') => format!("{}.{}", receiver, call.callee_name),
+                _ => call.callee_name.clone(),
+            };
+            let (error_type, raised_by) = if propagates_with_question_mark(line, &call.callee_name) {
+                let declared = call_graph.nodes.get(&call.caller)
+                    .and_then(|node| declared_error("rust", &node.signature.signature).into_iter().next());
+                (declared.unwrap_or_else(|| "error".to_string()), format!("`{}(...)?`", written))
+            } else if panics_on_error(line, &call.callee_name) {
+                (PANIC.to_string(), format!("`{}(...)` unwrapped", written))
+            } else {
+                continue;
+            };
+            let column = line.find(&call.callee_name).unwrap_or(usize::MAX);
+            let key = (&call.caller, call.call_site_line);
+            if library_calls.get(&key).is_some_and(|(earlier, _)| *earlier <= column) {
+                continue;
+            }
+            library_calls.insert(key, (column, ErrorOrigin {
+                error_type,
+                method: call.caller.clone(),
+                line: call.call_site_line,
+                raised_by,
+            }));
+        }
+        origins.extend(library_calls.into_values().map(|(_, origin)| origin));
+
+        origins.sort_by(|a, b| a.method.file_path.cmp(&b.method.file_path).then(a.line.cmp(&b.line)));
+        origins
+    }
+
+    /// Errors a line raises, with the code that raises them
+    fn raised_on_line(&self, language: &str, line: &str, declared: Option<&String>) -> Vec<(String, String)> {
+        let code = line.trim();
+        let mut raised = Vec::new();
+
+        match language {
+            "rust" => {
+                for captures in self.rust_err.captures_iter(line) {
+                    let value = &captures[1];
+                    // `Err(e)` passes on an error from elsewhere; `Err(OrderError::Empty)` starts one
+                    let error_type = if value.starts_with(|c: char| c.is_ascii_uppercase()) {
+                        value.split("::").next().unwrap_or(value).to_string()
+                    } else if value == "\"" || value.ends_with('!') {
+                        // A message (`Err("...")`, `Err(format!(...))`) in the declared error type
+                        declared.cloned().unwrap_or_else(|| "error".to_string())
+                    } else {
+                        continue;
+                    };
+                    raised.push((error_type, format!("`{}`", code)));
+                }
+                if self.rust_err.find(line).is_none() && self.rust_macro_error.is_match(line) {
+                    raised.push((declared.cloned().unwrap_or_else(|| "anyhow::Error".to_string()), format!("`{}`", code)));
+                }
+                if self.rust_panic.is_match(line) {
+                    raised.push((PANIC.to_string(), format!("`{}`", code)));
+                }
+            }
+            "java" | "csharp" | "javascript" => {
+                for captures in self.thrown.captures_iter(line) {
+                    raised.push((captures[1].to_string(), format!("`{}`", code)));
+                }
+            }
+            "python" => {
+                for captures in self.raised.captures_iter(line) {
+                    // `raise error` re-raises a caught exception under a variable name
+                    if captures[1].starts_with(|c: char| c.is_ascii_uppercase()) || captures[1].contains('.') {
+                        raised.push((captures[1].to_string(), format!("`{}`", code)));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        raised
+    }
+
+    /// Whether a call site passes its callee's errors on, and if not, how it stops them
+    fn call_handling(&self, file: &ParsedFile, caller: &CallNode, callee_name: &str, line: usize) -> CallHandling {
+        let lines: Vec<&str> = file.source_content.lines().collect();
+        let Some(call_line) = lines.get(line.saturating_sub(1)) else {
+            return CallHandling::Propagates;
+        };
+
+        if file.language == "rust" {
+            return if propagates_with_question_mark(call_line, callee_name) || panics_on_error(call_line, callee_name) {
+                CallHandling::Propagates
+            } else {
+                CallHandling::Inspected
+            };
+        }
+
+        // The catch clauses of a try block opened before the call, within the caller
+        let start = caller.line_range.0.max(1);
+        let end = caller.line_range.1.min(lines.len());
+        let Some(try_line) = (start..line).rev().find(|number| self.try_start.is_match(lines[number - 1])) else {
+            return CallHandling::Propagates;
+        };
+        // The try body is everything indented deeper than the `try` until its first clause
+        let try_indent = indentation(lines[try_line - 1]);
+        let outside_body = |text: &str| !text.trim().is_empty() && indentation(text) <= try_indent;
+        if !outside_body(call_line) && (try_line + 1..line).any(|number| outside_body(lines[number - 1])) {
+            return CallHandling::Propagates;
+        }
+
+        let mut catches = Vec::new();
+        for text in lines[line..end].iter().filter(|text| outside_body(text)) {
+            let clause = text.trim_start();
+            let before = catches.len();
+            match file.language.as_str() {
+                "python" => if let Some(captures) = self.python_except.captures(text) {
+                    let names = captures[1].split(" as ").next().unwrap_or_default()
+                        .trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace());
+                    catches.extend(names.split(',').map(|name| name.trim().to_string()));
+                },
+                "java" => catches.extend(self.java_catch.captures_iter(text)
+                    .flat_map(|captures| captures[1].split('|').map(|name| name.trim().to_string()).collect::<Vec<_>>())),
+                "csharp" => catches.extend(self.csharp_catch.captures_iter(text)
+                    .map(|captures| captures.get(1).map(|name| name.as_str().to_string()).unwrap_or_default())),
+                _ => if clause.contains("catch") {
+                    catches.push(String::new());
+                },
+            }
+            // Past the last clause of the try statement
+            if catches.len() == before && !clause.starts_with('}') && !clause.starts_with("finally") {
+                break;
+            }
+        }
+
+        if catches.is_empty() {
+            CallHandling::Propagates
+        } else {
+            CallHandling::Caught(catches)
+        }
+    }
+
+    /// Whether one of the catch clauses catches the error, directly or as a supertype
+    fn catches(&self, hierarchy: &TypeHierarchy, catches: &[String], error_type: &str) -> bool {
+        let error = simple_type_name(error_type);
+        let supertypes = hierarchy.supertypes_of(&error);
+        catches.iter().any(|catch| {
+            let caught = simple_type_name(catch);
+            CATCH_ALL.contains(&catch.as_str()) || CATCH_ALL.contains(&caught.as_str())
+                || caught == error || supertypes.contains(&caught)
+        })
+    }
+}
+
+impl Default for FailureModeAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The project's type hierarchy with the standard exceptions of `language` added
+fn exception_hierarchy(project: &TypeHierarchy, language: &str) -> TypeHierarchy {
+    let standard = match language {
+        "python" => PYTHON_EXCEPTIONS,
+        "java" => JAVA_EXCEPTIONS,
+        "csharp" => CSHARP_EXCEPTIONS,
+        "javascript" | "typescript" => JAVASCRIPT_EXCEPTIONS,
+        _ => &[],
+    };
+
+    let mut hierarchy = project.clone();
+    for (supertype, subtypes) in standard {
+        for subtype in *subtypes {
+            hierarchy.add_relation(subtype, supertype);
+        }
+    }
+    hierarchy
+}
+
+/// A failure mode as seen from a group: one error, the entry points it escapes and where it is caught
+pub struct GroupFailureMode<'a> {
+    pub mode: &'a FailureMode,
+    pub reaches: Vec<&'a MethodSignature>,
+    pub handled_in: BTreeSet<String>,
+}
+
+impl FailureModes {
+    pub fn of_entry_point(&self, method: &MethodSignature) -> &[FailureMode] {
+        self.by_entry_point.get(method).map(|modes| modes.as_slice()).unwrap_or_default()
+    }
+
+    /// Failure modes of all the group's entry points, each error listed once
+    pub fn of_group<'a>(&'a self, group: &'a CallChainGroup) -> Vec<GroupFailureMode<'a>> {
+        let mut merged: Vec<GroupFailureMode> = Vec::new();
+        for chain in &group.call_chains {
+            let entry_point = &chain.entry_point.signature;
+            for mode in self.of_entry_point(entry_point) {
+                let index = match merged.iter().position(|merged| merged.mode.error_type == mode.error_type
+                    && merged.mode.origin == mode.origin && merged.mode.line == mode.line) {
+                    Some(index) => index,
+                    None => {
+                        merged.push(GroupFailureMode { mode, reaches: vec![], handled_in: BTreeSet::new() });
+                        merged.len() - 1
+                    }
+                };
+                if mode.reaches_entry_point && !merged[index].reaches.contains(&entry_point) {
+                    merged[index].reaches.push(entry_point);
+                }
+                merged[index].handled_in.extend(mode.handled_in.iter().map(|method| method.display_name()));
+            }
+        }
+
+        merged.sort_by(|a, b| b.reaches.len().cmp(&a.reaches.len())
+            .then_with(|| a.mode.error_type.cmp(&b.mode.error_type))
+            .then(a.mode.line.cmp(&b.mode.line)));
+        merged
+    }
+
+    /// Distinct error types that escape an entry point unhandled
+    pub fn unhandled_types(&self, method: &MethodSignature) -> Vec<&str> {
+        let mut types: Vec<&str> = self.of_entry_point(method).iter()
+            .filter(|mode| mode.reaches_entry_point)
+            .map(|mode| mode.error_type.as_str())
+            .collect();
+        types.sort();
+        types.dedup();
+        types
+    }
+}
+
+/// Functions reachable from a method, itself included
+fn reachable_from<'a>(
+    callees: &HashMap<&'a MethodSignature, Vec<&'a MethodSignature>>,
+    start: &'a MethodSignature,
+) -> HashSet<&'a MethodSignature> {
+    let mut visited = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(current) = queue.pop_front() {
+        for callee in callees.get(current).into_iter().flatten() {
+            if visited.insert(*callee) {
+                queue.push_back(*callee);
+            }
+        }
+    }
+    visited
+}
+
+/// Error types a signature declares: Rust's `Result` error, Java's `throws` list
+fn declared_error(language: &str, signature: &str) -> Vec<String> {
+    match language {
+        "rust" => {
+            let Some(return_type) = signature.split("->").nth(1) else {
+                return vec![];
+            };
+            let Some(start) = return_type.find("Result<") else {
+                return vec![];
+            };
+            let prefix = return_type[..start].trim();
+            let mut depth = 0;
+            let mut arguments = vec![String::new()];
+            for c in return_type[start + "Result<".len()..].chars() {
+                match c {
+                    '<' | '(' => depth += 1,
+                    '>' | ')' if depth == 0 => break,
+                    '>' | ')' => depth -= 1,
+                    ',' if depth == 0 => {
+                        arguments.push(String::new());
+                        continue;
+                    }
+                    _ => {}
+                }
+                arguments.last_mut().expect("non-empty").push(c);
+            }
+            match arguments.get(1).map(|error| error.trim()) {
+                Some(error) if !error.is_empty() => vec![error.to_string()],
+                _ if prefix.ends_with("io::") => vec!["io::Error".to_string()],
+                _ if prefix.ends_with("anyhow::") => vec!["anyhow::Error".to_string()],
+                _ => vec![],
+            }
+        }
+        "java" | "csharp" => signature.split_once(" throws ")
+            .map(|(_, list)| list.split(',').map(|name| name.trim().to_string()).filter(|name| !name.is_empty()).collect())
+            .unwrap_or_default(),
+        _ => vec![],
+    }
+}
+
+/// Whether `callee(...)` on this line is followed by `?`
+fn propagates_with_question_mark(line: &str, callee: &str) -> bool {
+    after_call(line, callee).is_some_and(|rest| rest.contains('?'))
+}
+
+/// Whether `callee(...)` on this line is unwrapped, turning its error into a panic
+fn panics_on_error(line: &str, callee: &str) -> bool {
+    after_call(line, callee).is_some_and(|rest| rest.contains(".unwrap()") || rest.contains(".expect("))
+}
+
+fn after_call<'a>(line: &'a str, callee: &str) -> Option<&'a str> {
+    line.find(&format!("{}(", callee))
+        .or_else(|| line.find(callee))
+        .map(|position| &line[position..])
+}
+
+/// The innermost function containing a line, among the given ones
+fn innermost<'a>(nodes: &[&'a CallNode], line: usize) -> Option<&'a CallNode> {
+    nodes.iter()
+        .filter(|node| node.line_range.0 <= line && line <= node.line_range.1)
+        .min_by_key(|node| node.line_range.1 - node.line_range.0)
+        .copied()
+}
+
+fn is_comment(line: &str) -> bool {
+    let code = line.trim_start();
+    ["//", "#", "/*", "*"].iter().any(|marker| code.starts_with(marker)) && !code.starts_with("#[")
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::{self, entry_point, method, parsed_file};
+
+    fn node(file: &str, name: &str, signature: &str, lines: (usize, usize)) -> CallNode {
+        let signature = MethodSignature { signature: signature.to_string(), ..method(file, None, name) };
+        test_support::node(signature, lines)
+    }
+
+    fn graph(nodes: &[&CallNode], calls: &[(&CallNode, &CallNode, usize)]) -> CallGraph {
+        let nodes: Vec<CallNode> = nodes.iter().map(|node| (*node).clone()).collect();
+        let calls: Vec<_> = calls.iter().map(|(caller, callee, line)| (&caller.signature, &callee.signature, *line)).collect();
+        test_support::graph(&nodes, &calls)
+    }
+
+    #[test]
+    fn test_rust_errors_propagate_through_question_marks() {
+        let source = "fn handle() -> Result<(), AppError> {\n    load()?;\n    let _ = check();\n    Ok(())\n}\n\
+            fn load() -> Result<u8, AppError> {\n    Err(AppError::Missing)\n}\n\
+            fn check() -> Result<(), AppError> {\n    Err(AppError::Invalid)\n}\n";
+        let file = parsed_file("lib.rs", "rust", source);
+        let handle = node("lib.rs", "handle", "fn handle() -> Result<(), AppError>", (1, 5));
+        let load = node("lib.rs", "load", "fn load() -> Result<u8, AppError>", (6, 8));
+        let check = node("lib.rs", "check", "fn check() -> Result<(), AppError>", (9, 11));
+        let graph = graph(&[&handle, &load, &check], &[(&handle, &load, 2), (&handle, &check, 3)]);
+
+        let modes = FailureModeAnalyzer::new().analyze(&graph, &[file], &[entry_point(&handle.signature, EntryPointType::PublicApi)]);
+        let modes = modes.of_entry_point(&handle.signature);
+
+        assert_eq!(modes.len(), 2);
+        assert!(modes[0].reaches_entry_point && modes[0].origin == load.signature && modes[0].line == 7);
+        assert!(!modes[1].reaches_entry_point && modes[1].origin == check.signature);
+        assert_eq!(modes[1].handled_in, vec![handle.signature.clone()]);
+    }
+
+    #[test]
+    fn test_python_except_catches_subtypes() {
+        let source = "def handle():\n    try:\n        load()\n    except LookupError:\n        pass\n    save()\n\n\
+            def load():\n    raise KeyError('missing')\n\n\
+            def save():\n    raise ValueError('bad')\n";
+        let file = parsed_file("app.py", "python", source);
+        let handle = node("app.py", "handle", "def handle()", (1, 6));
+        let load = node("app.py", "load", "def load()", (8, 9));
+        let save = node("app.py", "save", "def save()", (11, 12));
+        let graph = graph(&[&handle, &load, &save], &[(&handle, &load, 3), (&handle, &save, 6)]);
+
+        let modes = FailureModeAnalyzer::new().analyze(&graph, &[file], &[entry_point(&handle.signature, EntryPointType::PublicApi)]);
+
+        assert_eq!(modes.unhandled_types(&handle.signature), vec!["ValueError"]);
+        let key_error = modes.of_entry_point(&handle.signature).iter()
+            .find(|mode| mode.error_type == "KeyError")
+            .expect("KeyError is reported");
+        assert_eq!(key_error.handled_in, vec![handle.signature.clone()]);
+    }
+}
@@ -0,0 +1,472 @@
+// src/core/call_graph/graph_diff.rs
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+
+use super::{CallChain, CallChainGroup, CallGraph, EntryPoint, MethodSignature};
+
+/// Call graph, entry points, chains and groups of one revision
+pub struct GraphSnapshot {
+    pub revision: String,
+    /// Checkout directory; file paths are reported relative to it
+    pub root: PathBuf,
+    pub call_graph: CallGraph,
+    pub entry_points: Vec<EntryPoint>,
+    pub call_chains: Vec<CallChain>,
+    pub groups: Vec<CallChainGroup>,
+}
+
+/// A function as it appears in diff output
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct DiffFunction {
+    pub file: String,
+    pub name: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffCall {
+    pub caller: DiffFunction,
+    pub callee: DiffFunction,
+    pub call_site_line: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffEntryPoint {
+    #[serde(flatten)]
+    pub function: DiffFunction,
+    pub entry_type: String,
+}
+
+/// Entry point whose chains now reach different functions
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainChange {
+    pub entry_point: DiffFunction,
+    pub newly_reached: Vec<DiffFunction>,
+    pub no_longer_reached: Vec<DiffFunction>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupStatus {
+    Added,
+    Removed,
+    /// Same group, with different files or entry points
+    Changed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupChange {
+    pub name: String,
+    pub status: GroupStatus,
+    pub added_files: Vec<String>,
+    pub removed_files: Vec<String>,
+    /// Entry points whose chains moved into the group
+    pub joined: Vec<String>,
+    /// Entry points whose chains moved out of the group
+    pub left: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NewCycle {
+    pub members: Vec<DiffFunction>,
+    pub crosses_packages: bool,
+}
+
+/// Architectural changes between two revisions
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphDiff {
+    pub base: String,
+    pub head: String,
+    pub added_functions: Vec<DiffFunction>,
+    pub removed_functions: Vec<DiffFunction>,
+    pub added_calls: Vec<DiffCall>,
+    pub removed_calls: Vec<DiffCall>,
+    pub new_entry_points: Vec<DiffEntryPoint>,
+    pub removed_entry_points: Vec<DiffEntryPoint>,
+    pub changed_chains: Vec<ChainChange>,
+    pub changed_groups: Vec<GroupChange>,
+    pub new_cycles: Vec<NewCycle>,
+}
+
+/// Compares snapshots by path relative to their checkout, so both revisions line up
+struct Side<'a> {
+    snapshot: &'a GraphSnapshot,
+    functions: BTreeMap<String, &'a MethodSignature>,
+}
+
+impl<'a> Side<'a> {
+    fn new(snapshot: &'a GraphSnapshot) -> Self {
+        let functions = snapshot.call_graph.nodes.keys()
+            .map(|signature| (function_key(&snapshot.root, signature), signature))
+            .collect();
+        Self { snapshot, functions }
+    }
+
+    fn key(&self, signature: &MethodSignature) -> String {
+        function_key(&self.snapshot.root, signature)
+    }
+
+    fn function(&self, signature: &MethodSignature) -> DiffFunction {
+        DiffFunction {
+            file: relative_path(&self.snapshot.root, &signature.file_path),
+            name: signature.display_name(),
+            line: self.snapshot.call_graph.nodes.get(signature).map(|node| node.line_range.0).unwrap_or(0),
+        }
+    }
+
+    /// Calls keyed by caller and callee, ignoring the line they moved to
+    fn calls(&self) -> BTreeMap<(String, String), DiffCall> {
+        self.snapshot.call_graph.edges.iter()
+            .map(|edge| ((self.key(&edge.caller), self.key(&edge.callee)), DiffCall {
+                caller: self.function(&edge.caller),
+                callee: self.function(&edge.callee),
+                call_site_line: edge.call_site_line,
+            }))
+            .collect()
+    }
+
+    fn entry_points(&self) -> BTreeMap<String, DiffEntryPoint> {
+        self.snapshot.entry_points.iter()
+            .map(|entry_point| (self.key(&entry_point.signature), DiffEntryPoint {
+                function: self.function(&entry_point.signature),
+                entry_type: format!("{:?}", entry_point.entry_type),
+            }))
+            .collect()
+    }
+
+    /// Functions each entry point's chains reach
+    fn reached(&self) -> HashMap<String, BTreeMap<String, DiffFunction>> {
+        let mut reached: HashMap<String, BTreeMap<String, DiffFunction>> = HashMap::new();
+        for chain in &self.snapshot.call_chains {
+            let functions = reached.entry(self.key(&chain.entry_point.signature)).or_default();
+            for step in &chain.steps {
+                functions.insert(self.key(&step.method), self.function(&step.method));
+            }
+        }
+        reached
+    }
+
+    fn group_files(&self, group: &CallChainGroup) -> BTreeSet<String> {
+        group.involved_files.iter().map(|file| relative_path(&self.snapshot.root, file)).collect()
+    }
+
+    fn group_entry_points(&self, group: &CallChainGroup) -> BTreeSet<String> {
+        group.call_chains.iter().map(|chain| self.key(&chain.entry_point.signature)).collect()
+    }
+
+    fn cycles(&self) -> BTreeMap<BTreeSet<String>, NewCycle> {
+        self.snapshot.call_graph.recursive_clusters.iter()
+            .map(|cluster| (
+                cluster.members.iter().map(|member| self.key(member)).collect(),
+                NewCycle {
+                    members: cluster.members.iter().map(|member| self.function(member)).collect(),
+                    crosses_packages: cluster.crosses_packages(),
+                },
+            ))
+            .collect()
+    }
+}
+
+impl GraphDiff {
+    pub fn compute(base: &GraphSnapshot, head: &GraphSnapshot) -> Self {
+        let before = Side::new(base);
+        let after = Side::new(head);
+
+        let added_functions = only_in(&after.functions, &before.functions)
+            .map(|signature| after.function(signature))
+            .collect();
+        let removed_functions = only_in(&before.functions, &after.functions)
+            .map(|signature| before.function(signature))
+            .collect();
+
+        let (calls_before, calls_after) = (before.calls(), after.calls());
+        let (entry_points_before, entry_points_after) = (before.entry_points(), after.entry_points());
+
+        Self {
+            base: base.revision.clone(),
+            head: head.revision.clone(),
+            added_functions,
+            removed_functions,
+            added_calls: only_in(&calls_after, &calls_before).cloned().collect(),
+            removed_calls: only_in(&calls_before, &calls_after).cloned().collect(),
+            new_entry_points: only_in(&entry_points_after, &entry_points_before).cloned().collect(),
+            removed_entry_points: only_in(&entry_points_before, &entry_points_after).cloned().collect(),
+            changed_chains: Self::changed_chains(&before, &after, &entry_points_before),
+            changed_groups: Self::changed_groups(&before, &after),
+            new_cycles: {
+                let cycles_before = before.cycles();
+                after.cycles().into_iter()
+                    .filter(|(members, _)| !cycles_before.contains_key(members))
+                    .map(|(_, cycle)| cycle)
+                    .collect()
+            },
+        }
+    }
+
+    /// Entry points present in both revisions whose chains reach a different set of functions
+    fn changed_chains(before: &Side, after: &Side, entry_points_before: &BTreeMap<String, DiffEntryPoint>) -> Vec<ChainChange> {
+        let (reached_before, reached_after) = (before.reached(), after.reached());
+        let empty = BTreeMap::new();
+
+        let mut changes: Vec<ChainChange> = after.entry_points().into_iter()
+            .filter(|(key, _)| entry_points_before.contains_key(key))
+            .filter_map(|(key, entry_point)| {
+                let was = reached_before.get(&key).unwrap_or(&empty);
+                let now = reached_after.get(&key).unwrap_or(&empty);
+                let newly_reached: Vec<DiffFunction> = only_in(now, was).cloned().collect();
+                let no_longer_reached: Vec<DiffFunction> = only_in(was, now).cloned().collect();
+
+                (!newly_reached.is_empty() || !no_longer_reached.is_empty()).then_some(ChainChange {
+                    entry_point: entry_point.function,
+                    newly_reached,
+                    no_longer_reached,
+                })
+            })
+            .collect();
+        changes.sort_by(|a, b| a.entry_point.cmp(&b.entry_point));
+        changes
+    }
+
+    /// Groups are matched by ID, which follows their most representative entry point;
+    /// an added and a removed group that share entry points are reported as one changed group
+    fn changed_groups(before: &Side, after: &Side) -> Vec<GroupChange> {
+        let (groups_before, groups_after) = (&before.snapshot.groups, &after.snapshot.groups);
+        let entry_points_before: Vec<BTreeSet<String>> = groups_before.iter().map(|group| before.group_entry_points(group)).collect();
+
+        let mut unmatched_before: BTreeSet<usize> = (0..groups_before.len()).collect();
+        let mut changes = Vec::new();
+
+        for group in groups_after {
+            let entry_points = after.group_entry_points(group);
+            let previous = unmatched_before.iter().copied()
+                .find(|&old| groups_before[old].group_id == group.group_id)
+                .or_else(|| unmatched_before.iter().copied().find(|&old| !entry_points_before[old].is_disjoint(&entry_points)));
+
+            let files = after.group_files(group);
+            let change = match previous {
+                Some(old) => {
+                    unmatched_before.remove(&old);
+                    let old_files = before.group_files(&groups_before[old]);
+                    if old_files == files && entry_points_before[old] == entry_points {
+                        continue;
+                    }
+                    GroupChange {
+                        name: group.name.clone(),
+                        status: GroupStatus::Changed,
+                        added_files: files.difference(&old_files).cloned().collect(),
+                        removed_files: old_files.difference(&files).cloned().collect(),
+                        joined: names_of(after, group, |key| !entry_points_before[old].contains(key)),
+                        left: names_of(before, &groups_before[old], |key| !entry_points.contains(key)),
+                    }
+                }
+                None => GroupChange {
+                    name: group.name.clone(),
+                    status: GroupStatus::Added,
+                    added_files: files.into_iter().collect(),
+                    removed_files: Vec::new(),
+                    joined: Vec::new(),
+                    left: Vec::new(),
+                },
+            };
+            changes.push(change);
+        }
+
+        for old in unmatched_before {
+            changes.push(GroupChange {
+                name: groups_before[old].name.clone(),
+                status: GroupStatus::Removed,
+                added_files: Vec::new(),
+                removed_files: before.group_files(&groups_before[old]).into_iter().collect(),
+                joined: Vec::new(),
+                left: Vec::new(),
+            });
+        }
+
+        changes.sort_by(|a, b| a.name.cmp(&b.name));
+        changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_functions.is_empty()
+            && self.removed_functions.is_empty()
+            && self.added_calls.is_empty()
+            && self.removed_calls.is_empty()
+            && self.new_entry_points.is_empty()
+            && self.removed_entry_points.is_empty()
+            && self.changed_chains.is_empty()
+            && self.changed_groups.is_empty()
+            && self.new_cycles.is_empty()
+    }
+
+    /// Render as markdown for a pull request description
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!("## Call graph changes: `{}` → `{}`\n\n", self.base, self.head);
+
+        if self.is_empty() {
+            md.push_str("No architectural changes: functions, calls, entry points and groups are unchanged.\n");
+            return md;
+        }
+
+        md.push_str(&format!(
+            "| | Added | Removed |\n|---|---:|---:|\n| Functions | {} | {} |\n| Calls | {} | {} |\n| Entry points | {} | {} |\n\n",
+            self.added_functions.len(), self.removed_functions.len(),
+            self.added_calls.len(), self.removed_calls.len(),
+            self.new_entry_points.len(), self.removed_entry_points.len()
+        ));
+
+        if !self.new_cycles.is_empty() {
+            md.push_str("### ⚠️ New Cycles\n\n");
+            for cycle in &self.new_cycles {
+                let names: Vec<String> = cycle.members.iter().map(|member| format!("`{}`", member.name)).collect();
+                md.push_str(&format!("- {}{}\n", names.join(" ↔ "),
+                                     if cycle.crosses_packages { " (crosses packages)" } else { "" }));
+            }
+            md.push('\n');
+        }
+
+        write_functions(&mut md, "New Entry Points", self.new_entry_points.iter().map(|entry_point| {
+            format!("{} — {}", entry_point.function.describe(), entry_point.entry_type)
+        }));
+        write_functions(&mut md, "Removed Entry Points", self.removed_entry_points.iter().map(|entry_point| {
+            format!("{} — {}", entry_point.function.describe(), entry_point.entry_type)
+        }));
+
+        if !self.changed_chains.is_empty() {
+            md.push_str("### Changed Call Chains\n\n");
+            for change in &self.changed_chains {
+                md.push_str(&format!("- **`{}`**\n", change.entry_point.name));
+                for function in &change.newly_reached {
+                    md.push_str(&format!("  - now reaches {}\n", function.describe()));
+                }
+                for function in &change.no_longer_reached {
+                    md.push_str(&format!("  - no longer reaches {}\n", function.describe()));
+                }
+            }
+            md.push('\n');
+        }
+
+        if !self.changed_groups.is_empty() {
+            md.push_str("### Changed Groups\n\n");
+            for group in &self.changed_groups {
+                let status = match group.status {
+                    GroupStatus::Added => "added",
+                    GroupStatus::Removed => "removed",
+                    GroupStatus::Changed => "changed",
+                };
+                md.push_str(&format!("- **{}** ({})", group.name, status));
+                if group.status == GroupStatus::Changed {
+                    let mut changes: Vec<String> = group.added_files.iter().map(|file| format!("+`{}`", file)).collect();
+                    changes.extend(group.removed_files.iter().map(|file| format!("-`{}`", file)));
+                    changes.extend(group.joined.iter().map(|name| format!("`{}` joined", name)));
+                    changes.extend(group.left.iter().map(|name| format!("`{}` left", name)));
+                    md.push_str(&format!(": {}", changes.join(", ")));
+                }
+                md.push('\n');
+            }
+            md.push('\n');
+        }
+
+        write_functions(&mut md, "Added Functions", self.added_functions.iter().map(DiffFunction::describe));
+        write_functions(&mut md, "Removed Functions", self.removed_functions.iter().map(DiffFunction::describe));
+        write_functions(&mut md, "New Calls", self.added_calls.iter().map(DiffCall::describe));
+        write_functions(&mut md, "Removed Calls", self.removed_calls.iter().map(DiffCall::describe));
+
+        md
+    }
+}
+
+impl DiffFunction {
+    fn describe(&self) -> String {
+        format!("`{}` ({}:{})", self.name, self.file, self.line)
+    }
+}
+
+impl DiffCall {
+    fn describe(&self) -> String {
+        format!("`{}` → `{}` ({}:{})", self.caller.name, self.callee.name, self.caller.file, self.call_site_line)
+    }
+}
+
+/// Display names of a group's entry points whose keys pass `filter`
+fn names_of(side: &Side, group: &CallChainGroup, filter: impl Fn(&String) -> bool) -> Vec<String> {
+    let mut names: Vec<String> = group.call_chains.iter()
+        .filter(|chain| filter(&side.key(&chain.entry_point.signature)))
+        .map(|chain| chain.entry_point.signature.display_name())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Markdown section with one bullet per item, omitted when there are none
+fn write_functions(md: &mut String, title: &str, items: impl Iterator<Item = String>) {
+    let items: Vec<String> = items.collect();
+    if items.is_empty() {
+        return;
+    }
+    md.push_str(&format!("### {}\n\n", title));
+    for item in items {
+        md.push_str(&format!("- {}\n", item));
+    }
+    md.push('\n');
+}
+
+/// Values whose keys appear in `these` but not in `others`
+fn only_in<'a, K: Ord, V, W>(these: &'a BTreeMap<K, V>, others: &'a BTreeMap<K, W>) -> impl Iterator<Item = &'a V> {
+    these.iter().filter(|(key, _)| !others.contains_key(*key)).map(|(_, value)| value)
+}
+
+fn relative_path(root: &Path, file: &Path) -> String {
+    file.strip_prefix(root).unwrap_or(file).to_string_lossy().replace('\\', "/")
+}
+
+fn function_key(root: &Path, signature: &MethodSignature) -> String {
+    format!("{}#{}", relative_path(root, &signature.file_path), signature.to_unique_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::{self, method};
+    use crate::config::AnalysisConfig;
+    use crate::core::call_graph::{CallChainGrouper, CallChainTracer, EntryPointDetector};
+
+    fn snapshot(root: &str, calls: &[(&str, &str)]) -> GraphSnapshot {
+        let file = format!("{}/src/app/lib.rs", root);
+        let calls: Vec<_> = calls.iter().map(|(caller, callee)| (method(&file, None, caller), method(&file, None, callee))).collect();
+        let calls: Vec<_> = calls.iter().map(|(caller, callee)| (caller, callee, 1)).collect();
+
+        let mut call_graph = test_support::graph(&[], &calls);
+        for node in call_graph.nodes.values_mut() {
+            node.visibility = "private".to_string();
+        }
+        let edges = call_graph.edges.clone();
+        call_graph.recursive_clusters = super::super::recursive_clusters::find_recursive_clusters(call_graph.nodes.keys(), &edges);
+
+        let entry_points = EntryPointDetector::new().detect_entry_points(&call_graph).unwrap();
+        let call_chains = CallChainTracer::new(&AnalysisConfig::default()).trace_all_chains(&call_graph, &entry_points).unwrap();
+        let groups = CallChainGrouper::default().group_call_chains(call_chains.clone(), &[]).unwrap();
+
+        GraphSnapshot { revision: root.to_string(), root: PathBuf::from(root), call_graph, entry_points, call_chains, groups }
+    }
+
+    #[test]
+    fn test_diff_across_checkouts() {
+        let base = snapshot("/tmp/base", &[("main", "load"), ("load", "parse")]);
+        let head = snapshot("/tmp/head", &[("main", "load"), ("load", "validate"), ("validate", "load")]);
+
+        let diff = GraphDiff::compute(&base, &head);
+
+        let names = |functions: &[DiffFunction]| functions.iter().map(|f| f.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&diff.added_functions), ["validate"]);
+        assert_eq!(names(&diff.removed_functions), ["parse"]);
+        assert_eq!(diff.added_calls.len(), 2);
+        assert_eq!(diff.removed_calls.len(), 1);
+        assert_eq!(diff.changed_chains.len(), 1);
+        assert_eq!(names(&diff.changed_chains[0].newly_reached), ["validate"]);
+        assert_eq!(diff.new_cycles.len(), 1);
+        assert!(diff.new_entry_points.is_empty());
+
+        assert!(GraphDiff::compute(&base, &snapshot("/tmp/other", &[("main", "load"), ("load", "parse")])).is_empty());
+    }
+}
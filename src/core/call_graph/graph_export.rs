@@ -0,0 +1,416 @@
+// src/core/call_graph/graph_export.rs
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use serde_json::json;
+
+use crate::error::{CodesworthError, Result};
+use super::{CallGraph, CallType, MethodSignature};
+
+/// Output formats supported by `graph export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Dot,
+    Mermaid,
+    GraphMl,
+    Cytoscape,
+}
+
+impl ExportFormat {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "dot" | "graphviz" => Ok(Self::Dot),
+            "mermaid" => Ok(Self::Mermaid),
+            "graphml" => Ok(Self::GraphMl),
+            "cytoscape" | "cytoscape-json" => Ok(Self::Cytoscape),
+            other => Err(CodesworthError::Config(format!(
+                "Unsupported graph format: {} (expected dot, mermaid, graphml or cytoscape)", other
+            ))),
+        }
+    }
+
+    /// File extension for the exported graph
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Dot => "dot",
+            Self::Mermaid => "mmd",
+            Self::GraphMl => "graphml",
+            Self::Cytoscape => "cytoscape.json",
+        }
+    }
+}
+
+/// Level at which nodes are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    Method,
+    File,
+    Package,
+}
+
+impl Granularity {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "method" | "function" => Ok(Self::Method),
+            "file" => Ok(Self::File),
+            "package" => Ok(Self::Package),
+            other => Err(CodesworthError::Config(format!(
+                "Unsupported collapse level: {} (expected method, file or package)", other
+            ))),
+        }
+    }
+}
+
+/// Which part of the call graph to export
+#[derive(Debug, Clone)]
+pub struct GraphFilter {
+    /// Only keep methods in these packages (all packages when empty)
+    pub packages: Vec<String>,
+    /// Only keep methods reachable from methods with this name
+    pub entry: Option<String>,
+    /// Maximum number of calls to follow from the entry point
+    pub max_depth: Option<usize>,
+    /// Drop edges resolved with less confidence than this
+    pub min_confidence: f32,
+    /// Collapse methods into files or packages
+    pub granularity: Granularity,
+}
+
+impl Default for GraphFilter {
+    fn default() -> Self {
+        Self {
+            packages: Vec::new(),
+            entry: None,
+            max_depth: None,
+            min_confidence: 0.0,
+            granularity: Granularity::Method,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ExportNode {
+    label: String,
+    package: String,
+    file: String,
+    line: usize,
+}
+
+#[derive(Debug, Clone)]
+struct ExportEdge {
+    source: usize,
+    target: usize,
+    call_type: CallType,
+    confidence: f32,
+    /// Number of call sites merged into this edge
+    weight: usize,
+}
+
+/// A filtered, optionally collapsed view of the call graph ready to be rendered
+pub struct GraphExport {
+    nodes: Vec<ExportNode>,
+    edges: Vec<ExportEdge>,
+}
+
+impl GraphExport {
+    /// Apply the filter to the call graph
+    pub fn new(call_graph: &CallGraph, filter: &GraphFilter) -> Result<Self> {
+        let methods = Self::selected_methods(call_graph, filter)?;
+
+        // Sort methods so that node ids are stable between runs
+        let mut ordered: Vec<&MethodSignature> = methods.iter().copied().collect();
+        ordered.sort_by_cached_key(|method| {
+            let line = call_graph.nodes.get(*method).map(|node| node.line_range.0).unwrap_or(0);
+            (method.file_path.clone(), line, method.to_unique_string())
+        });
+
+        let mut nodes = Vec::new();
+        let mut node_ids: HashMap<String, usize> = HashMap::new();
+        let mut method_ids: HashMap<&MethodSignature, usize> = HashMap::new();
+
+        for method in ordered {
+            let line = call_graph.nodes.get(method).map(|node| node.line_range.0).unwrap_or(0);
+            let file = method.file_path.display().to_string();
+            let package = method.package_name();
+            let (key, label) = match filter.granularity {
+                Granularity::Method => (format!("{}::{}", file, method.to_unique_string()), method.display_name()),
+                Granularity::File => (file.clone(), file.clone()),
+                Granularity::Package => (package.clone(), package.clone()),
+            };
+
+            let id = *node_ids.entry(key).or_insert_with(|| {
+                nodes.push(ExportNode {
+                    label,
+                    package: package.clone(),
+                    file: if filter.granularity == Granularity::Package { String::new() } else { file.clone() },
+                    line: if filter.granularity == Granularity::Method { line } else { 0 },
+                });
+                nodes.len() - 1
+            });
+            method_ids.insert(method, id);
+        }
+
+        let mut merged: BTreeMap<(usize, usize), ExportEdge> = BTreeMap::new();
+        for edge in &call_graph.edges {
+            if edge.confidence < filter.min_confidence {
+                continue;
+            }
+            let (Some(&source), Some(&target)) = (method_ids.get(&edge.caller), method_ids.get(&edge.callee)) else {
+                continue;
+            };
+            // Calls inside a collapsed file or package are not interesting at that level
+            if source == target && filter.granularity != Granularity::Method {
+                continue;
+            }
+
+            merged.entry((source, target))
+                .and_modify(|existing| {
+                    existing.weight += 1;
+                    existing.confidence = existing.confidence.max(edge.confidence);
+                })
+                .or_insert_with(|| ExportEdge {
+                    source,
+                    target,
+                    call_type: edge.call_type.clone(),
+                    confidence: edge.confidence,
+                    weight: 1,
+                });
+        }
+
+        Ok(Self {
+            nodes,
+            edges: merged.into_values().collect(),
+        })
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn render(&self, format: ExportFormat) -> Result<String> {
+        match format {
+            ExportFormat::Dot => Ok(self.to_dot()),
+            ExportFormat::Mermaid => Ok(self.to_mermaid()),
+            ExportFormat::GraphMl => Ok(self.to_graphml()),
+            ExportFormat::Cytoscape => Ok(serde_json::to_string_pretty(&self.to_cytoscape())?),
+        }
+    }
+
+    /// Methods that pass the package and entry point filters
+    fn selected_methods<'a>(call_graph: &'a CallGraph, filter: &GraphFilter) -> Result<HashSet<&'a MethodSignature>> {
+        let in_packages = |method: &MethodSignature| {
+            filter.packages.is_empty() || filter.packages.contains(&method.package_name())
+        };
+
+        let Some(entry) = &filter.entry else {
+            return Ok(call_graph.nodes.keys().filter(|method| in_packages(method)).collect());
+        };
+
+        let starts: Vec<&MethodSignature> = call_graph.nodes.keys()
+            .filter(|method| method.display_name() == *entry || method.method_name == *entry)
+            .collect();
+        if starts.is_empty() {
+            return Err(CodesworthError::Query(format!("No function named {} in the call graph", entry)));
+        }
+
+        // Caller/callee pairs with at least one call confident enough to follow
+        let confident: HashSet<(&MethodSignature, &MethodSignature)> = call_graph.edges.iter()
+            .filter(|edge| edge.confidence >= filter.min_confidence)
+            .map(|edge| (&edge.caller, &edge.callee))
+            .collect();
+
+        let mut selected = HashSet::new();
+        let mut queue: VecDeque<(&MethodSignature, usize)> = starts.into_iter().map(|start| (start, 0)).collect();
+        while let Some((method, depth)) = queue.pop_front() {
+            if !in_packages(method) || !selected.insert(method) {
+                continue;
+            }
+            if filter.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+                continue;
+            }
+            for callee in call_graph.get_callees(method) {
+                if confident.contains(&(method, callee)) {
+                    queue.push_back((callee, depth + 1));
+                }
+            }
+        }
+
+        Ok(selected)
+    }
+
+    fn to_dot(&self) -> String {
+        let mut out = String::from("digraph call_graph {\n    rankdir=LR;\n    node [shape=box, fontname=\"Helvetica\"];\n\n");
+
+        let mut by_package: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (id, node) in self.nodes.iter().enumerate() {
+            by_package.entry(node.package.as_str()).or_default().push(id);
+        }
+
+        for (index, (package, ids)) in by_package.iter().enumerate() {
+            out.push_str(&format!("    subgraph cluster_{} {{\n        label=\"{}\";\n", index, dot_escape(package)));
+            for id in ids {
+                let node = &self.nodes[*id];
+                let tooltip = if node.line > 0 { format!("{}:{}", node.file, node.line) } else { node.file.clone() };
+                out.push_str(&format!(
+                    "        n{} [label=\"{}\", tooltip=\"{}\"];\n",
+                    id, dot_escape(&node.label), dot_escape(&tooltip)
+                ));
+            }
+            out.push_str("    }\n");
+        }
+        out.push('\n');
+
+        for edge in &self.edges {
+            let style = match edge.call_type {
+                CallType::Dynamic => ", style=dashed",
+                CallType::Async => ", color=blue",
+                CallType::Callback => ", style=dotted",
+                CallType::CrossService => ", color=darkorange, penwidth=2",
+                _ => "",
+            };
+            let label = if edge.weight > 1 { format!(", label=\"{}\"", edge.weight) } else { String::new() };
+            out.push_str(&format!("    n{} -> n{} [tooltip=\"{:?} ({:.2})\"{}{}];\n",
+                                  edge.source, edge.target, edge.call_type, edge.confidence, style, label));
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    fn to_mermaid(&self) -> String {
+        let mut out = String::from("flowchart LR\n");
+
+        for (id, node) in self.nodes.iter().enumerate() {
+            out.push_str(&format!("    n{}[\"{}\"]\n", id, mermaid_escape(&node.label)));
+        }
+
+        for edge in &self.edges {
+            let arrow = match edge.call_type {
+                CallType::Dynamic | CallType::Callback => "-.->",
+                CallType::CrossService => "==>",
+                _ => "-->",
+            };
+            if edge.weight > 1 {
+                out.push_str(&format!("    n{} {}|{}| n{}\n", edge.source, arrow, edge.weight, edge.target));
+            } else {
+                out.push_str(&format!("    n{} {} n{}\n", edge.source, arrow, edge.target));
+            }
+        }
+
+        out
+    }
+
+    fn to_graphml(&self) -> String {
+        let mut out = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+            "  <key id=\"package\" for=\"node\" attr.name=\"package\" attr.type=\"string\"/>\n",
+            "  <key id=\"file\" for=\"node\" attr.name=\"file\" attr.type=\"string\"/>\n",
+            "  <key id=\"line\" for=\"node\" attr.name=\"line\" attr.type=\"int\"/>\n",
+            "  <key id=\"call_type\" for=\"edge\" attr.name=\"call_type\" attr.type=\"string\"/>\n",
+            "  <key id=\"confidence\" for=\"edge\" attr.name=\"confidence\" attr.type=\"double\"/>\n",
+            "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"int\"/>\n",
+            "  <graph id=\"call_graph\" edgedefault=\"directed\">\n",
+        ));
+
+        for (id, node) in self.nodes.iter().enumerate() {
+            out.push_str(&format!(
+                "    <node id=\"n{}\">\n      <data key=\"label\">{}</data>\n      <data key=\"package\">{}</data>\n      <data key=\"file\">{}</data>\n      <data key=\"line\">{}</data>\n    </node>\n",
+                id, xml_escape(&node.label), xml_escape(&node.package), xml_escape(&node.file), node.line
+            ));
+        }
+
+        for (id, edge) in self.edges.iter().enumerate() {
+            out.push_str(&format!(
+                "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\">\n      <data key=\"call_type\">{:?}</data>\n      <data key=\"confidence\">{:.2}</data>\n      <data key=\"weight\">{}</data>\n    </edge>\n",
+                id, edge.source, edge.target, edge.call_type, edge.confidence, edge.weight
+            ));
+        }
+
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    fn to_cytoscape(&self) -> serde_json::Value {
+        let nodes: Vec<serde_json::Value> = self.nodes.iter().enumerate()
+            .map(|(id, node)| json!({
+                "data": {
+                    "id": format!("n{}", id),
+                    "label": node.label,
+                    "package": node.package,
+                    "file": node.file,
+                    "line": node.line,
+                }
+            }))
+            .collect();
+
+        let edges: Vec<serde_json::Value> = self.edges.iter().enumerate()
+            .map(|(id, edge)| json!({
+                "data": {
+                    "id": format!("e{}", id),
+                    "source": format!("n{}", edge.source),
+                    "target": format!("n{}", edge.target),
+                    "call_type": edge.call_type,
+                    "confidence": edge.confidence,
+                    "weight": edge.weight,
+                }
+            }))
+            .collect();
+
+        json!({ "elements": { "nodes": nodes, "edges": edges } })
+    }
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_escape(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::{self, method};
+
+    fn graph() -> CallGraph {
+        let (place, validate) = (method("/p/src/orders/service.rs", None, "place"), method("/p/src/orders/service.rs", None, "validate"));
+        let (insert, write) = (method("/p/src/util/store.rs", None, "insert"), method("/p/src/util/log.rs", None, "write"));
+        let mut graph = test_support::graph(&[], &[(&place, &insert, 3), (&place, &validate, 3), (&insert, &write, 3)]);
+        for (edge, confidence) in graph.edges.iter_mut().zip([0.95, 0.95, 0.4]) {
+            edge.confidence = confidence;
+        }
+        graph
+    }
+
+    #[test]
+    fn test_entry_depth_and_confidence_filters() {
+        let filter = GraphFilter { entry: Some("place".to_string()), max_depth: Some(1), ..GraphFilter::default() };
+        let export = GraphExport::new(&graph(), &filter).unwrap();
+        assert_eq!((export.node_count(), export.edge_count()), (3, 2));
+
+        let filter = GraphFilter { min_confidence: 0.5, ..GraphFilter::default() };
+        let export = GraphExport::new(&graph(), &filter).unwrap();
+        assert_eq!(export.edge_count(), 2);
+    }
+
+    #[test]
+    fn test_collapse_to_packages() {
+        let filter = GraphFilter { granularity: Granularity::Package, ..GraphFilter::default() };
+        let export = GraphExport::new(&graph(), &filter).unwrap();
+
+        assert_eq!(export.node_count(), 2);
+        assert_eq!(export.render(ExportFormat::Mermaid).unwrap(), "flowchart LR\n    n0[\"orders\"]\n    n1[\"util\"]\n    n0 --> n1\n");
+    }
+}
@@ -0,0 +1,450 @@
+// src/core/call_graph/graph_query.rs
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use serde::Serialize;
+
+use crate::error::{CodesworthError, Result};
+use super::{CallEdge, CallGraph, EntryPoint, MethodSignature};
+
+/// A question asked of the call graph from the command line
+#[derive(Debug, Clone)]
+pub enum GraphQueryRequest {
+    /// Who calls `function`, up to `depth` levels up
+    Callers { function: String, depth: usize },
+    /// What `function` calls, up to `depth` levels down
+    Callees { function: String, depth: usize },
+    /// Call paths from `from` to `to`: the shortest, or all simple paths up to `max_paths`
+    Path { from: String, to: String, all: bool, max_paths: usize },
+    /// Everything `function` can (transitively) call
+    ReachableFrom { function: String, depth: Option<usize> },
+    /// Entry points that can reach `function`
+    Impact { function: String },
+}
+
+/// A function as it appears in query output
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryMethod {
+    pub name: String,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// Callers or callees rooted at one function
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryTree {
+    #[serde(flatten)]
+    pub method: QueryMethod,
+    /// Line of the call linking this function to its parent in the tree
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub call_site_line: Option<usize>,
+    /// Already shown elsewhere in the tree, so not expanded again
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub repeated: bool,
+    pub children: Vec<QueryTree>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReachableMethod {
+    #[serde(flatten)]
+    pub method: QueryMethod,
+    /// Number of calls from the starting function
+    pub distance: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImpactedEntryPoint {
+    pub entry_point: QueryMethod,
+    pub entry_type: String,
+    /// Shortest call path from the entry point to the queried function
+    pub path: Vec<QueryMethod>,
+}
+
+/// Result of a graph query, printable as a tree or as JSON
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case", tag = "query", content = "results")]
+pub enum QueryOutput {
+    Callers(Vec<QueryTree>),
+    Callees(Vec<QueryTree>),
+    Paths(Vec<Vec<QueryMethod>>),
+    Reachable(Vec<ReachableMethod>),
+    Impact(Vec<ImpactedEntryPoint>),
+}
+
+/// Answers reachability questions over a built call graph
+pub struct GraphQuery<'a> {
+    call_graph: &'a CallGraph,
+    /// Outgoing edges, one per distinct callee, in call-site order
+    outgoing: HashMap<&'a MethodSignature, Vec<&'a CallEdge>>,
+    /// Incoming edges, one per distinct caller
+    incoming: HashMap<&'a MethodSignature, Vec<&'a CallEdge>>,
+}
+
+impl<'a> GraphQuery<'a> {
+    pub fn new(call_graph: &'a CallGraph) -> Self {
+        let mut outgoing: HashMap<&MethodSignature, Vec<&CallEdge>> = HashMap::new();
+        let mut incoming: HashMap<&MethodSignature, Vec<&CallEdge>> = HashMap::new();
+
+        for edge in &call_graph.edges {
+            outgoing.entry(&edge.caller).or_default().push(edge);
+            incoming.entry(&edge.callee).or_default().push(edge);
+        }
+
+        for edges in outgoing.values_mut() {
+            edges.sort_by_key(|edge| edge.call_site_line);
+            let mut seen = HashSet::new();
+            edges.retain(|edge| seen.insert(&edge.callee));
+        }
+        for edges in incoming.values_mut() {
+            edges.sort_by_key(|edge| (edge.caller.file_path.clone(), edge.call_site_line));
+            let mut seen = HashSet::new();
+            edges.retain(|edge| seen.insert(&edge.caller));
+        }
+
+        Self { call_graph, outgoing, incoming }
+    }
+
+    pub fn run(&self, request: &GraphQueryRequest, entry_points: &[EntryPoint]) -> Result<QueryOutput> {
+        match request {
+            GraphQueryRequest::Callers { function, depth } => {
+                Ok(QueryOutput::Callers(self.trees(function, *depth, true)?))
+            }
+            GraphQueryRequest::Callees { function, depth } => {
+                Ok(QueryOutput::Callees(self.trees(function, *depth, false)?))
+            }
+            GraphQueryRequest::Path { from, to, all, max_paths } => {
+                let targets: HashSet<&MethodSignature> = self.resolve(to)?.into_iter().collect();
+                let mut paths = Vec::new();
+                for start in self.resolve(from)? {
+                    if *all {
+                        self.all_paths(start, &targets, *max_paths, &mut vec![start], &mut paths);
+                    } else if let Some(path) = self.shortest_path(start, &targets) {
+                        paths.push(path);
+                    }
+                }
+                if !*all {
+                    paths.sort_by_key(|path| path.len());
+                    paths.truncate(1);
+                }
+                Ok(QueryOutput::Paths(paths.iter().map(|path| self.methods(path)).collect()))
+            }
+            GraphQueryRequest::ReachableFrom { function, depth } => {
+                let mut distances: HashMap<&MethodSignature, usize> = HashMap::new();
+                for start in self.resolve(function)? {
+                    for (method, distance) in self.distances(start, *depth, false) {
+                        let best = distances.entry(method).or_insert(distance);
+                        *best = (*best).min(distance);
+                    }
+                }
+
+                let mut reachable: Vec<ReachableMethod> = distances.into_iter()
+                    .filter(|(_, distance)| *distance > 0)
+                    .map(|(method, distance)| ReachableMethod { method: self.method(method), distance })
+                    .collect();
+                reachable.sort_by(|a, b| a.distance.cmp(&b.distance).then(a.method.name.cmp(&b.method.name)));
+                Ok(QueryOutput::Reachable(reachable))
+            }
+            GraphQueryRequest::Impact { function } => {
+                let targets: HashSet<&MethodSignature> = self.resolve(function)?.into_iter().collect();
+                let mut impacted: Vec<ImpactedEntryPoint> = entry_points.iter()
+                    .filter_map(|entry_point| {
+                        let path = self.shortest_path(&entry_point.signature, &targets)?;
+                        Some(ImpactedEntryPoint {
+                            entry_point: self.method(&entry_point.signature),
+                            entry_type: format!("{:?}", entry_point.entry_type),
+                            path: self.methods(&path),
+                        })
+                    })
+                    .collect();
+                impacted.sort_by(|a, b| a.path.len().cmp(&b.path.len()).then(a.entry_point.name.cmp(&b.entry_point.name)));
+                Ok(QueryOutput::Impact(impacted))
+            }
+        }
+    }
+
+    /// Functions whose display name (`Type::method`) or plain name matches
+    pub fn resolve(&self, name: &str) -> Result<Vec<&'a MethodSignature>> {
+        let mut matches: Vec<&MethodSignature> = self.call_graph.nodes.keys()
+            .filter(|method| method.display_name() == name)
+            .collect();
+        if matches.is_empty() {
+            matches = self.call_graph.nodes.keys()
+                .filter(|method| method.method_name == name)
+                .collect();
+        }
+        if matches.is_empty() {
+            return Err(CodesworthError::Query(format!("No function named {} in the call graph", name)));
+        }
+
+        matches.sort_by_cached_key(|method| (method.file_path.clone(), self.line(method)));
+        Ok(matches)
+    }
+
+    fn trees(&self, name: &str, depth: usize, upwards: bool) -> Result<Vec<QueryTree>> {
+        Ok(self.resolve(name)?.into_iter()
+            .map(|method| {
+                let mut expanded = HashSet::new();
+                self.tree(method, None, depth, upwards, &mut expanded)
+            })
+            .collect())
+    }
+
+    fn tree(
+        &self,
+        method: &'a MethodSignature,
+        call_site_line: Option<usize>,
+        depth: usize,
+        upwards: bool,
+        expanded: &mut HashSet<&'a MethodSignature>,
+    ) -> QueryTree {
+        let edges = if upwards { self.incoming.get(method) } else { self.outgoing.get(method) };
+        let edges = edges.map(Vec::as_slice).unwrap_or_default();
+
+        let repeated = !edges.is_empty() && depth > 0 && !expanded.insert(method);
+        let children = if repeated || depth == 0 {
+            Vec::new()
+        } else {
+            edges.iter()
+                .map(|edge| {
+                    let next = if upwards { &edge.caller } else { &edge.callee };
+                    self.tree(next, Some(edge.call_site_line), depth - 1, upwards, expanded)
+                })
+                .collect()
+        };
+
+        QueryTree { method: self.method(method), call_site_line, repeated, children }
+    }
+
+    /// Breadth-first distances from `start`, following calls forwards or backwards
+    fn distances(&self, start: &'a MethodSignature, max_depth: Option<usize>, upwards: bool) -> HashMap<&'a MethodSignature, usize> {
+        let mut distances = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(method) = queue.pop_front() {
+            let distance = distances[method];
+            if max_depth.is_some_and(|max_depth| distance >= max_depth) {
+                continue;
+            }
+            let edges = if upwards { self.incoming.get(method) } else { self.outgoing.get(method) };
+            for edge in edges.into_iter().flatten() {
+                let next = if upwards { &edge.caller } else { &edge.callee };
+                if !distances.contains_key(next) {
+                    distances.insert(next, distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        distances
+    }
+
+    fn shortest_path(&self, start: &'a MethodSignature, targets: &HashSet<&MethodSignature>) -> Option<Vec<&'a MethodSignature>> {
+        let mut previous: HashMap<&MethodSignature, &MethodSignature> = HashMap::new();
+        let mut seen = HashSet::from([start]);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(method) = queue.pop_front() {
+            if targets.contains(method) {
+                let mut path = vec![method];
+                let mut current = method;
+                while let Some(&before) = previous.get(current) {
+                    path.push(before);
+                    current = before;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for edge in self.outgoing.get(method).into_iter().flatten() {
+                if seen.insert(&edge.callee) {
+                    previous.insert(&edge.callee, method);
+                    queue.push_back(&edge.callee);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Depth-first enumeration of simple paths, stopping after `max_paths`
+    fn all_paths(
+        &self,
+        method: &'a MethodSignature,
+        targets: &HashSet<&MethodSignature>,
+        max_paths: usize,
+        path: &mut Vec<&'a MethodSignature>,
+        paths: &mut Vec<Vec<&'a MethodSignature>>,
+    ) {
+        if paths.len() >= max_paths {
+            return;
+        }
+        if targets.contains(method) {
+            paths.push(path.clone());
+            return;
+        }
+
+        for edge in self.outgoing.get(method).into_iter().flatten() {
+            if !path.contains(&&edge.callee) {
+                path.push(&edge.callee);
+                self.all_paths(&edge.callee, targets, max_paths, path, paths);
+                path.pop();
+            }
+        }
+    }
+
+    fn line(&self, method: &MethodSignature) -> usize {
+        self.call_graph.nodes.get(method).map(|node| node.line_range.0).unwrap_or(0)
+    }
+
+    fn method(&self, method: &MethodSignature) -> QueryMethod {
+        QueryMethod {
+            name: method.display_name(),
+            file: method.file_path.clone(),
+            line: self.line(method),
+        }
+    }
+
+    fn methods(&self, path: &[&MethodSignature]) -> Vec<QueryMethod> {
+        path.iter().map(|method| self.method(method)).collect()
+    }
+}
+
+impl QueryMethod {
+    fn location(&self) -> String {
+        format!("{} ({}:{})", self.name, self.file.display(), self.line)
+    }
+}
+
+impl QueryOutput {
+    /// Human-readable tree or list
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+
+        match self {
+            Self::Callers(trees) | Self::Callees(trees) => {
+                for tree in trees {
+                    out.push_str(&tree.method.location());
+                    out.push('\n');
+                    let count = tree.children.len();
+                    for (index, child) in tree.children.iter().enumerate() {
+                        child.write_branch(&mut out, "", index + 1 == count);
+                    }
+                }
+            }
+            Self::Paths(paths) => {
+                if paths.is_empty() {
+                    out.push_str("No call path found\n");
+                }
+                for path in paths {
+                    let names: Vec<&str> = path.iter().map(|method| method.name.as_str()).collect();
+                    out.push_str(&format!("{}\n", names.join(" -> ")));
+                }
+            }
+            Self::Reachable(methods) => {
+                for reachable in methods {
+                    out.push_str(&format!("{:>3}  {}\n", reachable.distance, reachable.method.location()));
+                }
+            }
+            Self::Impact(impacted) => {
+                if impacted.is_empty() {
+                    out.push_str("No entry point reaches this function\n");
+                }
+                for entry in impacted {
+                    let names: Vec<&str> = entry.path.iter().map(|method| method.name.as_str()).collect();
+                    out.push_str(&format!("{} [{}]\n    {}\n", entry.entry_point.location(), entry.entry_type, names.join(" -> ")));
+                }
+            }
+        }
+
+        out
+    }
+}
+
+impl QueryTree {
+    fn write_branch(&self, out: &mut String, prefix: &str, last: bool) {
+        out.push_str(prefix);
+        out.push_str(if last { "└── " } else { "├── " });
+        out.push_str(&self.method.location());
+        if let Some(line) = self.call_site_line {
+            out.push_str(&format!(" [line {}]", line));
+        }
+        if self.repeated {
+            out.push_str(" (see above)");
+        }
+        out.push('\n');
+
+        let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        let count = self.children.len();
+        for (index, child) in self.children.iter().enumerate() {
+            child.write_branch(out, &child_prefix, index + 1 == count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::{self, entry_point};
+    use crate::core::call_graph::EntryPointType;
+
+    fn method(name: &str) -> MethodSignature {
+        test_support::method("src/lib.rs", None, name)
+    }
+
+    fn graph() -> CallGraph {
+        // main -> a -> c, main -> b -> c -> d
+        let calls: Vec<_> = [("main", "a"), ("main", "b"), ("a", "c"), ("b", "c"), ("c", "d")].into_iter()
+            .map(|(caller, callee)| (method(caller), method(callee)))
+            .collect();
+        let calls: Vec<_> = calls.iter().enumerate().map(|(line, (caller, callee))| (caller, callee, line + 10)).collect();
+        test_support::graph(&[], &calls)
+    }
+
+    #[test]
+    fn test_paths_and_impact() {
+        let graph = graph();
+        let query = GraphQuery::new(&graph);
+
+        let request = GraphQueryRequest::Path { from: "main".to_string(), to: "d".to_string(), all: true, max_paths: 10 };
+        assert_eq!(query.run(&request, &[]).unwrap().to_text(), "main -> a -> c -> d\nmain -> b -> c -> d\n");
+
+        let entry_points = vec![entry_point(&method("main"), EntryPointType::Main)];
+        let QueryOutput::Impact(impacted) = query.run(&GraphQueryRequest::Impact { function: "c".to_string() }, &entry_points).unwrap() else {
+            panic!("expected impact output");
+        };
+        assert_eq!(impacted.len(), 1);
+        assert_eq!(impacted[0].path.len(), 3);
+    }
+
+    #[test]
+    fn test_call_trees_mark_repeats() {
+        let graph = graph();
+        let query = GraphQuery::new(&graph);
+        let text = query.run(&GraphQueryRequest::Callers { function: "c".to_string(), depth: 3 }, &[]).unwrap().to_text();
+
+        // `main` calls nothing further up, so reaching it twice is not a repeat
+        assert_eq!(text, concat!(
+            "c (src/lib.rs:1)\n",
+            "├── a (src/lib.rs:1) [line 12]\n",
+            "│   └── main (src/lib.rs:1) [line 10]\n",
+            "└── b (src/lib.rs:1) [line 13]\n",
+            "    └── main (src/lib.rs:1) [line 11]\n",
+        ));
+
+        // The diamond reaches `c` through `a` and `b`; its subtree is expanded only the first time
+        let QueryOutput::Callees(trees) = query.run(&GraphQueryRequest::Callees { function: "main".to_string(), depth: 3 }, &[]).unwrap() else {
+            panic!("expected callees output");
+        };
+        let [through_a, through_b] = trees[0].children.as_slice() else {
+            panic!("expected two callees: {:?}", trees[0].children);
+        };
+        assert!(!through_a.children[0].repeated && through_a.children[0].children.len() == 1);
+        assert!(through_b.children[0].repeated && through_b.children[0].children.is_empty());
+        assert_eq!(QueryOutput::Callees(trees).to_text(), concat!(
+            "main (src/lib.rs:1)\n",
+            "├── a (src/lib.rs:1) [line 10]\n",
+            "│   └── c (src/lib.rs:1) [line 12]\n",
+            "│       └── d (src/lib.rs:1) [line 14]\n",
+            "└── b (src/lib.rs:1) [line 11]\n",
+            "    └── c (src/lib.rs:1) [line 13] (see above)\n",
+        ));
+    }
+}
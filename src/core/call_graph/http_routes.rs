@@ -0,0 +1,813 @@
+// src/core/call_graph/http_routes.rs
+use std::collections::{HashMap, HashSet};
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+
+use super::super::ParsedFile;
+use super::{CallEdge, CallGraph, CallNode, CallType, MethodSignature};
+
+/// Confidence of an edge matched on URL shape alone
+const CROSS_SERVICE_CONFIDENCE: f32 = 0.7;
+
+/// Characters that can follow a client call to close its options (`fetch(url, {...})`)
+const CLIENT_OPTIONS_WINDOW: usize = 300;
+
+/// An HTTP route a server-side function handles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpRoute {
+    /// Upper-case HTTP method, or `None` when the route accepts any
+    pub method: Option<String>,
+    /// Path as declared, with the controller prefix applied
+    pub path: String,
+    pub handler: MethodSignature,
+    /// Line of the route declaration
+    pub line: usize,
+    /// Framework the route is declared with, e.g. "Spring" or "FastAPI"
+    #[serde(default)]
+    pub framework: String,
+}
+
+impl HttpRoute {
+    /// Method and path, e.g. "GET /orders/{id}" ("ANY" when the route accepts any method)
+    pub fn endpoint(&self) -> String {
+        format!("{} {}", self.method.as_deref().unwrap_or("ANY"), self.path)
+    }
+}
+
+/// An outgoing HTTP request found in client code
+#[derive(Debug, Clone)]
+struct HttpClientCall {
+    method: Option<String>,
+    url: String,
+    line: usize,
+}
+
+/// Finds route declarations and HTTP client calls, and links them with cross-service edges
+pub struct HttpRouteLinker {
+    spring_mapping: Regex,
+    spring_method: Regex,
+    jaxrs_annotation: Regex,
+    aspnet_attribute: Regex,
+    minimal_api_route: Regex,
+    minimal_api_group: Regex,
+    python_decorator: Regex,
+    python_methods: Regex,
+    python_router: Regex,
+    python_prefix: Regex,
+    django_route: Regex,
+    express_route: Regex,
+    next_default_export: Regex,
+    rust_attribute: Regex,
+    axum_route: Regex,
+    axum_handler: Regex,
+    actix_resource: Regex,
+    actix_handler: Regex,
+    type_declaration: Regex,
+    client_call: Regex,
+    client_method: Regex,
+}
+
+/// A path split into segments, `None` standing for a parameter (`{id}`, `:id`, `${id}`, `<id>`)
+type Segments = Vec<Option<String>>;
+
+impl HttpRouteLinker {
+    pub fn new() -> Self {
+        let regex = |pattern: &str| Regex::new(pattern).expect("valid regex");
+        // A string literal, optionally inside `format!(...)` or with an f/$/@ prefix
+        let url = r#"(?:&?format!\(\s*)?[f$@]?(?:"([^"\n]*)"|'([^'\n]*)'|`([^`]*)`)"#;
+
+        Self {
+            spring_mapping: regex(r#"@(Get|Post|Put|Delete|Patch|Request)Mapping\b(?:\s*\(([^)]*)\))?"#),
+            spring_method: regex(r"RequestMethod\.([A-Z]+)"),
+            jaxrs_annotation: regex(r#"@(?:(GET|POST|PUT|DELETE|PATCH|HEAD|OPTIONS)\b|Path\s*\(\s*(?:value\s*=\s*)?"([^"]*)"\s*\))"#),
+            aspnet_attribute: regex(r#"\[\s*(?:Http(Get|Post|Put|Delete|Patch)|(Route))\s*(?:\(\s*@?"([^"]*)"[^)]*\))?\s*[\],]"#),
+            minimal_api_route: regex(r#"\b(\w+)\.Map(Get|Post|Put|Delete|Patch)\(\s*"([^"]*)"\s*,\s*([\w.]+)\s*\)"#),
+            minimal_api_group: regex(r#"\b(\w+)\s*=\s*(\w+)\.MapGroup\(\s*"([^"]*)"\s*\)"#),
+            python_decorator: regex(r#"@(\w+(?:\.\w+)*)\.(route|get|post|put|delete|patch)\(\s*[rf]?["']([^"']*)["']([^)]*)\)"#),
+            python_methods: regex(r#"methods\s*=\s*[\[(]\s*["'](\w+)["']"#),
+            python_router: regex(r"\b(\w+)\s*=\s*(?:\w+\.)*(?:APIRouter|Blueprint)\(([^)]*)\)"),
+            python_prefix: regex(r#"\b(?:url_)?prefix\s*=\s*["']([^"']*)["']"#),
+            django_route: regex(r#"\b(?:path|re_path|url)\(\s*r?["']([^"']*)["']\s*,\s*([\w.]+)(\(\s*\))?\s*[,)]"#),
+            express_route: regex(r#"\b\w+\.(get|post|put|delete|patch|all)\(\s*["'`]([^"'`]+)["'`]\s*,\s*(?:[\w.]+\s*,\s*)*([\w.]+)\s*\)"#),
+            next_default_export: regex(r"(?m)\bexport\s+default\s+(?:async\s+)?(?:function\s+(\w+)|(\w+)\s*;?\s*$)"),
+            rust_attribute: regex(r#"#\[\s*(?:actix_web::)?(get|post|put|delete|patch)\(\s*"([^"]*)""#),
+            axum_route: regex(r#"\.route\(\s*"([^"]*)"\s*,"#),
+            axum_handler: regex(r"\b(get|post|put|delete|patch|any)\(\s*([\w:]+)\s*\)"),
+            actix_resource: regex(r#"\bresource\(\s*"([^"]*)"\s*\)"#),
+            actix_handler: regex(r"\b(get|post|put|delete|patch)\(\)\s*\.to\(\s*([\w:]+)\s*\)"),
+            type_declaration: regex(r"\b(?:class|interface|record|struct)\s+(\w+)"),
+            client_call: regex(&format!(
+                r"(?:\b(fetch)|\breqwest::(get)|\.(get|post|put|delete|patch|request)|\.(getForObject|getForEntity|postForObject|postForEntity|patchForObject|exchange)|\.(get|post|put|delete|patch)\(\)\s*\.uri|\.(Get|Post|Put|Delete|Patch|Send)(?:FromJson|AsJson|String|Stream|ByteArray)?Async)(?:<[^<>()]*>)?\(\s*{}",
+                url
+            )),
+            client_method: regex(r#"(?:method\s*[:=]\s*["'`]|HttpMethod\.)([A-Za-z]+)"#),
+        }
+    }
+
+    /// Find every route declaration whose handler is a function in the graph
+    pub fn detect_routes(&self, call_graph: &CallGraph, files: &[ParsedFile]) -> Vec<HttpRoute> {
+        let mut routes = Vec::new();
+        for file in files {
+            let lines = LineIndex::new(&file.source_content);
+            match file.language.as_str() {
+                "java" => {
+                    self.spring_routes(call_graph, file, &lines, &mut routes);
+                    self.jaxrs_routes(call_graph, file, &lines, &mut routes);
+                }
+                "csharp" => {
+                    self.aspnet_routes(call_graph, file, &lines, &mut routes);
+                    self.minimal_api_routes(call_graph, file, &lines, &mut routes);
+                }
+                "python" => {
+                    self.python_routes(call_graph, file, &lines, &mut routes);
+                    self.django_routes(call_graph, file, &lines, &mut routes);
+                }
+                "javascript" => {
+                    self.express_routes(call_graph, file, &lines, &mut routes);
+                    self.nextjs_routes(call_graph, file, &lines, &mut routes);
+                }
+                "rust" => self.rust_routes(call_graph, file, &lines, &mut routes),
+                _ => {}
+            }
+        }
+
+        routes.sort_by(|a, b| a.handler.file_path.cmp(&b.handler.file_path).then(a.line.cmp(&b.line)));
+        routes
+    }
+
+    /// Edges from functions making HTTP requests to the handlers of the routes they hit
+    pub fn link_calls(&self, call_graph: &CallGraph, files: &[ParsedFile], routes: &[HttpRoute]) -> Vec<CallEdge> {
+        let route_segments: Vec<Segments> = routes.iter().map(|route| segments(&route.path)).collect();
+
+        let mut edges = Vec::new();
+        let mut seen = HashSet::new();
+        for file in files {
+            for call in self.client_calls(file) {
+                let Some(caller) = call_graph.find_containing_method(file, call.line) else {
+                    continue;
+                };
+
+                let client = segments(&call.url);
+                let scores: Vec<(usize, usize)> = routes.iter()
+                    .enumerate()
+                    .filter(|(_, route)| match (&call.method, &route.method) {
+                        (Some(requested), Some(served)) => requested == served,
+                        _ => true,
+                    })
+                    .filter_map(|(index, _)| match_score(&client, &route_segments[index]).map(|score| (index, score)))
+                    .collect();
+                let Some(best) = scores.iter().map(|(_, score)| *score).max() else {
+                    continue;
+                };
+
+                for (index, _) in scores.into_iter().filter(|(_, score)| *score == best) {
+                    let handler = &routes[index].handler;
+                    if *handler != caller && seen.insert((caller.clone(), handler.clone(), call.line)) {
+                        edges.push(CallEdge {
+                            caller: caller.clone(),
+                            callee: handler.clone(),
+                            call_site_line: call.line,
+                            call_type: CallType::CrossService,
+                            confidence: CROSS_SERVICE_CONFIDENCE,
+                        });
+                    }
+                }
+            }
+        }
+
+        edges
+    }
+
+    /// `@GetMapping("/x")` on methods, with a class-level `@RequestMapping` as prefix
+    fn spring_routes(&self, call_graph: &CallGraph, file: &ParsedFile, lines: &LineIndex, routes: &mut Vec<HttpRoute>) {
+        let content = &file.source_content;
+        let mut prefix = String::new();
+
+        for captures in self.spring_mapping.captures_iter(content) {
+            let whole = captures.get(0).expect("match");
+            let arguments = captures.get(2).map_or("", |arguments| arguments.as_str());
+            let path = first_string_literal(arguments).unwrap_or_default();
+
+            if self.annotated_type(content, whole.end()).is_some() {
+                prefix = path;
+                continue;
+            }
+
+            let method = match &captures[1] {
+                "Request" => self.spring_method.captures(arguments).map(|method| method[1].to_string()),
+                verb => Some(verb.to_uppercase()),
+            };
+            let line = lines.line_of(whole.start());
+            if let Some(handler) = next_function(call_graph, file, line) {
+                routes.push(HttpRoute { method, path: join_paths(&prefix, &path), handler, line, framework: "Spring".to_string() });
+            }
+        }
+    }
+
+    /// JAX-RS `@GET`/`@POST` resource methods, with method- and class-level `@Path`
+    fn jaxrs_routes(&self, call_graph: &CallGraph, file: &ParsedFile, lines: &LineIndex, routes: &mut Vec<HttpRoute>) {
+        struct Resource {
+            handler: MethodSignature,
+            method: Option<String>,
+            path: String,
+            line: usize,
+        }
+
+        let content = &file.source_content;
+        let mut prefix = String::new();
+        let mut resources: Vec<Resource> = Vec::new();
+
+        for captures in self.jaxrs_annotation.captures_iter(content) {
+            let whole = captures.get(0).expect("match");
+            let path = captures.get(2).map(|path| path.as_str().to_string());
+            if path.is_some() && self.annotated_type(content, whole.end()).is_some() {
+                prefix = path.unwrap_or_default();
+                continue;
+            }
+
+            let line = lines.line_of(whole.start());
+            let Some(handler) = next_function(call_graph, file, line) else {
+                continue;
+            };
+            if resources.last().is_none_or(|resource| resource.handler != handler) {
+                resources.push(Resource { handler, method: None, path: String::new(), line });
+            }
+            let resource = resources.last_mut().expect("resource");
+            match (captures.get(1), path) {
+                (Some(verb), _) => resource.method = Some(verb.as_str().to_string()),
+                (None, Some(path)) => resource.path = path,
+                (None, None) => {}
+            }
+        }
+
+        // A `@Path` without a verb is a sub-resource locator, not a route
+        for resource in resources.into_iter().filter(|resource| resource.method.is_some()) {
+            routes.push(HttpRoute {
+                method: resource.method,
+                path: join_paths(&prefix, &resource.path),
+                handler: resource.handler,
+                line: resource.line,
+                framework: "JAX-RS".to_string(),
+            });
+        }
+    }
+
+    /// `[HttpGet("{id}")]` and `[Route("...")]`, with `[controller]`/`[action]` tokens filled in.
+    /// Attributes on one method combine: `[Route("x")]` gives the path, `[HttpGet]` the method.
+    fn aspnet_routes(&self, call_graph: &CallGraph, file: &ParsedFile, lines: &LineIndex, routes: &mut Vec<HttpRoute>) {
+        struct Action {
+            handler: MethodSignature,
+            prefix: String,
+            template: String,
+            /// `(method, template, line)` of each `[HttpX]` attribute
+            verbs: Vec<(String, String, usize)>,
+            line: usize,
+        }
+
+        let content = &file.source_content;
+        let mut prefix = String::new();
+        let mut actions: Vec<Action> = Vec::new();
+
+        for captures in self.aspnet_attribute.captures_iter(content) {
+            let whole = captures.get(0).expect("match");
+            let template = captures.get(3).map_or("", |template| template.as_str()).to_string();
+            let line = lines.line_of(whole.start());
+
+            if let Some(type_name) = self.annotated_type(content, whole.end()) {
+                if captures.get(2).is_some() {
+                    let controller = type_name.strip_suffix("Controller").unwrap_or(&type_name);
+                    prefix = template.replace("[controller]", controller);
+                }
+                continue;
+            }
+
+            let Some(handler) = next_function(call_graph, file, line) else {
+                continue;
+            };
+            if actions.last().is_none_or(|action| action.handler != handler) {
+                actions.push(Action { handler, prefix: prefix.clone(), template: String::new(), verbs: Vec::new(), line });
+            }
+            let action = actions.last_mut().expect("action");
+            match captures.get(1) {
+                Some(verb) => action.verbs.push((verb.as_str().to_uppercase(), template, line)),
+                None => action.template = template,
+            }
+        }
+
+        for action in actions {
+            let resolve = |template: &str| {
+                let path = if let Some(absolute) = template.strip_prefix('~') {
+                    absolute.to_string()
+                } else if template.starts_with('/') {
+                    template.to_string()
+                } else {
+                    join_paths(&action.prefix, template)
+                };
+                path.replace("[action]", &action.handler.method_name)
+            };
+
+            let framework = "ASP.NET".to_string();
+            if action.verbs.is_empty() {
+                routes.push(HttpRoute {
+                    method: None,
+                    path: resolve(&action.template),
+                    handler: action.handler.clone(),
+                    line: action.line,
+                    framework: framework.clone(),
+                });
+            }
+            for (method, template, line) in &action.verbs {
+                let template = if template.is_empty() { &action.template } else { template };
+                routes.push(HttpRoute {
+                    method: Some(method.clone()),
+                    path: resolve(template),
+                    handler: action.handler.clone(),
+                    line: *line,
+                    framework: framework.clone(),
+                });
+            }
+        }
+    }
+
+    /// Minimal APIs: `app.MapGet("/x", Handler)` with a named handler, under any `MapGroup` prefixes
+    fn minimal_api_routes(&self, call_graph: &CallGraph, file: &ParsedFile, lines: &LineIndex, routes: &mut Vec<HttpRoute>) {
+        let content = &file.source_content;
+
+        let mut prefixes: HashMap<String, String> = HashMap::new();
+        for captures in self.minimal_api_group.captures_iter(content) {
+            let parent = prefixes.get(&captures[2]).cloned().unwrap_or_default();
+            prefixes.insert(captures[1].to_string(), join_paths(&parent, &captures[3]));
+        }
+
+        for captures in self.minimal_api_route.captures_iter(content) {
+            let line = lines.line_of(captures.get(0).expect("match").start());
+            let prefix = prefixes.get(&captures[1]).map_or("", String::as_str);
+            if let Some(handler) = function_named(call_graph, file, &captures[4]) {
+                routes.push(HttpRoute {
+                    method: Some(captures[2].to_uppercase()),
+                    path: join_paths(prefix, &captures[3]),
+                    handler,
+                    line,
+                    framework: "ASP.NET minimal API".to_string(),
+                });
+            }
+        }
+    }
+
+    /// Flask `@app.route("/x", methods=["POST"])` and FastAPI `@app.get("/x")`, under the
+    /// `APIRouter(prefix=...)` or `Blueprint(url_prefix=...)` they are declared on
+    fn python_routes(&self, call_graph: &CallGraph, file: &ParsedFile, lines: &LineIndex, routes: &mut Vec<HttpRoute>) {
+        let content = &file.source_content;
+        let framework = if content.contains("fastapi") { "FastAPI" } else { "Flask" };
+
+        let prefixes: HashMap<&str, String> = self.python_router.captures_iter(content)
+            .filter_map(|captures| {
+                let prefix = self.python_prefix.captures(captures.get(2)?.as_str())?;
+                Some((captures.get(1)?.as_str(), prefix[1].to_string()))
+            })
+            .collect();
+
+        for captures in self.python_decorator.captures_iter(content) {
+            let line = lines.line_of(captures.get(0).expect("match").start());
+            let method = match &captures[2] {
+                "route" => self.python_methods.captures(&captures[4]).map(|method| method[1].to_uppercase()),
+                verb => Some(verb.to_uppercase()),
+            };
+            let path = match prefixes.get(&captures[1]) {
+                Some(prefix) => join_paths(prefix, &captures[3]),
+                None => captures[3].to_string(),
+            };
+            if let Some(handler) = next_function(call_graph, file, line) {
+                routes.push(HttpRoute { method, path, handler, line, framework: framework.to_string() });
+            }
+        }
+    }
+
+    /// Django URLconf entries: `path("orders/<int:id>/", views.detail)` for function views, and
+    /// `OrderView.as_view()` for class-based views, whose `get`/`post`/... methods handle each verb
+    fn django_routes(&self, call_graph: &CallGraph, file: &ParsedFile, lines: &LineIndex, routes: &mut Vec<HttpRoute>) {
+        const VIEW_METHODS: &[&str] = &["get", "post", "put", "patch", "delete"];
+
+        let content = &file.source_content;
+        if !content.contains("urlpatterns") {
+            return;
+        }
+
+        for captures in self.django_route.captures_iter(content) {
+            let line = lines.line_of(captures.get(0).expect("match").start());
+            let path = format!("/{}", captures[1].trim_start_matches('^').trim_end_matches('$').trim_start_matches('/'));
+            let reference = &captures[2];
+
+            match reference.strip_suffix(".as_view") {
+                Some(view) if captures.get(3).is_some() => {
+                    let class = view.rsplit('.').next().unwrap_or(view);
+                    let mut handlers: Vec<&MethodSignature> = call_graph.nodes.keys()
+                        .filter(|signature| signature.class_name.as_deref() == Some(class)
+                            && VIEW_METHODS.contains(&signature.method_name.as_str()))
+                        .collect();
+                    handlers.sort_by_key(|signature| signature.to_unique_string());
+                    for handler in handlers {
+                        routes.push(HttpRoute {
+                            method: Some(handler.method_name.to_uppercase()),
+                            path: path.clone(),
+                            handler: handler.clone(),
+                            line,
+                            framework: "Django".to_string(),
+                        });
+                    }
+                }
+                Some(_) => {}
+                None if captures.get(3).is_none() => {
+                    if let Some(handler) = function_named(call_graph, file, reference) {
+                        routes.push(HttpRoute { method: None, path, handler, line, framework: "Django".to_string() });
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Express- and Koa-style `app.get('/x', auth, handler)` with a named handler
+    fn express_routes(&self, call_graph: &CallGraph, file: &ParsedFile, lines: &LineIndex, routes: &mut Vec<HttpRoute>) {
+        let framework = if file.source_content.contains("koa") { "Koa" } else { "Express" };
+        for captures in self.express_route.captures_iter(&file.source_content) {
+            if !captures[2].starts_with('/') {
+                continue;
+            }
+            let line = lines.line_of(captures.get(0).expect("match").start());
+            let method = Some(captures[1].to_uppercase()).filter(|method| method != "ALL");
+            if let Some(handler) = function_named(call_graph, file, &captures[3]) {
+                routes.push(HttpRoute { method, path: captures[2].to_string(), handler, line, framework: framework.to_string() });
+            }
+        }
+    }
+
+    /// Next.js API routes, routed by file path: the default export of `pages/api/**`, and the
+    /// `GET`/`POST`/... exports of `app/**/route.js`
+    fn nextjs_routes(&self, call_graph: &CallGraph, file: &ParsedFile, lines: &LineIndex, routes: &mut Vec<HttpRoute>) {
+        let directories: Vec<String> = file.path.parent()
+            .map(|parent| parent.components().map(|component| component.as_os_str().to_string_lossy().to_string()).collect())
+            .unwrap_or_default();
+        let stem = file.path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let framework = "Next.js".to_string();
+
+        if let Some(pages) = directories.windows(2).rposition(|pair| pair[0] == "pages" && pair[1] == "api") {
+            let Some(captures) = self.next_default_export.captures(&file.source_content) else {
+                return;
+            };
+            let name = captures.get(1).or_else(|| captures.get(2)).expect("name").as_str();
+            let mut parts: Vec<&str> = directories[pages + 1..].iter().map(String::as_str).collect();
+            if stem != "index" {
+                parts.push(&stem);
+            }
+            if let Some(handler) = function_named(call_graph, file, name) {
+                let line = lines.line_of(captures.get(0).expect("match").start());
+                routes.push(HttpRoute { method: None, path: format!("/{}", parts.join("/")), handler, line, framework });
+            }
+        } else if stem == "route" {
+            let Some(app) = directories.iter().rposition(|directory| directory == "app") else {
+                return;
+            };
+            // `(group)` directories organize routes without appearing in the URL
+            let parts: Vec<&str> = directories[app + 1..].iter()
+                .map(String::as_str)
+                .filter(|part| !(part.starts_with('(') && part.ends_with(')')))
+                .collect();
+            let mut handlers: Vec<&CallNode> = call_graph.nodes.values()
+                .filter(|node| node.signature.file_path == file.path
+                    && ["GET", "POST", "PUT", "PATCH", "DELETE"].contains(&node.signature.method_name.as_str()))
+                .collect();
+            handlers.sort_by_key(|node| node.line_range.0);
+            for node in handlers {
+                routes.push(HttpRoute {
+                    method: Some(node.signature.method_name.clone()),
+                    path: format!("/{}", parts.join("/")),
+                    handler: node.signature.clone(),
+                    line: node.line_range.0,
+                    framework: framework.clone(),
+                });
+            }
+        }
+    }
+
+    /// actix `#[get("/x")]` attributes, `.route("/x", web::get().to(handler))` and
+    /// `web::resource("/x").route(...)`, and axum `.route("/x", get(handler).post(other))`
+    fn rust_routes(&self, call_graph: &CallGraph, file: &ParsedFile, lines: &LineIndex, routes: &mut Vec<HttpRoute>) {
+        let content = &file.source_content;
+        let framework = if content.contains("actix") { "actix" } else { "axum" };
+
+        for captures in self.rust_attribute.captures_iter(content) {
+            let line = lines.line_of(captures.get(0).expect("match").start());
+            if let Some(handler) = next_function(call_graph, file, line) {
+                routes.push(HttpRoute {
+                    method: Some(captures[1].to_uppercase()),
+                    path: captures[2].to_string(),
+                    handler,
+                    line,
+                    framework: "actix".to_string(),
+                });
+            }
+        }
+
+        let declarations = self.axum_route.captures_iter(content).chain(self.actix_resource.captures_iter(content));
+        for captures in declarations {
+            let whole = captures.get(0).expect("match");
+            let line = lines.line_of(whole.start());
+            // Handlers run to the end of the statement or the next chained `.route("/y", ...)`/`resource(...)`
+            let rest = &content[whole.end()..];
+            let end = [rest.find([';', '{', '}']), rest.find(".route(\""), rest.find("resource(")]
+                .into_iter()
+                .flatten()
+                .min()
+                .unwrap_or(rest.len());
+            let handlers = &rest[..end];
+            let by_method = self.axum_handler.captures_iter(handlers).chain(self.actix_handler.captures_iter(handlers));
+            for handler_captures in by_method {
+                let method = Some(handler_captures[1].to_uppercase()).filter(|method| method != "ANY");
+                if let Some(handler) = function_named(call_graph, file, &handler_captures[2]) {
+                    routes.push(HttpRoute { method, path: captures[1].to_string(), handler, line, framework: framework.to_string() });
+                }
+            }
+        }
+    }
+
+    /// fetch/axios, reqwest, RestTemplate/WebClient, requests/httpx and HttpClient calls with a URL literal
+    fn client_calls(&self, file: &ParsedFile) -> Vec<HttpClientCall> {
+        let content = &file.source_content;
+        let lines = LineIndex::new(content);
+
+        self.client_call.captures_iter(content)
+            .filter_map(|captures| {
+                let url = (7..=9).find_map(|group| captures.get(group))?.as_str();
+                if !url.contains('/') {
+                    return None;
+                }
+
+                // Server-side registrations look like client calls (`app.get('/x', ...)`, `@app.get("/x")`)
+                let whole = captures.get(0).expect("match");
+                let line_start = content[..whole.start()].rfind('\n').map_or(0, |newline| newline + 1);
+                let line_end = content[whole.start()..].find('\n').map_or(content.len(), |newline| whole.start() + newline);
+                let line_text = &content[line_start..line_end];
+                if line_text.trim_start().starts_with('@') || self.express_route.is_match(line_text) || self.axum_route.is_match(line_text) {
+                    return None;
+                }
+
+                let verb = (1..=6).find_map(|group| captures.get(group)).map_or("", |verb| verb.as_str());
+                let method = self.client_method_of(verb, content, whole.end());
+
+                Some(HttpClientCall { method, url: url.to_string(), line: lines.line_of(whole.start()) })
+            })
+            .collect()
+    }
+
+    /// HTTP method implied by the client function name, or given in its options
+    fn client_method_of(&self, verb: &str, content: &str, end: usize) -> Option<String> {
+        let lower = verb.to_lowercase();
+        if let Some(method) = ["get", "post", "put", "delete", "patch"].into_iter().find(|method| lower.starts_with(method)) {
+            return Some(method.to_uppercase());
+        }
+
+        // fetch(url, { method: 'POST' }), exchange(url, HttpMethod.PUT, ...), request/SendAsync
+        let mut window_end = (end + CLIENT_OPTIONS_WINDOW).min(content.len());
+        while !content.is_char_boundary(window_end) {
+            window_end -= 1;
+        }
+        let window = &content[end..window_end];
+        let window = window.split(';').next().unwrap_or(window);
+        match self.client_method.captures(window) {
+            Some(method) => Some(method[1].to_uppercase()),
+            None if lower == "fetch" => Some("GET".to_string()),
+            None => None,
+        }
+    }
+
+    /// Name of the type declared right after an annotation, if it annotates a type rather than a method
+    fn annotated_type(&self, content: &str, end: usize) -> Option<String> {
+        for line in content[end..].lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('@') || trimmed.starts_with('[') {
+                continue;
+            }
+            return self.type_declaration.captures(trimmed)
+                .filter(|_| !trimmed.contains('('))
+                .map(|captures| captures[1].to_string());
+        }
+        None
+    }
+}
+
+impl Default for HttpRouteLinker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Maps byte offsets to 1-based line numbers
+struct LineIndex {
+    starts: Vec<usize>,
+}
+
+impl LineIndex {
+    fn new(content: &str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        Self { starts }
+    }
+
+    fn line_of(&self, offset: usize) -> usize {
+        self.starts.partition_point(|&start| start <= offset)
+    }
+}
+
+/// The first function in `file` starting at or after `line`: the one an annotation belongs to
+fn next_function(call_graph: &CallGraph, file: &ParsedFile, line: usize) -> Option<MethodSignature> {
+    call_graph.nodes.values()
+        .filter(|node| node.signature.file_path == file.path && node.line_range.0 >= line)
+        .min_by_key(|node| node.line_range.0)
+        .map(|node| node.signature.clone())
+}
+
+/// A handler referenced by name (`handler`, `orders::list`, `controller.list`),
+/// preferring the file that registers it
+fn function_named(call_graph: &CallGraph, file: &ParsedFile, reference: &str) -> Option<MethodSignature> {
+    let name = reference.rsplit([':', '.']).next().unwrap_or(reference);
+    let mut candidates: Vec<&MethodSignature> = call_graph.nodes.keys()
+        .filter(|signature| signature.method_name == name)
+        .collect();
+    candidates.sort_by_key(|signature| (signature.file_path != file.path, signature.to_unique_string()));
+
+    match candidates.as_slice() {
+        [only] => Some((*only).clone()),
+        [first, second, ..] if first.file_path == file.path && second.file_path != file.path => Some((*first).clone()),
+        _ => None,
+    }
+}
+
+fn first_string_literal(text: &str) -> Option<String> {
+    let start = text.find('"')? + 1;
+    let end = text[start..].find('"')? + start;
+    Some(text[start..end].to_string())
+}
+
+fn join_paths(prefix: &str, path: &str) -> String {
+    match (prefix.trim_matches('/'), path.trim_start_matches('/')) {
+        ("", path) => format!("/{}", path),
+        (prefix, "") => format!("/{}", prefix),
+        (prefix, path) => format!("/{}/{}", prefix, path),
+    }
+}
+
+/// Split a route or URL into segments, dropping the scheme, host, query string
+/// and any leading base-URL placeholder
+fn segments(path: &str) -> Segments {
+    let mut path = path.trim();
+    if let Some(scheme_end) = path.find("://") {
+        let after_host = &path[scheme_end + 3..];
+        path = after_host.find('/').map_or("", |slash| &after_host[slash..]);
+    }
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+
+    let segments: Segments = path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            let is_parameter = segment.starts_with(':')
+                || segment.contains(['{', '}', '<', '>', '$', '[', '%']);
+            (!is_parameter).then(|| segment.to_lowercase())
+        })
+        .collect();
+
+    let leading_placeholders = segments.iter().take_while(|segment| segment.is_none()).count();
+    segments.into_iter().skip(leading_placeholders).collect()
+}
+
+/// Number of literally equal segments, or `None` when the paths cannot be the same route
+fn match_score(client: &Segments, route: &Segments) -> Option<usize> {
+    if client.len() != route.len() {
+        return None;
+    }
+
+    let mut literal_matches = 0;
+    for (requested, served) in client.iter().zip(route) {
+        match (requested, served) {
+            (Some(requested), Some(served)) if requested == served => literal_matches += 1,
+            (Some(_), Some(_)) => return None,
+            _ => {}
+        }
+    }
+
+    (literal_matches > 0).then_some(literal_matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::{method, node, parsed_file};
+
+    #[test]
+    fn test_segments_normalize_parameters() {
+        let route = segments("/api/orders/{id}");
+        assert_eq!(segments("/api/orders/:id"), route);
+        assert_eq!(segments("/api/orders/<int:id>"), route);
+        assert_eq!(segments("${API_BASE}/api/orders/${id}?expand=lines"), route);
+        assert_eq!(segments("https://shop.example.com/api/orders/{}"), route);
+
+        assert_eq!(match_score(&segments("/api/orders/42"), &route), Some(2));
+        assert_eq!(match_score(&segments("/api/customers/42"), &route), None);
+        assert_eq!(match_score(&segments("/api/orders"), &route), None);
+    }
+
+    #[test]
+    fn test_framework_routes() {
+        let files = vec![
+            parsed_file("/p/src/OrderResource.java", "java",
+                 "@Path(\"/orders\")\npublic class OrderResource {\n    @GET\n    @Path(\"{id}\")\n    public Order find(long id) {}\n\n    @POST\n    public Order create(Order order) {}\n}\n"),
+            parsed_file("/p/shop/urls.py", "python",
+                 "urlpatterns = [\n    path(\"carts/<int:id>/\", views.cart_detail),\n    path(\"checkout/\", CheckoutView.as_view()),\n]\n"),
+            parsed_file("/p/shop/api.py", "python",
+                 "from fastapi import APIRouter\nrouter = APIRouter(prefix=\"/items\")\n\n@router.get(\"/{item_id}\")\nasync def read_item(item_id):\n    pass\n"),
+            parsed_file("/p/web/pages/api/invoices/[id].ts", "javascript",
+                 "export default async function handler(req, res) {\n  res.json({});\n}\n"),
+            parsed_file("/p/web/app/(shop)/api/users/route.ts", "javascript",
+                 "export async function GET() {}\nexport async function POST(request) {}\n"),
+            parsed_file("/p/Api/Program.cs", "csharp",
+                 "var payments = app.MapGroup(\"/payments\");\npayments.MapGet(\"/{id}\", GetPayment);\n"),
+            parsed_file("/p/src/app.rs", "rust",
+                 "Router::new()\n    .route(\"/carts\", get(list_carts))\n    .route(\"/carts/:id\", delete(remove_cart));\n"),
+            parsed_file("/p/src/server.rs", "rust",
+                 "use actix_web::web;\ncfg.route(\"/health\", web::get().to(health))\n   .service(web::resource(\"/status\").route(web::post().to(status)));\n"),
+        ];
+
+        let mut graph = CallGraph::new();
+        let nodes = [
+            ("/p/src/OrderResource.java", Some("OrderResource"), "find", 5),
+            ("/p/src/OrderResource.java", Some("OrderResource"), "create", 8),
+            ("/p/shop/views.py", None, "cart_detail", 1),
+            ("/p/shop/views.py", Some("CheckoutView"), "post", 5),
+            ("/p/shop/api.py", None, "read_item", 5),
+            ("/p/web/pages/api/invoices/[id].ts", None, "handler", 1),
+            ("/p/web/app/(shop)/api/users/route.ts", None, "GET", 1),
+            ("/p/web/app/(shop)/api/users/route.ts", None, "POST", 2),
+            ("/p/Api/Handlers.cs", Some("Handlers"), "GetPayment", 3),
+            ("/p/src/app.rs", None, "list_carts", 5),
+            ("/p/src/app.rs", None, "remove_cart", 8),
+            ("/p/src/server.rs", None, "health", 5),
+            ("/p/src/server.rs", None, "status", 9),
+        ];
+        for (path, class, name, line) in nodes {
+            graph.add_node(node(method(path, class, name), (line, line + 2)));
+        }
+
+        let routes: Vec<(String, String, String)> = HttpRouteLinker::new().detect_routes(&graph, &files).into_iter()
+            .map(|route| (route.endpoint(), route.handler.method_name.clone(), route.framework))
+            .collect();
+        let expected = |endpoint: &str, handler: &str, framework: &str| (endpoint.to_string(), handler.to_string(), framework.to_string());
+
+        for route in [
+            expected("GET /orders/{id}", "find", "JAX-RS"),
+            expected("POST /orders", "create", "JAX-RS"),
+            expected("ANY /carts/<int:id>/", "cart_detail", "Django"),
+            expected("POST /checkout/", "post", "Django"),
+            expected("GET /items/{item_id}", "read_item", "FastAPI"),
+            expected("ANY /api/invoices/[id]", "handler", "Next.js"),
+            expected("GET /api/users", "GET", "Next.js"),
+            expected("POST /api/users", "POST", "Next.js"),
+            expected("GET /payments/{id}", "GetPayment", "ASP.NET minimal API"),
+            expected("GET /carts", "list_carts", "axum"),
+            expected("DELETE /carts/:id", "remove_cart", "axum"),
+            expected("GET /health", "health", "actix"),
+            expected("POST /status", "status", "actix"),
+        ] {
+            assert!(routes.contains(&route), "missing {:?} in {:?}", route, routes);
+        }
+        assert_eq!(routes.len(), 13);
+    }
+
+    #[test]
+    fn test_client_calls() {
+        let linker = HttpRouteLinker::new();
+        let file = |language: &str, source: &str| ParsedFile {
+            path: "/p/src/web/client".into(),
+            language: language.to_string(),
+            content_hash: String::new(),
+            modified_time: std::time::SystemTime::UNIX_EPOCH,
+            modules: Vec::new(),
+            file_docs: None,
+            source_content: source.to_string(),
+            calls: Vec::new(),
+        };
+        let found = |language: &str, source: &str| -> Vec<(Option<String>, String)> {
+            linker.client_calls(&file(language, source)).into_iter().map(|call| (call.method, call.url)).collect()
+        };
+        let get = |url: &str| vec![(Some("GET".to_string()), url.to_string())];
+
+        assert_eq!(found("javascript", "const r = await fetch(`/api/orders/${id}`);"), get("/api/orders/${id}"));
+        assert_eq!(found("javascript", "fetch('/api/orders', { method: 'POST', body })"),
+                   vec![(Some("POST".to_string()), "/api/orders".to_string())]);
+        assert_eq!(found("rust", r#"client.get(&format!("{}/api/orders/{}", base, id)).send()"#), get("{}/api/orders/{}"));
+        assert_eq!(found("java", r#"rest.getForObject("/api/orders/{id}", Order.class, id)"#), get("/api/orders/{id}"));
+        assert_eq!(found("python", r#"requests.get(f"{BASE}/api/orders/{order_id}")"#), get("{BASE}/api/orders/{order_id}"));
+        assert_eq!(found("csharp", r#"await http.GetFromJsonAsync<Order>($"api/orders/{id}")"#), get("api/orders/{id}"));
+        assert!(found("rust", r#"cache.get("key")"#).is_empty());
+    }
+}
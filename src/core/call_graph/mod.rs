@@ -6,22 +6,51 @@
 //! documentation.
 
 mod call_graph;
+mod call_resolver;
+mod type_hierarchy;
+mod recursive_clusters;
+mod http_routes;
 mod entry_point_detector;
 mod call_chain_tracer;
 mod call_chain_grouper;
 mod call_chain_analyzer;
+mod synthesis;
+mod sequence_diagram;
 mod call_chain_engine;
+mod example_miner;
+mod test_coverage;
+mod dead_code;
+mod side_effects;
+mod failure_modes;
+mod concurrency;
+mod centrality;
+mod graph_export;
+mod graph_query;
+mod graph_diff;
+mod analysis_store;
+#[cfg(test)]
+mod test_support;
 
-pub use call_graph::{CallGraph, CallNode, CallEdge, MethodSignature, CallType, CallGraphStats};
-pub use entry_point_detector::{EntryPointDetector, EntryPoint, EntryPointType};
-pub use call_chain_tracer::{CallChainTracer, CallChain, CallStep};
-pub use call_chain_grouper::{CallChainGrouper, CallChainGroup, GroupingStats};
-pub use call_chain_analyzer::{
-    CallChainAnalyzer, GroupAnalysis, VisitedSet, ComponentInteraction,
-    DomainInsight, Gotcha, GotchaSeverity, SystemSynthesis
+pub use call_graph::{
+    CallGraph, CallNode, CallEdge, MethodSignature, CallType,
+    package_name_for_path
 };
-pub use call_chain_engine::{CallChainEngine, CallChainAnalysisResult, AnalysisStatistics};
-
-// Re-export needed types from other modules for internal use
-pub use super::llm::{LlmDocumenter, EnhancementRequest, EnhancementType, DocumentationContext, ProjectInfo};
-pub use super::{ParsedFile, CodeParser};
\ No newline at end of file
+pub use recursive_clusters::RecursiveCluster;
+pub use http_routes::HttpRoute;
+pub use entry_point_detector::{EntryPointDetector, EntryPoint, EntryPointType};
+pub use call_chain_tracer::{CallChainTracer, CallChain};
+pub use call_chain_grouper::{CallChainGrouper, CallChainGroup};
+pub use call_chain_analyzer::{CallChainAnalyzer, GroupAnalysis, RankedGotcha, SystemSynthesis};
+pub use sequence_diagram::SequenceDiagram;
+pub use call_chain_engine::{CallChainEngine, CallChainAnalysisResult};
+pub use example_miner::{ExampleMiner, UsageExample};
+pub use test_coverage::{TestCoverageAnalyzer, TestCoverage};
+pub use centrality::{CentralityAnalyzer, Centrality};
+pub use side_effects::{SideEffectAnalyzer, SideEffects, describe_effects};
+pub use failure_modes::{FailureModeAnalyzer, FailureModes};
+pub use concurrency::{ConcurrencyAnalyzer, Concurrency, locks_held_across_await};
+pub use dead_code::{DeadCodeAnalyzer, DeadCodeReport, DeadCodeKind, DeadCodeConfidence};
+pub use graph_export::{GraphExport, GraphFilter, ExportFormat, Granularity};
+pub use graph_query::{GraphQuery, GraphQueryRequest};
+pub use graph_diff::{GraphDiff, GraphSnapshot};
+pub use analysis_store::AnalysisStore;
\ No newline at end of file
@@ -0,0 +1,173 @@
+// src/core/call_graph/recursive_clusters.rs
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+use serde::{Serialize, Deserialize};
+
+use super::{CallEdge, MethodSignature};
+
+/// A set of methods that can (indirectly) call each other: a strongly connected
+/// component with more than one member, or a single directly recursive method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecursiveCluster {
+    /// Methods in the cluster, sorted by their unique string
+    pub members: Vec<MethodSignature>,
+    /// Calls from outside the cluster into one of its members
+    pub entry_edges: Vec<CallEdge>,
+    /// Files the members live in
+    pub files: BTreeSet<PathBuf>,
+}
+
+impl RecursiveCluster {
+    /// Stable identity of the cluster, independent of member order
+    pub fn key(&self) -> String {
+        self.members.iter()
+            .map(|member| member.to_unique_string())
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    /// Packages the members belong to
+    pub fn packages(&self) -> BTreeSet<String> {
+        self.members.iter().map(|member| member.package_name()).collect()
+    }
+
+    /// Whether the recursion spans more than one package
+    pub fn crosses_packages(&self) -> bool {
+        self.packages().len() > 1
+    }
+}
+
+/// Find all recursive clusters with an iterative version of Tarjan's algorithm,
+/// so deep call graphs cannot overflow the stack
+pub fn find_recursive_clusters<'a>(
+    nodes: impl IntoIterator<Item = &'a MethodSignature>,
+    edges: &[CallEdge],
+) -> Vec<RecursiveCluster> {
+    let mut methods: Vec<&MethodSignature> = nodes.into_iter().collect();
+    for edge in edges {
+        methods.push(&edge.caller);
+        methods.push(&edge.callee);
+    }
+    methods.sort_by_cached_key(|method| method.to_unique_string());
+    methods.dedup();
+
+    let index_of: HashMap<&MethodSignature, usize> = methods.iter()
+        .enumerate()
+        .map(|(index, method)| (*method, index))
+        .collect();
+
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); methods.len()];
+    for edge in edges {
+        successors[index_of[&edge.caller]].push(index_of[&edge.callee]);
+    }
+    for targets in &mut successors {
+        targets.sort_unstable();
+        targets.dedup();
+    }
+
+    let components = strongly_connected_components(&successors);
+
+    let mut clusters: Vec<RecursiveCluster> = components.into_iter()
+        .filter(|component| component.len() > 1 || successors[component[0]].contains(&component[0]))
+        .map(|component| {
+            let members: Vec<MethodSignature> = component.iter().map(|&index| methods[index].clone()).collect();
+            let member_set: BTreeSet<usize> = component.iter().copied().collect();
+            let entry_edges = edges.iter()
+                .filter(|edge| member_set.contains(&index_of[&edge.callee]) && !member_set.contains(&index_of[&edge.caller]))
+                .cloned()
+                .collect();
+            let files = members.iter().map(|member| member.file_path.clone()).collect();
+
+            RecursiveCluster { members, entry_edges, files }
+        })
+        .collect();
+
+    clusters.sort_by_key(|cluster| cluster.key());
+    clusters
+}
+
+/// Tarjan's algorithm with an explicit call stack; each component is returned sorted
+fn strongly_connected_components(successors: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+
+    let count = successors.len();
+    let mut index = vec![UNVISITED; count];
+    let mut low_link = vec![0; count];
+    let mut on_stack = vec![false; count];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+    let mut next_index = 0;
+
+    for root in 0..count {
+        if index[root] != UNVISITED {
+            continue;
+        }
+
+        // (node, position of the next successor to look at)
+        let mut work = vec![(root, 0)];
+        while let Some(&mut (node, ref mut next)) = work.last_mut() {
+            if *next == 0 {
+                index[node] = next_index;
+                low_link[node] = next_index;
+                next_index += 1;
+                stack.push(node);
+                on_stack[node] = true;
+            }
+
+            if let Some(&successor) = successors[node].get(*next) {
+                *next += 1;
+                if index[successor] == UNVISITED {
+                    work.push((successor, 0));
+                } else if on_stack[successor] {
+                    low_link[node] = low_link[node].min(index[successor]);
+                }
+                continue;
+            }
+
+            work.pop();
+            if let Some(&(parent, _)) = work.last() {
+                low_link[parent] = low_link[parent].min(low_link[node]);
+            }
+
+            if low_link[node] == index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                component.sort_unstable();
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strongly_connected_components() {
+        // 0 -> 1 -> 2 -> 0, 2 -> 3, 3 -> 3, 4 alone
+        let successors = vec![vec![1], vec![2], vec![0, 3], vec![3], vec![]];
+        let mut components = strongly_connected_components(&successors);
+        components.sort();
+
+        assert_eq!(components, vec![vec![0, 1, 2], vec![3], vec![4]]);
+    }
+
+    #[test]
+    fn test_deep_chain_does_not_overflow() {
+        let count = 200_000;
+        let successors: Vec<Vec<usize>> = (0..count).map(|node| vec![(node + 1) % count]).collect();
+        let components = strongly_connected_components(&successors);
+
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].len(), count);
+    }
+}
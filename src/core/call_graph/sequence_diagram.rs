@@ -0,0 +1,236 @@
+// src/core/call_graph/sequence_diagram.rs
+use std::collections::HashMap;
+
+use crate::config::AnalysisConfig;
+use super::{CallChain, CallType, MethodSignature};
+
+/// Renders traced call chains as Mermaid sequence diagrams
+pub struct SequenceDiagram {
+    /// Most calls drawn in one diagram; deeper calls are collapsed into notes beyond it
+    max_messages: usize,
+}
+
+/// A call whose message (and block, if any) is still open while its callees are drawn
+struct OpenCall {
+    depth: usize,
+    participant: usize,
+    /// Whether the call opened a `loop`/`opt`/`critical` block that needs an `end`
+    block: bool,
+}
+
+impl SequenceDiagram {
+    pub fn new(config: &AnalysisConfig) -> Self {
+        Self {
+            max_messages: config.max_sequence_messages.max(1),
+        }
+    }
+
+    /// A `sequenceDiagram` with a participant per class (or module) and a message per call
+    pub fn render(&self, chain: &CallChain) -> String {
+        let Some(entry) = chain.steps.first() else {
+            return String::new();
+        };
+        let max_depth = self.depth_limit(chain);
+
+        let mut participants: Vec<String> = Vec::new();
+        let mut participant_ids: HashMap<String, usize> = HashMap::new();
+        for step in chain.steps.iter().filter(|step| step.depth <= max_depth) {
+            let name = participant_name(&step.method);
+            if !participant_ids.contains_key(&name) {
+                participant_ids.insert(name.clone(), participants.len());
+                participants.push(name);
+            }
+        }
+
+        let mut out = String::from("sequenceDiagram\n    actor Caller\n");
+        for (id, name) in participants.iter().enumerate() {
+            out.push_str(&format!("    participant p{} as {}\n", id, escape(name)));
+        }
+
+        let entry_participant = participant_ids[&participant_name(&entry.method)];
+        out.push_str(&format!("    Caller->>p{}: {}()\n", entry_participant, escape(&entry.method.method_name)));
+
+        let mut open: Vec<OpenCall> = vec![OpenCall { depth: 0, participant: entry_participant, block: false }];
+        let mut messages = 0;
+        let mut collapsed = 0;
+        let mut omitted = 0;
+
+        for step in chain.steps.iter().skip(1) {
+            if step.depth > max_depth {
+                collapsed += 1;
+                continue;
+            }
+            if messages >= self.max_messages {
+                omitted += 1;
+                continue;
+            }
+
+            close_calls(&mut out, &mut open, step.depth, &mut collapsed);
+
+            let caller = open.last().map(|call| call.participant).unwrap_or(entry_participant);
+            let callee = participant_ids[&participant_name(&step.method)];
+            let indent = "    ".repeat(1 + open.iter().filter(|call| call.block).count());
+
+            let block = match step.call_type {
+                CallType::Loop => Some(format!("loop line {}", step.call_site_line)),
+                CallType::Conditional => Some(format!("opt conditional, line {}", step.call_site_line)),
+                CallType::Try => Some(format!("critical line {} may fail", step.call_site_line)),
+                _ => None,
+            };
+            let (arrow, marker) = match step.call_type {
+                CallType::Async => ("-)", " (async)"),
+                CallType::Callback => ("-->>", " (callback)"),
+                CallType::Dynamic => ("-->>", " (dispatch)"),
+                CallType::CrossService => ("-)", " (HTTP)"),
+                _ => ("->>", ""),
+            };
+
+            let message_indent = match &block {
+                Some(label) => {
+                    out.push_str(&format!("{}{}\n", indent, label));
+                    format!("{}    ", indent)
+                }
+                None => indent,
+            };
+            out.push_str(&format!("{}p{}{}p{}: {}(){}\n",
+                                  message_indent, caller, arrow, callee, escape(&step.method.method_name), marker));
+
+            open.push(OpenCall { depth: step.depth, participant: callee, block: block.is_some() });
+            messages += 1;
+        }
+
+        close_calls(&mut out, &mut open, 0, &mut collapsed);
+
+        if omitted > 0 {
+            out.push_str(&format!("    Note over p{}: {} more call(s) not shown\n", entry_participant, omitted));
+        } else if chain.truncated {
+            out.push_str(&format!("    Note over p{}: trace cut short by depth or fan-out limits\n", entry_participant));
+        }
+
+        out
+    }
+
+    /// Deepest level that fits within the message limit; calls below it are collapsed
+    fn depth_limit(&self, chain: &CallChain) -> usize {
+        let mut per_depth: Vec<usize> = Vec::new();
+        for step in chain.steps.iter().skip(1) {
+            if per_depth.len() < step.depth {
+                per_depth.resize(step.depth, 0);
+            }
+            per_depth[step.depth - 1] += 1;
+        }
+
+        let mut total = 0;
+        for (level, count) in per_depth.iter().enumerate() {
+            total += count;
+            if total > self.max_messages {
+                return level.max(1);
+            }
+        }
+        per_depth.len()
+    }
+}
+
+/// Close the calls at `depth` or deeper, noting any collapsed callees on the innermost one
+fn close_calls(out: &mut String, open: &mut Vec<OpenCall>, depth: usize, collapsed: &mut usize) {
+    while let Some(call) = open.last() {
+        if call.depth < depth || (depth == 0 && open.len() == 1) {
+            break;
+        }
+        let indent = "    ".repeat(open.iter().filter(|call| call.block).count());
+        if *collapsed > 0 {
+            out.push_str(&format!("    {}Note over p{}: {} deeper call(s) collapsed\n", indent, call.participant, collapsed));
+            *collapsed = 0;
+        }
+        if call.block {
+            out.push_str(&format!("{}end\n", indent));
+        }
+        open.pop();
+    }
+}
+
+/// The class a method belongs to, or else its module (file name)
+fn participant_name(method: &MethodSignature) -> String {
+    method.class_name.clone().unwrap_or_else(|| {
+        method.file_path.file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| method.package_name())
+    })
+}
+
+/// Mermaid treats `;` and `#` specially in sequence diagram text
+fn escape(text: &str) -> String {
+    text.replace('#', "#35;").replace(';', "#59;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::core::call_graph::{EntryPoint, EntryPointType};
+    use crate::core::call_graph::call_chain_tracer::CallStep;
+
+    fn method(class: Option<&str>, name: &str) -> MethodSignature {
+        MethodSignature::new(PathBuf::from("/p/src/orders/service.rs"), name.to_string(),
+                             class.map(str::to_string), None, String::new())
+    }
+
+    fn chain(steps: &[(usize, Option<&str>, &str, CallType)]) -> CallChain {
+        CallChain {
+            entry_point: EntryPoint {
+                signature: method(steps[0].1, steps[0].2),
+                entry_type: EntryPointType::PublicApi,
+                confidence: 0.8,
+                reasoning: String::new(),
+                routes: vec![],
+            },
+            steps: steps.iter().enumerate().map(|(line, (depth, class, name, call_type))| CallStep {
+                method: method(*class, name),
+                depth: *depth,
+                call_site_line: line,
+                call_type: call_type.clone(),
+                callees: vec![],
+            }).collect(),
+            involved_files: HashSet::new(),
+            has_cycles: false,
+            complexity_score: 0,
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn test_renders_calls_with_blocks() {
+        let chain = chain(&[
+            (0, Some("OrderService"), "place", CallType::Direct),
+            (1, Some("Store"), "save", CallType::Loop),
+            (2, None, "encode", CallType::Direct),
+            (1, Some("Mailer"), "send", CallType::Async),
+        ]);
+        let diagram = SequenceDiagram::new(&AnalysisConfig::default()).render(&chain);
+
+        assert_eq!(diagram, "sequenceDiagram\n    actor Caller\n\
+            \x20   participant p0 as OrderService\n    participant p1 as Store\n\
+            \x20   participant p2 as service\n    participant p3 as Mailer\n\
+            \x20   Caller->>p0: place()\n\
+            \x20   loop line 1\n        p0->>p1: save()\n        p1->>p2: encode()\n    end\n\
+            \x20   p0-)p3: send() (async)\n");
+    }
+
+    #[test]
+    fn test_collapses_deep_calls_over_the_limit() {
+        let chain = chain(&[
+            (0, Some("A"), "run", CallType::Direct),
+            (1, Some("B"), "step", CallType::Direct),
+            (2, Some("C"), "inner", CallType::Direct),
+            (2, Some("C"), "other", CallType::Direct),
+            (1, Some("B"), "finish", CallType::Direct),
+        ]);
+        let config = AnalysisConfig { max_sequence_messages: 3, ..AnalysisConfig::default() };
+        let diagram = SequenceDiagram::new(&config).render(&chain);
+
+        assert!(!diagram.contains("inner()"));
+        assert!(diagram.contains("    p0->>p1: step()\n    Note over p1: 2 deeper call(s) collapsed\n    p0->>p1: finish()\n"));
+    }
+}
@@ -0,0 +1,540 @@
+// src/core/call_graph/side_effects.rs
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+use serde::{Serialize, Deserialize};
+
+use super::super::ParsedFile;
+use super::call_graph::{signature_map, UnresolvedCall};
+use super::{CallChainGroup, CallGraph, MethodSignature};
+
+/// What kind of outside resource a call touches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum EffectKind {
+    Database,
+    Messaging,
+    Network,
+    Filesystem,
+    Process,
+    Environment,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum EffectAccess {
+    Read,
+    Write,
+}
+
+/// One kind of I/O, e.g. "writes to Postgres"
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SideEffect {
+    pub kind: EffectKind,
+    pub access: EffectAccess,
+    /// The system touched (Postgres, Kafka, HTTP), when the imports tell
+    pub system: Option<String>,
+}
+
+/// A library call that performs a side effect
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SideEffectSite {
+    pub effect: SideEffect,
+    /// The call as written, e.g. `fs::write`
+    pub call: String,
+    pub line: usize,
+}
+
+/// I/O each function performs itself, and everything it can reach performs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SideEffects {
+    /// Side-effecting library calls made directly in each function
+    #[serde(with = "signature_map")]
+    pub direct: HashMap<MethodSignature, Vec<SideEffectSite>>,
+    /// Side effects of each function and all functions it (transitively) calls
+    #[serde(with = "signature_map")]
+    pub reachable: HashMap<MethodSignature, BTreeSet<SideEffect>>,
+}
+
+/// How to tell a read from a write beyond the rule's default
+#[derive(Clone, Copy)]
+enum Refine {
+    None,
+    /// SQL keywords in the statement at the call site
+    Sql,
+    /// `open(path, "w")` style mode arguments
+    OpenMode,
+}
+
+/// A library API that performs I/O
+struct EffectRule {
+    /// Languages the rule applies to (all when empty)
+    languages: &'static [&'static str],
+    /// Last segment of the receiver (`fs` in `std::fs`, `pool` in `self.pool`), lower-cased;
+    /// a receiver whose last segment is one of them matches, and an empty list matches calls without a receiver
+    receivers: &'static [&'static str],
+    /// Called names, lower-cased
+    callees: &'static [&'static str],
+    kind: EffectKind,
+    access: EffectAccess,
+    /// System touched regardless of imports
+    system: Option<&'static str>,
+    /// Text the file must contain, for calls too generic to trust otherwise
+    requires: Option<&'static str>,
+    refine: Refine,
+}
+
+const fn rule(
+    languages: &'static [&'static str],
+    receivers: &'static [&'static str],
+    callees: &'static [&'static str],
+    kind: EffectKind,
+    access: EffectAccess,
+) -> EffectRule {
+    EffectRule { languages, receivers, callees, kind, access, system: None, requires: None, refine: Refine::None }
+}
+
+impl EffectRule {
+    const fn system(self, system: &'static str) -> Self {
+        Self { system: Some(system), ..self }
+    }
+
+    const fn requires(self, text: &'static str) -> Self {
+        Self { requires: Some(text), ..self }
+    }
+
+    const fn refine(self, refine: Refine) -> Self {
+        Self { refine, ..self }
+    }
+}
+
+use EffectAccess::{Read, Write};
+use EffectKind::{Database, Environment, Filesystem, Messaging, Network, Process};
+
+const RUST: &[&str] = &["rust"];
+const JAVA: &[&str] = &["java"];
+const PYTHON: &[&str] = &["python"];
+const CSHARP: &[&str] = &["csharp"];
+const JAVASCRIPT: &[&str] = &["javascript"];
+const ANY: &[&str] = &[];
+/// Matches calls on any receiver, but not calls without one
+const ANY_RECEIVER: &[&str] = &[""];
+
+const SQL_RECEIVERS: &[&str] = &["pool", "conn", "connection", "db", "tx", "transaction", "executor", "cursor"];
+const HTTP_VERBS: &[&str] = &["get", "post", "put", "delete", "patch", "head", "request"];
+
+const RULES: &[EffectRule] = &[
+    // Rust
+    rule(RUST, &["fs"], &["read", "read_to_string", "read_dir", "metadata", "canonicalize", "read_link"], Filesystem, Read),
+    rule(RUST, &["fs"], &["write", "create_dir", "create_dir_all", "remove_file", "remove_dir", "remove_dir_all", "rename", "copy", "hard_link"], Filesystem, Write),
+    rule(RUST, &["file"], &["open"], Filesystem, Read),
+    rule(RUST, &["file"], &["create", "create_new"], Filesystem, Write),
+    rule(RUST, &["env"], &["var", "var_os", "vars"], Environment, Read),
+    rule(RUST, &["env"], &["set_var", "remove_var"], Environment, Write),
+    rule(RUST, &["command"], &["new"], Process, Write),
+    rule(RUST, &["reqwest"], &["get"], Network, Write).system("HTTP"),
+    rule(RUST, &["client", "http_client"], HTTP_VERBS, Network, Write).system("HTTP").requires("reqwest"),
+    rule(RUST, &["tcpstream", "udpsocket", "tcplistener"], &["connect", "bind"], Network, Write),
+    rule(RUST, &["sqlx"], &["query", "query_as", "query_scalar"], Database, Read).refine(Refine::Sql),
+    rule(RUST, SQL_RECEIVERS, &["fetch_one", "fetch_all", "fetch_optional", "query", "query_one", "query_opt", "prepare"], Database, Read).refine(Refine::Sql),
+    rule(RUST, SQL_RECEIVERS, &["execute", "batch_execute", "commit"], Database, Write).refine(Refine::Sql),
+    rule(RUST, &["producer", "publisher"], &["send", "send_result", "produce", "publish"], Messaging, Write),
+    rule(RUST, &["channel"], &["basic_publish"], Messaging, Write),
+    rule(RUST, &["consumer", "subscriber"], &["recv", "poll", "subscribe", "stream"], Messaging, Read),
+    // Java
+    rule(JAVA, &["files"], &["readallbytes", "readalllines", "readstring", "lines", "newbufferedreader", "newinputstream", "list", "walk"], Filesystem, Read),
+    rule(JAVA, &["files"], &["write", "writestring", "delete", "deleteifexists", "createdirectories", "createfile", "copy", "move", "newbufferedwriter", "newoutputstream"], Filesystem, Write),
+    rule(JAVA, &["system"], &["getenv"], Environment, Read),
+    rule(JAVA, &["getruntime", "runtime"], &["exec"], Process, Write),
+    rule(JAVA, &["processbuilder", "builder"], &["start"], Process, Write),
+    rule(JAVA, &["resttemplate"], &["getforobject", "getforentity", "postforobject", "postforentity", "exchange", "put", "delete", "patchforobject"], Network, Write).system("HTTP"),
+    rule(JAVA, &["webclient", "httpclient"], &["get", "post", "put", "delete", "patch", "send", "sendasync"], Network, Write).system("HTTP"),
+    rule(JAVA, &["jdbctemplate"], &["query", "queryforobject", "queryforlist", "queryformap"], Database, Read),
+    rule(JAVA, &["jdbctemplate"], &["update", "batchupdate", "execute"], Database, Write),
+    rule(JAVA, &["statement", "stmt"], &["executequery"], Database, Read),
+    rule(JAVA, &["statement", "stmt"], &["executeupdate", "execute", "executebatch"], Database, Write).refine(Refine::Sql),
+    rule(JAVA, &["entitymanager"], &["find", "createquery", "createnativequery"], Database, Read),
+    rule(JAVA, &["entitymanager"], &["persist", "merge", "remove", "flush"], Database, Write),
+    rule(JAVA, &["repository", "repo"], &["findall", "findbyid", "existsbyid", "count"], Database, Read),
+    rule(JAVA, &["repository", "repo"], &["save", "saveall", "saveandflush", "delete", "deleteall", "deletebyid"], Database, Write),
+    rule(JAVA, &["kafkatemplate"], &["send", "senddefault"], Messaging, Write).system("Kafka"),
+    rule(JAVA, &["rabbittemplate", "amqptemplate"], &["convertandsend", "send"], Messaging, Write).system("RabbitMQ"),
+    rule(JAVA, &["jmstemplate"], &["convertandsend", "send"], Messaging, Write).system("JMS"),
+    rule(JAVA, &["producer"], &["send"], Messaging, Write),
+    rule(JAVA, &["consumer"], &["poll", "receive"], Messaging, Read),
+    // Python
+    rule(PYTHON, &[], &["open"], Filesystem, Read).refine(Refine::OpenMode),
+    rule(PYTHON, &["os"], &["listdir", "scandir", "walk", "stat"], Filesystem, Read),
+    rule(PYTHON, &["os"], &["remove", "unlink", "makedirs", "mkdir", "rename", "replace", "rmdir"], Filesystem, Write),
+    rule(PYTHON, &["shutil"], &["copy", "copyfile", "copytree", "move", "rmtree"], Filesystem, Write),
+    rule(PYTHON, ANY_RECEIVER, &["read_text", "read_bytes"], Filesystem, Read),
+    rule(PYTHON, ANY_RECEIVER, &["write_text", "write_bytes", "mkdir", "unlink", "touch"], Filesystem, Write).requires("pathlib"),
+    rule(PYTHON, &["os", "environ"], &["getenv", "get"], Environment, Read),
+    rule(PYTHON, &["os"], &["putenv"], Environment, Write),
+    rule(PYTHON, &["subprocess"], &["run", "call", "check_call", "check_output", "popen"], Process, Write),
+    rule(PYTHON, &["os"], &["system", "popen", "execv", "execvp"], Process, Write),
+    rule(PYTHON, &["requests", "httpx", "session", "client", "aiohttp"], HTTP_VERBS, Network, Write).system("HTTP").requires("requests"),
+    rule(PYTHON, &["httpx", "client"], HTTP_VERBS, Network, Write).system("HTTP").requires("httpx"),
+    rule(PYTHON, &["request"], &["urlopen"], Network, Write).system("HTTP"),
+    rule(PYTHON, &["socket", "sock"], &["connect", "bind"], Network, Write),
+    rule(PYTHON, &["cursor", "cur", "conn", "connection", "db", "engine", "session"], &["execute", "executemany"], Database, Read).refine(Refine::Sql),
+    rule(PYTHON, &["cursor", "cur"], &["fetchone", "fetchall", "fetchmany"], Database, Read),
+    rule(PYTHON, &["conn", "connection", "db", "session"], &["commit"], Database, Write),
+    rule(PYTHON, &["session", "db"], &["add", "add_all", "delete", "merge", "flush"], Database, Write).requires("sqlalchemy"),
+    rule(PYTHON, &["session", "db"], &["query", "get", "scalars", "scalar"], Database, Read).requires("sqlalchemy"),
+    rule(PYTHON, &["producer"], &["send", "produce"], Messaging, Write),
+    rule(PYTHON, &["consumer"], &["poll", "consume"], Messaging, Read),
+    rule(PYTHON, &["channel"], &["basic_publish"], Messaging, Write),
+    rule(PYTHON, &["channel"], &["basic_consume", "basic_get"], Messaging, Read),
+    rule(PYTHON, ANY_RECEIVER, &["send_message", "send_message_batch"], Messaging, Write).requires("boto3"),
+    rule(PYTHON, ANY_RECEIVER, &["receive_message"], Messaging, Read).requires("boto3"),
+    // C#
+    rule(CSHARP, &["file"], &["readalltext", "readalllines", "readallbytes", "readalltextasync", "readalllinesasync", "readallbytesasync", "openread", "opentext"], Filesystem, Read),
+    rule(CSHARP, &["file"], &["writealltext", "writealllines", "writeallbytes", "writealltextasync", "writealllinesasync", "writeallbytesasync", "appendalltext", "appendalltextasync", "delete", "move", "copy", "create", "openwrite"], Filesystem, Write),
+    rule(CSHARP, &["directory"], &["getfiles", "getdirectories", "enumeratefiles"], Filesystem, Read),
+    rule(CSHARP, &["directory"], &["createdirectory", "delete", "move"], Filesystem, Write),
+    rule(CSHARP, &["environment"], &["getenvironmentvariable", "getenvironmentvariables"], Environment, Read),
+    rule(CSHARP, &["environment"], &["setenvironmentvariable"], Environment, Write),
+    rule(CSHARP, &["process"], &["start"], Process, Write),
+    rule(CSHARP, &["httpclient", "client"], &["getasync", "postasync", "putasync", "deleteasync", "patchasync", "sendasync", "getstringasync", "getstreamasync", "getbytearrayasync", "getfromjsonasync", "postasjsonasync", "putasjsonasync"], Network, Write).system("HTTP"),
+    rule(CSHARP, &["command", "cmd"], &["executereader", "executereaderasync", "executescalar", "executescalarasync"], Database, Read),
+    rule(CSHARP, &["command", "cmd"], &["executenonquery", "executenonqueryasync"], Database, Write).refine(Refine::Sql),
+    rule(CSHARP, &["connection", "conn", "db"], &["query", "queryasync", "queryfirst", "queryfirstasync", "queryfirstordefault", "queryfirstordefaultasync", "querysingle", "querysingleasync"], Database, Read),
+    rule(CSHARP, &["connection", "conn", "db"], &["execute", "executeasync"], Database, Write).refine(Refine::Sql),
+    rule(CSHARP, &["context", "db"], &["savechanges", "savechangesasync"], Database, Write),
+    rule(CSHARP, &["producer"], &["produce", "produceasync"], Messaging, Write),
+    rule(CSHARP, &["sender"], &["sendmessageasync", "sendmessagesasync"], Messaging, Write),
+    rule(CSHARP, &["channel"], &["basicpublish", "basicpublishasync"], Messaging, Write),
+    rule(CSHARP, &["consumer", "receiver"], &["consume", "receivemessageasync", "receivemessagesasync"], Messaging, Read),
+    // JavaScript
+    rule(JAVASCRIPT, &["fs", "promises", "fsp"], &["readfile", "readfilesync", "readdir", "readdirsync", "stat", "statsync", "createreadstream"], Filesystem, Read),
+    rule(JAVASCRIPT, &["fs", "promises", "fsp"], &["writefile", "writefilesync", "appendfile", "appendfilesync", "mkdir", "mkdirsync", "unlink", "unlinksync", "rm", "rmsync", "rename", "renamesync", "createwritestream"], Filesystem, Write),
+    rule(JAVASCRIPT, &["child_process", "cp"], &["exec", "execsync", "execfile", "execfilesync", "spawn", "spawnsync", "fork"], Process, Write),
+    rule(JAVASCRIPT, &[], &["exec", "execsync", "execfile", "execfilesync", "spawn", "spawnsync", "fork"], Process, Write).requires("child_process"),
+    rule(JAVASCRIPT, &[], &["fetch"], Network, Write).system("HTTP"),
+    rule(JAVASCRIPT, &["axios", "http", "https", "got", "superagent", "ky"], HTTP_VERBS, Network, Write).system("HTTP"),
+    rule(JAVASCRIPT, &["pool", "client", "db", "connection", "conn", "knex", "sequelize"], &["query", "execute", "raw"], Database, Read).refine(Refine::Sql),
+    rule(JAVASCRIPT, &["producer"], &["send", "sendbatch"], Messaging, Write),
+    rule(JAVASCRIPT, &["channel"], &["publish", "sendtoqueue"], Messaging, Write),
+    rule(JAVASCRIPT, &["channel"], &["consume", "get"], Messaging, Read),
+    rule(JAVASCRIPT, &["consumer"], &["run", "subscribe"], Messaging, Read),
+    // Any language
+    rule(ANY, &["collection", "coll"], &["find", "findone", "find_one", "aggregate", "count_documents", "countdocuments"], Database, Read).system("MongoDB"),
+    rule(ANY, &["collection", "coll"], &["insertone", "insert_one", "insertmany", "insert_many", "updateone", "update_one", "updatemany", "update_many", "deleteone", "delete_one", "deletemany", "delete_many", "replaceone", "replace_one"], Database, Write).system("MongoDB"),
+    rule(ANY, &["redis", "cache"], &["get", "hget", "hgetall", "mget", "lrange", "smembers", "exists"], Database, Read).system("Redis").requires("redis"),
+    rule(ANY, &["redis", "cache"], &["set", "setex", "hset", "mset", "lpush", "rpush", "sadd", "del", "incr", "expire"], Database, Write).system("Redis").requires("redis"),
+];
+
+/// Environment reads that are not calls (`process.env.PORT`, `os.environ["PORT"]`)
+const ENVIRONMENT_ACCESS: &[(&str, &str)] = &[
+    ("javascript", "process.env"),
+    ("python", "os.environ["),
+];
+
+/// Systems recognized from a file's imports, in order of precedence
+const SYSTEMS: &[(EffectKind, &[&str], &str)] = &[
+    (Database, &["postgres", "psycopg", "asyncpg", "npgsql", "pgpool", "'pg'", "\"pg\""], "Postgres"),
+    (Database, &["mysql", "mariadb"], "MySQL"),
+    (Database, &["sqlite"], "SQLite"),
+    (Database, &["sqlclient", "mssql", "tiberius"], "SQL Server"),
+    (Database, &["mongo"], "MongoDB"),
+    (Database, &["dynamodb"], "DynamoDB"),
+    (Database, &["redis"], "Redis"),
+    (Messaging, &["kafka"], "Kafka"),
+    (Messaging, &["amqp", "lapin", "pika", "rabbit"], "RabbitMQ"),
+    (Messaging, &["sqs"], "SQS"),
+    (Messaging, &["sns"], "SNS"),
+    (Messaging, &["nats"], "NATS"),
+    (Messaging, &["pubsub"], "Pub/Sub"),
+    (Messaging, &["servicebus"], "Service Bus"),
+];
+
+/// SQL that changes data, as opposed to reading it
+const SQL_WRITES: &[&str] = &["INSERT ", "UPDATE ", "DELETE ", "UPSERT ", "MERGE ", "CREATE ", "DROP ", "ALTER ", "TRUNCATE "];
+
+/// Lines after a call site searched for its SQL or file mode
+const CALL_SITE_LINES: usize = 3;
+
+/// Finds the I/O functions perform through library calls and propagates it up the call graph
+pub struct SideEffectAnalyzer;
+
+impl SideEffectAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn analyze(&self, call_graph: &CallGraph, files: &[ParsedFile]) -> SideEffects {
+        let files_by_path: HashMap<&PathBuf, &ParsedFile> = files.iter().map(|file| (&file.path, file)).collect();
+
+        let mut direct: HashMap<MethodSignature, Vec<SideEffectSite>> = HashMap::new();
+        for call in &call_graph.unresolved_calls {
+            let Some(file) = files_by_path.get(&call.caller.file_path) else {
+                continue;
+            };
+            if let Some(site) = detect_call(file, call) {
+                direct.entry(call.caller.clone()).or_default().push(site);
+            }
+        }
+
+        for file in files {
+            for (language, token) in ENVIRONMENT_ACCESS {
+                if file.language != *language {
+                    continue;
+                }
+                for (index, line) in file.source_content.lines().enumerate() {
+                    let Some(method) = line.contains(token)
+                        .then(|| call_graph.find_containing_method(file, index + 1))
+                        .flatten() else {
+                        continue;
+                    };
+                    direct.entry(method).or_default().push(SideEffectSite {
+                        effect: SideEffect { kind: Environment, access: Read, system: None },
+                        call: token.trim_end_matches('[').to_string(),
+                        line: index + 1,
+                    });
+                }
+            }
+        }
+
+        for sites in direct.values_mut() {
+            sites.sort_by(|a, b| a.line.cmp(&b.line).then(a.effect.cmp(&b.effect)));
+        }
+
+        // Callers inherit their callees' effects; repeat until nothing changes, which also settles cycles
+        let mut reachable: HashMap<MethodSignature, BTreeSet<SideEffect>> = direct.iter()
+            .map(|(method, sites)| (method.clone(), sites.iter().map(|site| site.effect.clone()).collect()))
+            .collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for edge in &call_graph.edges {
+                let Some(inherited) = reachable.get(&edge.callee).cloned() else {
+                    continue;
+                };
+                let effects = reachable.entry(edge.caller.clone()).or_default();
+                let before = effects.len();
+                effects.extend(inherited);
+                changed |= effects.len() != before;
+            }
+        }
+
+        SideEffects { direct, reachable }
+    }
+}
+
+impl Default for SideEffectAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SideEffects {
+    /// Everything a function and its callees touch
+    pub fn effects_of(&self, method: &MethodSignature) -> BTreeSet<SideEffect> {
+        self.reachable.get(method).cloned().unwrap_or_default()
+    }
+
+    /// Everything the workflows of a group touch, starting from their entry points
+    pub fn effects_of_group(&self, group: &CallChainGroup) -> BTreeSet<SideEffect> {
+        group.call_chains.iter()
+            .flat_map(|chain| self.effects_of(&chain.entry_point.signature))
+            .collect()
+    }
+
+    /// The side-effecting calls made by the group's own functions
+    pub fn sites_in_group<'a>(&'a self, group: &'a CallChainGroup) -> Vec<(&'a MethodSignature, &'a SideEffectSite)> {
+        let mut sites: Vec<(&MethodSignature, &SideEffectSite)> = group.all_methods.iter()
+            .filter_map(|method| self.direct.get_key_value(method))
+            .flat_map(|(method, sites)| sites.iter().map(move |site| (method, site)))
+            .collect();
+        sites.sort_by(|(a, a_site), (b, b_site)| a_site.effect.cmp(&b_site.effect)
+            .then_with(|| a.to_unique_string().cmp(&b.to_unique_string()))
+            .then(a_site.line.cmp(&b_site.line)));
+        sites
+    }
+}
+
+impl SideEffect {
+    /// Plain-language description, e.g. "writes to Postgres"
+    pub fn describe(&self) -> String {
+        let system = self.system.as_deref();
+        match (self.kind, self.access) {
+            (Database, Read) => format!("reads from {}", system.unwrap_or("a database")),
+            (Database, Write) => format!("writes to {}", system.unwrap_or("a database")),
+            (Messaging, Read) => format!("consumes from {}", system.unwrap_or("a message broker")),
+            (Messaging, Write) => format!("publishes to {}", system.unwrap_or("a message broker")),
+            (Network, _) if system == Some("HTTP") => "makes HTTP requests".to_string(),
+            (Network, _) => "opens network connections".to_string(),
+            (Filesystem, Read) => "reads files".to_string(),
+            (Filesystem, Write) => "writes files".to_string(),
+            (Process, _) => "spawns processes".to_string(),
+            (Environment, Read) => "reads env".to_string(),
+            (Environment, Write) => "sets env".to_string(),
+        }
+    }
+}
+
+/// Comma-separated descriptions, e.g. "writes to Postgres, publishes to Kafka, reads env"
+pub fn describe_effects<'a>(effects: impl IntoIterator<Item = &'a SideEffect>) -> String {
+    let mut descriptions: Vec<String> = Vec::new();
+    for description in effects.into_iter().map(SideEffect::describe) {
+        if !descriptions.contains(&description) {
+            descriptions.push(description);
+        }
+    }
+    descriptions.join(", ")
+}
+
+/// Match an unresolved call against the rules for its file's language
+fn detect_call(file: &ParsedFile, call: &UnresolvedCall) -> Option<SideEffectSite> {
+    let callee = call.callee_name.to_lowercase();
+    let receiver = call.receiver.as_deref().map(receiver_name);
+
+    let rule = RULES.iter().find(|rule| {
+        (rule.languages.is_empty() || rule.languages.contains(&file.language.as_str()))
+            && rule.callees.contains(&callee.as_str())
+            && match &receiver {
+                Some(receiver) => rule.receivers == ANY_RECEIVER || rule.receivers.contains(&receiver.as_str()),
+                None => rule.receivers.is_empty(),
+            }
+            && rule.requires.is_none_or(|text| file.source_content.contains(text))
+    })?;
+
+    let call_site = call_site_text(file, call.call_site_line);
+    let access = match rule.refine {
+        Refine::None => rule.access,
+        Refine::Sql => {
+            let upper = call_site.to_uppercase();
+            if SQL_WRITES.iter().any(|keyword| upper.contains(keyword)) {
+                Write
+            } else if upper.contains("SELECT ") {
+                Read
+            } else {
+                rule.access
+            }
+        }
+        Refine::OpenMode => {
+            let writes = ["\"w", "'w", "\"a", "'a", "\"x", "'x", "+\"", "+'"];
+            if writes.iter().any(|mode| call_site.contains(mode)) { Write } else { rule.access }
+        }
+    };
+
+    let system = rule.system.map(str::to_string)
+        .or_else(|| imported_system(file, rule.kind).map(str::to_string));
+
+    Some(SideEffectSite {
+        effect: SideEffect { kind: rule.kind, access, system },
+        call: match &call.receiver {
+            Some(written) if !written.contains(['(', '\n']) => {
+                let path = format!("{}::{}", written, call.callee_name);
+                if call_site.contains(&path) { path } else { format!("{}.{}", written, call.callee_name) }
+            }
+            Some(_) => format!("{}.{}", receiver.unwrap_or_default(), call.callee_name),
+            None => call.callee_name.clone(),
+        },
+        line: call.call_site_line,
+    })
+}
+
+/// The last segment of a receiver expression, without call arguments or a leading `_`
+/// (`self.pool` → `pool`, `sqlx::query("...")` → `query`, `_httpClient` → `httpclient`)
+pub(super) fn receiver_name(receiver: &str) -> String {
+    let mut depth = 0usize;
+    let mut outside_parens = String::new();
+    for c in receiver.chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => outside_parens.push(c),
+            _ => {}
+        }
+    }
+
+    outside_parens.rsplit(['.', ':'])
+        .find(|segment| !segment.trim().is_empty())
+        .unwrap_or_default()
+        .trim()
+        .trim_start_matches(['_', '&', '*'])
+        .to_lowercase()
+}
+
+/// The call site line and the few after it, where a statement's SQL or a file mode usually is
+fn call_site_text(file: &ParsedFile, line: usize) -> String {
+    file.source_content.lines()
+        .skip(line.saturating_sub(1))
+        .take(CALL_SITE_LINES)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The first known system of the given kind among the file's imports
+fn imported_system(file: &ParsedFile, kind: EffectKind) -> Option<&'static str> {
+    let imports: Vec<String> = file.source_content.lines()
+        .map(str::trim)
+        .filter(|line| ["use ", "import ", "from ", "using ", "extern crate "].iter().any(|keyword| line.starts_with(keyword))
+            || line.contains("require("))
+        .map(str::to_lowercase)
+        .collect();
+
+    SYSTEMS.iter()
+        .filter(|(system_kind, _, _)| *system_kind == kind)
+        .find(|(_, needles, _)| imports.iter().any(|import| needles.iter().any(|needle| import.contains(needle))))
+        .map(|(_, _, name)| *name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::{graph, method, node, parsed_file};
+    use crate::core::call_graph::{CallNode, CallType};
+
+    fn function(name: &str, lines: (usize, usize)) -> CallNode {
+        node(method("/p/src/orders.rs", None, name), lines)
+    }
+
+    fn unresolved(caller: &CallNode, receiver: &str, callee: &str, line: usize) -> UnresolvedCall {
+        UnresolvedCall {
+            caller: caller.signature.clone(),
+            callee_name: callee.to_string(),
+            receiver: Some(receiver.to_string()),
+            call_site_line: line,
+            call_type: CallType::Direct,
+            candidates: vec![],
+        }
+    }
+
+    #[test]
+    fn test_detects_and_propagates_effects() {
+        let source = "use tokio_postgres::Client;\nuse rdkafka::producer::FutureProducer;\n\
+            fn place() {\n    save();\n    notify();\n}\n\
+            fn save() {\n    self.conn.execute(\"INSERT INTO orders VALUES ($1)\", &[]);\n}\n\
+            fn notify() {\n    self.producer.send(record);\n    std::env::var(\"TOPIC\");\n}\n";
+        let file = parsed_file("/p/src/orders.rs", "rust", source);
+
+        let (place, save, notify) = (function("place", (3, 6)), function("save", (7, 9)), function("notify", (10, 13)));
+        let mut graph = graph(
+            &[place.clone(), save.clone(), notify.clone()],
+            &[(&place.signature, &save.signature, 4), (&place.signature, &notify.signature, 4)],
+        );
+        graph.unresolved_calls = vec![
+            unresolved(&save, "self.conn", "execute", 8),
+            unresolved(&notify, "self.producer", "send", 11),
+            unresolved(&notify, "std::env", "var", 12),
+            // `profile` is not `File`, however it ends
+            unresolved(&notify, "self.profile", "open", 12),
+        ];
+
+        let effects = SideEffectAnalyzer::new().analyze(&graph, &[file]);
+
+        assert_eq!(describe_effects(&effects.effects_of(&place.signature)), "writes to Postgres, publishes to Kafka, reads env");
+        assert_eq!(describe_effects(&effects.effects_of(&save.signature)), "writes to Postgres");
+        assert_eq!(effects.direct[&notify.signature][0].call, "self.producer.send");
+        assert_eq!(effects.direct[&notify.signature][1].call, "std::env::var");
+        assert_eq!(effects.direct[&notify.signature].len(), 2);
+    }
+
+    #[test]
+    fn test_receiver_name() {
+        assert_eq!(receiver_name("self.pool"), "pool");
+        assert_eq!(receiver_name("sqlx::query(\"select a.b from t\")"), "query");
+        assert_eq!(receiver_name("_httpClient"), "httpclient");
+        assert_eq!(receiver_name("Runtime.getRuntime()"), "getruntime");
+    }
+}
@@ -0,0 +1,472 @@
+// src/core/call_graph/synthesis.rs
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use serde::Deserialize;
+use tracing::warn;
+
+use super::{CallChainGroup, GroupAnalysis, RankedGotcha};
+use super::super::{LlmDocumenter, EnhancementRequest, EnhancementType, DocumentationContext, ParsedFile, ProjectInfo};
+
+/// A summary at one level of the reduction, traced back to the groups it was drawn from
+#[derive(Debug, Clone)]
+pub(super) struct Summary {
+    /// How the LLM refers to this summary when citing it
+    pub label: String,
+    pub text: String,
+    pub themes: Vec<Claim>,
+    /// Groups this summary was drawn from
+    pub sources: BTreeSet<String>,
+}
+
+/// A statement with the groups that support it
+#[derive(Debug, Clone)]
+pub(super) struct Claim {
+    pub text: String,
+    pub sources: BTreeSet<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ReduceResponse {
+    summary: String,
+    themes: Vec<ThemeResponse>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ThemeResponse {
+    theme: String,
+    sources: Vec<String>,
+}
+
+/// Rough size of a token, used to turn the context limit into characters
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Reduces summaries with the LLM, batching them to fit its context limit
+pub(super) struct Reducer<'a> {
+    llm_documenter: &'a dyn LlmDocumenter,
+    /// Characters of summaries one call may carry
+    batch_chars: usize,
+    /// Calls left before falling back to merging summaries without the LLM
+    calls_left: Option<usize>,
+    pub calls_made: usize,
+}
+
+impl<'a> Reducer<'a> {
+    pub fn new(llm_documenter: &'a dyn LlmDocumenter, max_context_size: usize, call_budget: Option<usize>) -> Self {
+        Self {
+            llm_documenter,
+            // Leave half the context for the instructions and the response
+            batch_chars: (max_context_size * CHARS_PER_TOKEN / 2).max(1000),
+            calls_left: call_budget,
+            calls_made: 0,
+        }
+    }
+
+    /// Reduce `items` to one summary about `subject`, in several rounds if they do not fit one call.
+    /// A single item is passed through as it is.
+    pub async fn reduce(&mut self, label: &str, subject: &str, mut items: Vec<Summary>) -> Summary {
+        let mut round = 0;
+        loop {
+            match items.len() {
+                0 => return merge(label, items),
+                1 => {
+                    let mut only = items.remove(0);
+                    only.label = label.to_string();
+                    return only;
+                }
+                _ => {}
+            }
+
+            let mut batches = self.batch(items);
+            if batches.len() == 1 {
+                return self.reduce_batch(label, subject, batches.remove(0)).await;
+            }
+
+            round += 1;
+            items = Vec::new();
+            for (i, batch) in batches.into_iter().enumerate() {
+                let part_label = format!("{} (part {}.{})", label, round, i + 1);
+                items.push(self.reduce_batch(&part_label, subject, batch).await);
+            }
+        }
+    }
+
+    /// Split items into batches that fit one call. Every batch takes at least two
+    /// items, with each item cut to half a batch, so each round shrinks the list.
+    fn batch(&self, items: Vec<Summary>) -> Vec<Vec<Summary>> {
+        let item_limit = self.batch_chars / 2;
+        let mut batches: Vec<Vec<Summary>> = Vec::new();
+        let mut current: Vec<Summary> = Vec::new();
+        let mut current_chars = 0;
+
+        for mut item in items {
+            truncate(&mut item.text, item_limit);
+            let size = render(&item).len();
+            if current.len() >= 2 && current_chars + size > self.batch_chars {
+                batches.push(std::mem::take(&mut current));
+                current_chars = 0;
+            }
+            current_chars += size;
+            current.push(item);
+        }
+        if !current.is_empty() {
+            batches.push(current);
+        }
+        batches
+    }
+
+    /// Summarise items that fit one call
+    pub async fn reduce_batch(&mut self, label: &str, subject: &str, items: Vec<Summary>) -> Summary {
+        let sources: BTreeSet<String> = items.iter().flat_map(|item| item.sources.iter().cloned()).collect();
+
+        if self.calls_left == Some(0) {
+            return merge(label, items);
+        }
+
+        let mut prompt = format!(
+            "Below are summaries of parts of {}, each tagged with a [label]. \
+            Combine them into one summary of {} and name its recurring themes.\n\n",
+            subject, subject
+        );
+        for item in &items {
+            prompt.push_str(&render(item));
+        }
+        prompt.push_str(
+            "\nRespond in JSON format only:\n\
+            {\n\
+              \"summary\": \"What this part of the system does and why, in 2-5 sentences\",\n\
+              \"themes\": [{\"theme\": \"A short recurring theme\", \"sources\": [\"label of each summary it comes from\"]}]\n\
+            }\n"
+        );
+
+        let request = EnhancementRequest {
+            enhancement_type: EnhancementType::Custom(prompt),
+            context: synthesis_context(label),
+            current_content: None,
+            focus_areas: vec!["system_synthesis".to_string()],
+        };
+
+        self.calls_made += 1;
+        if let Some(calls_left) = &mut self.calls_left {
+            *calls_left -= 1;
+        }
+
+        let response = match self.llm_documenter.enhance_documentation(request).await {
+            Ok(response) => response,
+            Err(e) => {
+                warn!("LLM synthesis of {} failed: {}", label, e);
+                return merge(label, items);
+            }
+        };
+
+        let Some(parsed) = json_object(&response.content)
+            .and_then(|json| serde_json::from_str::<ReduceResponse>(json).ok())
+            .filter(|parsed| !parsed.summary.trim().is_empty()) else {
+            warn!("Could not parse the LLM synthesis of {}; merging summaries instead", label);
+            return merge(label, items);
+        };
+
+        let themes = parsed.themes.into_iter()
+            .filter(|theme| !theme.theme.trim().is_empty())
+            .map(|theme| {
+                // A theme the LLM cannot attribute is credited to everything it saw
+                let cited: BTreeSet<String> = items.iter()
+                    .filter(|item| theme.sources.iter().any(|source| source.trim_matches(['[', ']']) == item.label))
+                    .flat_map(|item| item.sources.iter().cloned())
+                    .collect();
+                Claim {
+                    text: theme.theme.trim().to_string(),
+                    sources: if cited.is_empty() { sources.clone() } else { cited },
+                }
+            })
+            .collect();
+
+        Summary {
+            label: label.to_string(),
+            text: parsed.summary.trim().to_string(),
+            themes: dedup_claims(themes),
+            sources,
+        }
+    }
+}
+
+impl Summary {
+    /// A group's analysis as the leaf of the reduction
+    pub fn from_group(group: &CallChainGroup, analysis: &GroupAnalysis) -> Self {
+        let mut text = format!("{}: {}", group.name, analysis.description.trim());
+        for insight in &analysis.domain_insights {
+            text.push_str(&format!(" {}: {}.", insight.category, insight.insight.trim_end_matches('.')));
+        }
+        Self {
+            label: group.group_id.clone(),
+            text,
+            themes: Vec::new(),
+            sources: BTreeSet::from([group.group_id.clone()]),
+        }
+    }
+}
+
+/// Combine summaries without the LLM: their first sentences and all their themes
+fn merge(label: &str, items: Vec<Summary>) -> Summary {
+    let text = items.iter()
+        .map(|item| first_sentence(&item.text))
+        .collect::<Vec<_>>()
+        .join(" ");
+    Summary {
+        label: label.to_string(),
+        text,
+        themes: dedup_claims(items.iter().flat_map(|item| item.themes.iter().cloned()).collect()),
+        sources: items.into_iter().flat_map(|item| item.sources).collect(),
+    }
+}
+
+fn render(item: &Summary) -> String {
+    let mut rendered = format!("[{}] {}\n", item.label, item.text);
+    if !item.themes.is_empty() {
+        let themes: Vec<&str> = item.themes.iter().map(|theme| theme.text.as_str()).collect();
+        rendered.push_str(&format!("  Themes: {}\n", themes.join("; ")));
+    }
+    rendered
+}
+
+fn first_sentence(text: &str) -> String {
+    let end = text.find(". ").map(|end| end + 1).unwrap_or(text.len());
+    text[..end].trim().to_string()
+}
+
+fn truncate(text: &mut String, max_chars: usize) {
+    if let Some((end, _)) = text.char_indices().nth(max_chars) {
+        text.truncate(end);
+        text.push('…');
+    }
+}
+
+/// Merge claims that say the same thing, keeping the first wording and every source
+pub(super) fn dedup_claims(claims: Vec<Claim>) -> Vec<Claim> {
+    let mut merged: Vec<Claim> = Vec::new();
+    let mut index: BTreeMap<String, usize> = BTreeMap::new();
+
+    for claim in claims {
+        let key = normalize(&claim.text);
+        if key.is_empty() {
+            continue;
+        }
+        match index.get(&key) {
+            Some(&i) => merged[i].sources.extend(claim.sources),
+            None => {
+                index.insert(key, merged.len());
+                merged.push(claim);
+            }
+        }
+    }
+    merged
+}
+
+/// Every group's gotchas, duplicates merged, most severe and most widespread first
+pub(super) fn rank_gotchas(analyses: &[GroupAnalysis]) -> Vec<RankedGotcha> {
+    let mut ranked: Vec<RankedGotcha> = Vec::new();
+    let mut index: BTreeMap<String, usize> = BTreeMap::new();
+
+    for analysis in analyses {
+        for gotcha in &analysis.gotchas {
+            let key = normalize(&gotcha.description);
+            if key.is_empty() {
+                continue;
+            }
+            match index.get(&key) {
+                Some(&i) => {
+                    let existing = &mut ranked[i];
+                    if gotcha.severity > existing.gotcha.severity {
+                        existing.gotcha.severity = gotcha.severity.clone();
+                    }
+                    if existing.gotcha.suggested_action.is_none() {
+                        existing.gotcha.suggested_action = gotcha.suggested_action.clone();
+                    }
+                    existing.source_groups.insert(analysis.group_id.clone());
+                }
+                None => {
+                    index.insert(key, ranked.len());
+                    ranked.push(RankedGotcha {
+                        gotcha: gotcha.clone(),
+                        source_groups: BTreeSet::from([analysis.group_id.clone()]),
+                    });
+                }
+            }
+        }
+    }
+
+    ranked.sort_by(|a, b| b.gotcha.severity.cmp(&a.gotcha.severity)
+        .then(b.source_groups.len().cmp(&a.source_groups.len()))
+        .then(a.gotcha.description.cmp(&b.gotcha.description)));
+    ranked
+}
+
+/// Lowercase words only, so differences in case and punctuation do not split duplicates
+fn normalize(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The JSON object in an LLM answer, which may be wrapped in a code fence or prose
+pub(super) fn json_object(content: &str) -> Option<&str> {
+    let start = content.find('{')?;
+    let end = content.rfind('}')?;
+    (end > start).then(|| &content[start..=end])
+}
+
+fn synthesis_context(label: &str) -> DocumentationContext {
+    DocumentationContext {
+        file: ParsedFile {
+            path: PathBuf::from(label),
+            language: "analysis".to_string(),
+            content_hash: String::new(),
+            modified_time: std::time::SystemTime::now(),
+            modules: vec![],
+            file_docs: None,
+            source_content: String::new(),
+            calls: vec![],
+        },
+        target_module: None,
+        related_files: vec![],
+        project_info: ProjectInfo {
+            name: "System Synthesis".to_string(),
+            description: None,
+            language: "multi".to_string(),
+            project_type: Some("analysis".to_string()),
+        },
+        architecture_docs: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::core::call_graph::call_chain_analyzer::{Gotcha, GotchaSeverity};
+    use crate::core::llm::{DocumenterCapabilities, EnhancementResponse};
+    use crate::error::Result;
+
+    /// Answers every synthesis prompt with a numbered summary and one theme cited to the first label
+    struct StubDocumenter {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl LlmDocumenter for StubDocumenter {
+        async fn enhance_documentation(&self, request: EnhancementRequest) -> Result<EnhancementResponse> {
+            let EnhancementType::Custom(prompt) = request.enhancement_type else {
+                unreachable!("synthesis always sends custom prompts");
+            };
+            let first_label = prompt.lines()
+                .find_map(|line| line.strip_prefix('[').and_then(|rest| rest.split_once(']')))
+                .map(|(label, _)| label.to_string())
+                .unwrap_or_default();
+            let call = self.calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Ok(EnhancementResponse {
+                content: format!(
+                    "```json\n{{\"summary\": \"Summary {}\", \"themes\": [{{\"theme\": \"Theme\", \"sources\": [\"{}\"]}}]}}\n```",
+                    call, first_label
+                ),
+                confidence: None,
+                suggestions: vec![],
+                metadata: HashMap::new(),
+            })
+        }
+
+        fn provider_name(&self) -> &str {
+            "stub"
+        }
+
+        fn model_name(&self) -> &str {
+            "stub"
+        }
+
+        async fn health_check(&self) -> Result<bool> {
+            Ok(true)
+        }
+
+        fn capabilities(&self) -> DocumenterCapabilities {
+            DocumenterCapabilities {
+                max_context_tokens: None,
+                max_response_tokens: None,
+                supported_enhancements: vec![],
+                supports_streaming: false,
+                supports_code_analysis: false,
+            }
+        }
+    }
+
+    fn analysis(group_id: &str, gotchas: &[(&str, GotchaSeverity)]) -> GroupAnalysis {
+        GroupAnalysis {
+            group_id: group_id.to_string(),
+            description: String::new(),
+            entry_point_descriptions: HashMap::new(),
+            component_interactions: vec![],
+            domain_insights: vec![],
+            gotchas: gotchas.iter().map(|(description, severity)| Gotcha {
+                category: "General".to_string(),
+                description: description.to_string(),
+                severity: severity.clone(),
+                suggested_action: None,
+            }).collect(),
+            confidence: 0.8,
+            is_incremental: false,
+            input_hash: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_ranks_and_merges_gotchas() {
+        let ranked = rank_gotchas(&[
+            analysis("g1", &[("Retries are not idempotent", GotchaSeverity::Warning), ("Logs secrets", GotchaSeverity::Info)]),
+            analysis("g2", &[("retries are NOT idempotent.", GotchaSeverity::Critical)]),
+            analysis("g3", &[("Cache never expires", GotchaSeverity::Warning)]),
+        ]);
+
+        let order: Vec<&str> = ranked.iter().map(|gotcha| gotcha.gotcha.description.as_str()).collect();
+        assert_eq!(order, vec!["Retries are not idempotent", "Cache never expires", "Logs secrets"]);
+        assert_eq!(ranked[0].gotcha.severity, GotchaSeverity::Critical);
+        assert_eq!(ranked[0].source_groups, BTreeSet::from(["g1".to_string(), "g2".to_string()]));
+    }
+
+    #[test]
+    fn test_dedups_claims_keeping_sources() {
+        let claim = |text: &str, source: &str| Claim { text: text.to_string(), sources: BTreeSet::from([source.to_string()]) };
+        let claims = dedup_claims(vec![claim("Event sourcing", "g1"), claim("event-sourcing", "g2"), claim("Caching", "g3")]);
+
+        assert_eq!(claims.len(), 2);
+        assert_eq!(claims[0].text, "Event sourcing");
+        assert_eq!(claims[0].sources.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_reduces_in_rounds_within_the_context_limit() {
+        let llm = StubDocumenter { calls: Default::default() };
+        let items: Vec<Summary> = (0..6).map(|i| Summary {
+            label: format!("g{}", i),
+            text: "x".repeat(800),
+            themes: vec![],
+            sources: BTreeSet::from([format!("g{}", i)]),
+        }).collect();
+
+        // A 1000-character batch fits two items: three calls for six items, then one for the three results
+        let mut reducer = Reducer::new(&llm, 0, None);
+        let summary = reducer.reduce("system", "the system", items).await;
+
+        assert_eq!(reducer.calls_made, 4);
+        assert_eq!(summary.label, "system");
+        assert_eq!(summary.sources.len(), 6);
+        assert_eq!(summary.themes[0].sources, BTreeSet::from(["g0".to_string(), "g1".to_string()]));
+
+        // Without budget the summaries are merged instead
+        let mut reducer = Reducer::new(&llm, 0, Some(0));
+        let merged = reducer.reduce("pkg", "the package", vec![summary.clone(), summary]).await;
+        assert_eq!(reducer.calls_made, 0);
+        assert_eq!(merged.text, "Summary 3 Summary 3");
+    }
+}
@@ -0,0 +1,154 @@
+// src/core/call_graph/test_coverage.rs
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use serde::{Serialize, Deserialize};
+
+use super::call_graph::signature_map;
+use super::{CallGraph, EntryPoint, EntryPointType, MethodSignature};
+
+/// Builds a "tested by" index by walking the call graph from test entry points
+pub struct TestCoverageAnalyzer;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TestCoverage {
+    /// Tests that (transitively) reach each non-test function
+    #[serde(with = "signature_map")]
+    pub tested_by: HashMap<MethodSignature, Vec<MethodSignature>>,
+    /// Public functions no test reaches, keyed by package
+    pub untested_public_api: BTreeMap<String, Vec<MethodSignature>>,
+}
+
+impl TestCoverageAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Compute reachability from every test entry point
+    pub fn analyze(&self, call_graph: &CallGraph, entry_points: &[EntryPoint]) -> TestCoverage {
+        let mut tested_by: HashMap<MethodSignature, Vec<MethodSignature>> = HashMap::new();
+
+        let tests = entry_points.iter()
+            .filter(|ep| matches!(ep.entry_type, EntryPointType::Test))
+            .map(|ep| &ep.signature);
+
+        for test in tests {
+            let mut visited = HashSet::new();
+            let mut queue = VecDeque::new();
+            visited.insert(test);
+            queue.push_back(test);
+
+            while let Some(current) = queue.pop_front() {
+                for callee in call_graph.get_callees(current) {
+                    if !visited.insert(callee) {
+                        continue;
+                    }
+
+                    let is_test = call_graph.nodes.get(callee).is_some_and(|node| node.is_test);
+                    if !is_test {
+                        tested_by.entry(callee.clone()).or_default().push(test.clone());
+                    }
+                    queue.push_back(callee);
+                }
+            }
+        }
+
+        for tests in tested_by.values_mut() {
+            tests.sort_by_key(|test| test.to_unique_string());
+        }
+
+        let mut untested_public_api: BTreeMap<String, Vec<MethodSignature>> = BTreeMap::new();
+        for node in call_graph.nodes.values() {
+            if node.visibility == "public" && !node.is_test && !tested_by.contains_key(&node.signature) {
+                untested_public_api.entry(node.signature.package_name())
+                    .or_default()
+                    .push(node.signature.clone());
+            }
+        }
+
+        for functions in untested_public_api.values_mut() {
+            functions.sort_by_key(|function| function.to_unique_string());
+        }
+
+        TestCoverage {
+            tested_by,
+            untested_public_api,
+        }
+    }
+}
+
+impl TestCoverage {
+    /// Tests that exercise the given function (empty if untested)
+    pub fn tests_for(&self, method: &MethodSignature) -> &[MethodSignature] {
+        self.tested_by.get(method).map(|tests| tests.as_slice()).unwrap_or_default()
+    }
+}
+
+impl Default for TestCoverageAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::test_support::{entry_point, graph, method, node};
+    use crate::core::call_graph::CallNode;
+
+    fn coverage() -> (TestCoverage, impl Fn(&str) -> MethodSignature) {
+        let signature = |name: &str| {
+            let path = if name.starts_with("test") { "/p/tests/orders_test.rs" } else { "/p/src/orders/service.rs" };
+            method(path, None, name)
+        };
+        let nodes = [
+            CallNode { is_test: true, ..node(signature("test_place"), (1, 5)) },
+            CallNode { is_test: true, ..node(signature("test_cancel"), (6, 9)) },
+            CallNode { is_test: true, ..node(signature("test_fixture"), (10, 12)) },
+            CallNode { visibility: "private".to_string(), ..node(signature("internal"), (1, 2)) },
+        ];
+        let graph = graph(&nodes, &[
+            (&signature("test_place"), &signature("place"), 2),
+            (&signature("test_place"), &signature("test_fixture"), 3),
+            (&signature("test_fixture"), &signature("load"), 11),
+            (&signature("place"), &signature("save"), 4),
+            (&signature("test_cancel"), &signature("place"), 7),
+            (&signature("cancel"), &signature("save"), 8),
+            (&signature("cancel"), &signature("internal"), 9),
+        ]);
+        let entry_points = vec![
+            entry_point(&signature("test_place"), EntryPointType::Test),
+            entry_point(&signature("test_cancel"), EntryPointType::Test),
+            entry_point(&signature("cancel"), EntryPointType::PublicApi),
+        ];
+
+        (TestCoverageAnalyzer::new().analyze(&graph, &entry_points), signature)
+    }
+
+    #[test]
+    fn test_reachability_from_tests() {
+        let (coverage, signature) = coverage();
+        let tests = |name: &str| coverage.tests_for(&signature(name)).iter()
+            .map(|test| test.method_name.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(tests("place"), vec!["test_cancel", "test_place"]);
+        assert_eq!(tests("save"), vec!["test_cancel", "test_place"]);
+        // Reached through a test helper, which is not itself listed as tested
+        assert_eq!(tests("load"), vec!["test_place"]);
+        assert!(tests("test_fixture").is_empty());
+        assert!(tests("cancel").is_empty());
+    }
+
+    #[test]
+    fn test_classifies_untested_public_api() {
+        let (coverage, _) = coverage();
+
+        let untested: Vec<_> = coverage.untested_public_api.iter()
+            .flat_map(|(package, functions)| functions.iter().map(move |function| (package.as_str(), function.method_name.as_str())))
+            .collect();
+        assert_eq!(untested, vec![("orders", "cancel")]);
+
+        let json = serde_json::to_string(&coverage).unwrap();
+        let restored: TestCoverage = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.tested_by, coverage.tested_by);
+    }
+}
@@ -0,0 +1,89 @@
+// src/core/call_graph/test_support.rs
+//! Fixtures shared by the call-graph unit tests
+
+use std::path::{Path, PathBuf};
+
+use super::{CallEdge, CallGraph, CallNode, CallType, EntryPoint, EntryPointType, MethodSignature};
+use super::super::ParsedFile;
+use super::super::languages::LanguageParser;
+
+/// A parsed file with `source` as its content and no extracted modules or calls
+pub(super) fn parsed_file(path: &str, language: &str, source: &str) -> ParsedFile {
+    ParsedFile {
+        path: PathBuf::from(path),
+        language: language.to_string(),
+        content_hash: String::new(),
+        modified_time: std::time::SystemTime::UNIX_EPOCH,
+        modules: vec![],
+        file_docs: None,
+        source_content: source.to_string(),
+        calls: vec![],
+    }
+}
+
+/// `source` run through a real language parser, with its modules and calls extracted
+pub(super) fn parsed_source(parser: &mut dyn LanguageParser, path: &str, source: &str) -> ParsedFile {
+    ParsedFile {
+        modules: parser.parse(source, Path::new(path)).unwrap(),
+        calls: parser.extract_calls(source).unwrap(),
+        ..parsed_file(path, parser.language_name(), source)
+    }
+}
+
+/// The signature of `name` declared in `path`, optionally inside `class`
+pub(super) fn method(path: &str, class: Option<&str>, name: &str) -> MethodSignature {
+    MethodSignature::new(PathBuf::from(path), name.to_string(), class.map(str::to_string), None, format!("fn {}()", name))
+}
+
+/// A public, synchronous, non-test node spanning `lines`
+pub(super) fn node(signature: MethodSignature, lines: (usize, usize)) -> CallNode {
+    CallNode {
+        signature,
+        line_range: lines,
+        visibility: "public".to_string(),
+        is_async: false,
+        documentation: None,
+        complexity_score: 1,
+        is_test: false,
+    }
+}
+
+/// A direct call made from `caller` to `callee` at `line`
+pub(super) fn edge(caller: &MethodSignature, callee: &MethodSignature, line: usize) -> CallEdge {
+    CallEdge {
+        caller: caller.clone(),
+        callee: callee.clone(),
+        call_site_line: line,
+        call_type: CallType::Direct,
+        confidence: 1.0,
+    }
+}
+
+/// A graph of `nodes` and `(caller, callee, call_site_line)` calls; call ends
+/// without an explicit node get a default one
+pub(super) fn graph(nodes: &[CallNode], calls: &[(&MethodSignature, &MethodSignature, usize)]) -> CallGraph {
+    let mut graph = CallGraph::new();
+    for node in nodes {
+        graph.add_node(node.clone());
+    }
+    for (caller, callee, line) in calls {
+        for signature in [*caller, *callee] {
+            if !graph.nodes.contains_key(signature) {
+                graph.add_node(node(signature.clone(), (1, 2)));
+            }
+        }
+        graph.add_edge(edge(caller, callee, *line));
+    }
+    graph
+}
+
+/// An entry point of the given type for `signature`
+pub(super) fn entry_point(signature: &MethodSignature, entry_type: EntryPointType) -> EntryPoint {
+    EntryPoint {
+        signature: signature.clone(),
+        entry_type,
+        confidence: 1.0,
+        reasoning: String::new(),
+        routes: vec![],
+    }
+}
@@ -0,0 +1,212 @@
+// src/core/call_graph/type_hierarchy.rs
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::sync::LazyLock;
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+
+use super::super::{ParsedFile, ParsedModule};
+
+/// `impl<T> Trait<T> for Type<T>`, capturing the trait and the type
+static RUST_IMPL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^impl(?:\s*<.*?>)?\s+(.+?)\s+for\s+(.+?)(?:\s+where\b.*)?$").expect("valid regex")
+});
+
+/// The type lists after `extends` and after `implements` in a Java/JavaScript header
+static SUPERTYPE_LISTS: LazyLock<Vec<Regex>> = LazyLock::new(|| {
+    ["extends", "implements"].iter()
+        .map(|keyword| {
+            let pattern = format!(r"\b{}\s+(.+?)(?:\s+(?:extends|implements|permits)\b|$)", keyword);
+            Regex::new(&pattern).expect("valid regex")
+        })
+        .collect()
+});
+
+/// Index of which types implement or extend which, built from `implements`/`extends`
+/// clauses, C#/Python base lists and Rust `impl Trait for Type` blocks
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TypeHierarchy {
+    /// Direct supertypes (interfaces, traits, base classes) of each type
+    supertypes: HashMap<String, BTreeSet<String>>,
+    /// Direct subtypes (implementors, subclasses) of each type
+    subtypes: HashMap<String, BTreeSet<String>>,
+}
+
+impl TypeHierarchy {
+    /// Build the hierarchy from every type declaration in the parsed files
+    pub fn build(files: &[ParsedFile]) -> Self {
+        let mut hierarchy = Self::default();
+
+        for file in files {
+            hierarchy.collect_from_modules(&file.modules, &file.language);
+        }
+
+        hierarchy
+    }
+
+    /// Record that `subtype` implements or extends `supertype`
+    pub fn add_relation(&mut self, subtype: &str, supertype: &str) {
+        if subtype.is_empty() || supertype.is_empty() || subtype == supertype {
+            return;
+        }
+
+        self.supertypes.entry(subtype.to_string()).or_default().insert(supertype.to_string());
+        self.subtypes.entry(supertype.to_string()).or_default().insert(subtype.to_string());
+    }
+
+    /// All types that (transitively) implement or extend `type_name`, nearest first
+    pub fn subtypes_of(&self, type_name: &str) -> Vec<String> {
+        Self::transitive(&self.subtypes, type_name)
+    }
+
+    /// All types `type_name` (transitively) implements or extends, nearest first
+    pub fn supertypes_of(&self, type_name: &str) -> Vec<String> {
+        Self::transitive(&self.supertypes, type_name)
+    }
+
+    fn transitive(edges: &HashMap<String, BTreeSet<String>>, start: &str) -> Vec<String> {
+        let mut result = Vec::new();
+        let mut queue = VecDeque::from([start.to_string()]);
+
+        while let Some(current) = queue.pop_front() {
+            for next in edges.get(&current).into_iter().flatten() {
+                if next != start && !result.contains(next) {
+                    result.push(next.clone());
+                    queue.push_back(next.clone());
+                }
+            }
+        }
+
+        result
+    }
+
+    fn collect_from_modules(&mut self, modules: &[ParsedModule], language: &str) {
+        for module in modules {
+            if let Some(signature) = &module.signature {
+                let header = signature.split('{').next().unwrap_or(signature);
+                let header = header.split_whitespace().collect::<Vec<_>>().join(" ");
+                let header = header.as_str();
+
+                match (language, module.item_type.as_str()) {
+                    ("rust", "impl") => self.collect_rust_impl(header),
+                    ("rust", "trait") => {
+                        if let Some((_, bounds)) = header.split_once(':') {
+                            for bound in bounds.split(['+', ',']) {
+                                let bound = bound.split("where").next().unwrap_or(bound);
+                                self.add_relation(&module.name, &simple_type_name(bound));
+                            }
+                        }
+                    }
+                    (_, "class" | "interface" | "struct") => {
+                        for supertype in self.declared_supertypes(header, language) {
+                            self.add_relation(&module.name, &supertype);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            self.collect_from_modules(&module.children, language);
+        }
+    }
+
+    /// `impl<T> Trait<T> for Type<T>`
+    fn collect_rust_impl(&mut self, header: &str) {
+        if let Some(captures) = RUST_IMPL.captures(header) {
+            let trait_name = simple_type_name(&captures[1]);
+            let type_name = simple_type_name(&captures[2]);
+            self.add_relation(&type_name, &trait_name);
+        }
+    }
+
+    /// Base types listed in a class/interface header
+    fn declared_supertypes(&self, header: &str, language: &str) -> Vec<String> {
+        let lists: Vec<String> = match language {
+            "java" | "javascript" => SUPERTYPE_LISTS.iter()
+                .filter_map(|regex| regex.captures(header).map(|captures| captures[1].to_string()))
+                .collect(),
+            "csharp" => header.split_once(':')
+                .map(|(_, bases)| vec![bases.split(" where ").next().unwrap_or(bases).to_string()])
+                .unwrap_or_default(),
+            "python" => header.split_once('(')
+                .map(|(_, bases)| vec![bases.trim_end_matches([')', ':']).to_string()])
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+
+        lists.iter()
+            .flat_map(|list| split_type_list(list))
+            .filter(|base| !base.contains('=') && base != "object")
+            .map(|base| simple_type_name(&base))
+            .collect()
+    }
+}
+
+/// `crate::plugins::Plugin<T>` -> `Plugin`, `Shop.IRepository` -> `IRepository`
+pub fn simple_type_name(path: &str) -> String {
+    let without_generics = path.split('<').next().unwrap_or(path);
+    without_generics.trim()
+        .trim_start_matches(['&', '?'])
+        .trim_start_matches("dyn ")
+        .rsplit(['.', ':'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+/// Split `Comparable<Map<K, V>>, Runnable` at top-level commas
+fn split_type_list(list: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut depth = 0;
+    let mut current = String::new();
+
+    for ch in list.chars() {
+        match ch {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(ch);
+    }
+    items.push(current.trim().to_string());
+
+    items.into_iter().filter(|item| !item.is_empty()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declared_supertypes() {
+        let hierarchy = TypeHierarchy::default();
+        assert_eq!(
+            hierarchy.declared_supertypes("public class LowerPlugin extends BasePlugin implements Plugin, Comparable<Map<K, V>>", "java"),
+            vec!["BasePlugin", "Plugin", "Comparable"]
+        );
+        assert_eq!(
+            hierarchy.declared_supertypes("public class Repo<T> : BaseRepo, Shop.IRepository<T> where T : class", "csharp"),
+            vec!["BaseRepo", "IRepository"]
+        );
+        assert_eq!(
+            hierarchy.declared_supertypes("class Handler(BaseHandler, metaclass=ABCMeta)", "python"),
+            vec!["BaseHandler"]
+        );
+    }
+
+    #[test]
+    fn test_transitive_subtypes() {
+        let mut hierarchy = TypeHierarchy::default();
+        hierarchy.collect_rust_impl("impl<T: Clone> crate::notify::Notifier<T> for EmailNotifier<T>");
+        hierarchy.add_relation("Notifier", "Named");
+        hierarchy.add_relation("LoudEmailNotifier", "EmailNotifier");
+
+        assert_eq!(hierarchy.subtypes_of("Named"), vec!["Notifier", "EmailNotifier", "LoudEmailNotifier"]);
+        assert_eq!(hierarchy.supertypes_of("EmailNotifier"), vec!["Notifier", "Named"]);
+    }
+}
@@ -1,4 +1,5 @@
 // src/core/engine.rs
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 use anyhow::Result;
 use tracing::{info, warn, debug};
@@ -10,7 +11,10 @@ use super::{
     PackageAnalyzer, BatchProcessor, ContextScanner, PackageAnalysis,
     BatchDocumentationRequest, HumanContext, SystemContext, AnalysisFocus,
     FocusArea, DepthLevel, TargetAudience, HierarchicalAnalyzer,
-    SystemOverviewGenerator, CallChainEngine, CallChainAnalysisResult
+    SystemOverviewGenerator, CallChainEngine, CallChainAnalysisResult, CallGraph,
+    GraphExport, GraphFilter, ExportFormat, GraphQuery, GraphQueryRequest, EntryPointDetector,
+    TestCoverageAnalyzer, DeadCodeAnalyzer, GraphDiff, GraphSnapshot, GitTree, CallChainTracer,
+    CallChainGrouper, AnalysisStore, describe_effects
 };
 
 // Import the BatchDocumentationResponse specifically to avoid confusion
@@ -53,7 +57,7 @@ impl Engine {
         let system_overview_generator = SystemOverviewGenerator::new(context_window_limit);
 
         // Initialize call-chain engine with appropriate parameters
-        let call_chain_engine = CallChainEngine::new(6, context_window_limit); // 6 levels deep, full context window
+        let call_chain_engine = CallChainEngine::new(&config.analysis, context_window_limit, config.llm.budget);
 
         // Initialize LLM documenter if enabled
         let llm_documenter = if config.llm.enabled {
@@ -91,15 +95,29 @@ impl Engine {
 
     /// Generate initial documentation using call-chain analysis
     pub async fn generate(&mut self, source: Option<PathBuf>, output: Option<PathBuf>, force: bool) -> Result<()> {
-        let source_dir = source.unwrap_or_else(|| self.config.project.source_dirs[0].clone());
+        let source_dirs = self.source_dirs(source);
         let output_dir = output.unwrap_or_else(|| self.config.project.docs_dir.clone());
 
+        // Reuse earlier LLM results unless forced
+        let store = if force { None } else { self.load_store(&self.config.project.docs_dir) };
+        self.generate_with(source_dirs, output_dir, store, None).await
+    }
+
+    /// Analyze `source_dirs` into `output_dir`, reusing the LLM results of `store` wherever their
+    /// inputs are unchanged; with `changed_files`, only packages in groups touching one of them are re-enhanced
+    async fn generate_with(
+        &mut self,
+        source_dirs: Vec<PathBuf>,
+        output_dir: PathBuf,
+        store: Option<AnalysisStore>,
+        changed_files: Option<&BTreeSet<PathBuf>>,
+    ) -> Result<()> {
         info!("🔍 Starting call-chain analysis for comprehensive documentation...");
-        info!("Source: {}", source_dir.display());
+        info!("Source: {}", display_dirs(&source_dirs));
         info!("Output: {}", output_dir.display());
 
         // Step 1: Scan for human context
-        let project_root = source_dir.parent().unwrap_or(&source_dir);
+        let project_root = source_dirs[0].parent().unwrap_or(&source_dirs[0]);
         info!("📖 Scanning for human-authored context in {}", project_root.display());
         let human_context = self.context_scanner.scan_project_context(&project_root).await
             .map_err(|e| anyhow::anyhow!("Failed to scan project context: {}", e))?;
@@ -111,10 +129,13 @@ impl Engine {
             human_context.inline_comments.len()
         );
 
-        // Step 2: Perform comprehensive call-chain analysis
+        // Step 2: Perform comprehensive call-chain analysis, reusing earlier LLM results
+        if let Some(store) = store {
+            self.call_chain_engine.reuse_results(store);
+        }
         info!("🔗 Performing call-chain analysis...");
         let call_chain_result = self.call_chain_engine
-            .analyze_codebase(&source_dir, &mut self.parser, self.llm_documenter.as_deref())
+            .analyze_codebase(&source_dirs, &mut self.parser, self.llm_documenter.as_deref())
             .await
             .map_err(|e| anyhow::anyhow!("Call-chain analysis failed: {}", e))?;
 
@@ -136,14 +157,18 @@ impl Engine {
             .await
             .map_err(|e| anyhow::anyhow!("Failed to generate call-chain documentation: {}", e))?;
 
+        // Keep the analysis in the configured docs directory, where every later command looks for it
+        AnalysisStore::from_result(&call_chain_result).save(&self.config.project.docs_dir)?;
+
         // Step 4: Generate enhanced package documentation if LLM is available
+        let mut llm_calls_made = stats.llm_calls_made;
         if self.llm_documenter.is_some() {
             info!("🧠 Generating enhanced package documentation...");
-            self.generate_enhanced_package_docs(&call_chain_result, &human_context, &output_dir).await?;
+            llm_calls_made += self.generate_enhanced_package_docs(&call_chain_result, &human_context, &output_dir, llm_calls_made, changed_files).await?;
         }
 
         // Step 5: Generate comprehensive system overview
-        if self.llm_documenter.is_some() {
+        if self.llm_documenter.is_some() && self.llm_budget_remaining(llm_calls_made) != Some(0) {
             info!("📋 Generating comprehensive system overview...");
             self.generate_call_chain_system_overview(&call_chain_result, &human_context, &output_dir).await?;
         }
@@ -152,19 +177,31 @@ impl Engine {
         Ok(())
     }
 
-    /// Generate enhanced package documentation based on call-chain analysis
+    /// LLM calls left, or `None` when the budget is unlimited
+    fn llm_budget_remaining(&self, calls_made: usize) -> Option<usize> {
+        self.config.llm.budget.map(|budget| budget.saturating_sub(calls_made))
+    }
+
+    /// Generate enhanced package documentation based on call-chain analysis.
+    /// Returns the number of packages enhanced (one LLM call each).
     async fn generate_enhanced_package_docs(
         &self,
         call_chain_result: &CallChainAnalysisResult,
         human_context: &HumanContext,
         output_dir: &Path,
-    ) -> Result<()> {
+        llm_calls_made: usize,
+        changed_files: Option<&BTreeSet<PathBuf>>,
+    ) -> Result<usize> {
+        let mut packages_enhanced = 0;
         if let Some(ref llm) = self.llm_documenter {
             info!("🔍 Analyzing packages for enhanced documentation...");
 
             // Get unique packages from call chains
             let mut packages_to_analyze = std::collections::HashSet::new();
             for group in &call_chain_result.call_chain_groups {
+                if changed_files.is_some_and(|changed| !group.involved_files.iter().any(|file| changed.contains(file))) {
+                    continue;
+                }
                 for file_path in &group.involved_files {
                     if let Some(package_name) = self.extract_package_name_from_path(file_path) {
                         packages_to_analyze.insert(package_name);
@@ -174,8 +211,17 @@ impl Engine {
 
             info!("Found {} packages to enhance", packages_to_analyze.len());
 
+            // Most central packages first, so a limited LLM budget goes where it matters most
+            let centrality = &call_chain_result.centrality;
+            let mut packages_to_analyze: Vec<String> = packages_to_analyze.into_iter().collect();
+            packages_to_analyze.sort_by(|a, b| centrality.package_score(b).total_cmp(&centrality.package_score(a)).then(a.cmp(b)));
+
             // For each unique package, generate enhanced documentation
             for package_name in packages_to_analyze {
+                if self.llm_budget_remaining(llm_calls_made + packages_enhanced) == Some(0) {
+                    info!("LLM budget reached; remaining packages keep their generated documentation");
+                    break;
+                }
                 info!("Enhancing package: {}", package_name);
 
                 // Find all groups that involve this package
@@ -187,11 +233,12 @@ impl Engine {
                 if !relevant_groups.is_empty() {
                     self.generate_package_docs_from_groups(&package_name, &relevant_groups,
                                                            &call_chain_result.group_analyses, human_context, output_dir, llm.as_ref()).await?;
+                    packages_enhanced += 1;
                 }
             }
         }
 
-        Ok(())
+        Ok(packages_enhanced)
     }
 
     /// Generate package documentation from call-chain groups
@@ -260,7 +307,7 @@ impl Engine {
                     content.push_str("\n");
                 }
 
-                content.push_str(&format!("[View detailed call chain analysis](../groups/{}.md)\n\n", group.group_id));
+                content.push_str(&format!("[View detailed call chain analysis](../groups/{}.md)\n\n", group.slug));
             }
         }
 
@@ -393,24 +440,7 @@ impl Engine {
     // Helper methods for call-chain integration
 
     fn extract_package_name_from_path(&self, file_path: &PathBuf) -> Option<String> {
-        let path_str = file_path.to_string_lossy();
-
-        // Try to extract package name using similar logic as PackageAnalyzer
-        if let Some(src_index) = path_str.find("/src/") {
-            let after_src = &path_str[src_index + 5..];
-            if let Some(first_slash) = after_src.find('/') {
-                let package = &after_src[..first_slash];
-                if package != "bin" && package != "test" && package != "tests" {
-                    return Some(package.to_string());
-                }
-            }
-        }
-
-        // Fallback to parent directory name
-        file_path.parent()
-            .and_then(|p| p.file_name())
-            .and_then(|name| name.to_str())
-            .map(|s| s.to_string())
+        super::call_graph::package_name_for_path(file_path)
     }
 
     fn build_package_overview_prompt(
@@ -502,6 +532,7 @@ impl Engine {
                 modules: vec![],
                 file_docs: None,
                 source_content: "".to_string(),
+                calls: vec![],
             },
             target_module: None,
             related_files: vec![],
@@ -527,6 +558,7 @@ impl Engine {
                 modules: vec![],
                 file_docs: None,
                 source_content: "".to_string(),
+                calls: vec![],
             },
             target_module: None,
             related_files: vec![],
@@ -548,22 +580,173 @@ impl Engine {
         Ok(())
     }
 
-    pub async fn sync(&self, dry_run: bool, fail_on_changes: bool) -> Result<()> {
+    /// Regenerate the documentation if the source changed since the stored analysis,
+    /// re-analyzing only the groups and packages the changed files take part in
+    pub async fn sync(&mut self, dry_run: bool, fail_on_changes: bool) -> Result<()> {
         info!("🔄 Synchronizing documentation with call-chain analysis...");
 
         if dry_run {
             info!("📋 Dry run mode - showing what would be updated");
         }
 
-        // TODO: Implement sync with call-chain approach
-        // This would re-run call-chain analysis and update only changed documentation
+        let docs_dir = self.config.project.docs_dir.clone();
+        let Some(store) = AnalysisStore::load(&docs_dir)? else {
+            if fail_on_changes {
+                return Err(CodesworthError::Validation(format!(
+                    "No stored analysis in {}; run `codesworth generate` first", docs_dir.display()
+                )).into());
+            }
+            info!("No stored analysis in {}; generating all documentation", docs_dir.display());
+            return if dry_run { Ok(()) } else { self.generate(None, Some(docs_dir), false).await };
+        };
+
+        let changes = store.changes_since(&self.parser.hash_directories(&store.source_dirs)?);
+        if changes.is_empty() {
+            info!("✅ Documentation is up to date");
+            return Ok(());
+        }
+
+        for (label, files) in [("added", &changes.added), ("modified", &changes.modified), ("removed", &changes.removed)] {
+            for file in files {
+                info!("  {} {}", label, file.display());
+            }
+        }
+        if fail_on_changes {
+            return Err(CodesworthError::Validation(format!(
+                "{} source file(s) changed since the documentation was generated", changes.total()
+            )).into());
+        }
+        if dry_run {
+            return Ok(());
+        }
+
+        let changed_files: BTreeSet<PathBuf> = changes.added.into_iter()
+            .chain(changes.modified)
+            .chain(changes.removed)
+            .collect();
+        let source_dirs = store.source_dirs.clone();
+        self.generate_with(source_dirs, docs_dir, Some(store), Some(&changed_files)).await
+    }
+
+    /// Report how current the stored analysis is
+    pub async fn status(&self) -> Result<()> {
+        let docs_dir = &self.config.project.docs_dir;
+        let Some(store) = AnalysisStore::load(docs_dir)? else {
+            println!("No stored analysis in {}; run `codesworth generate`", docs_dir.display());
+            return Ok(());
+        };
+
+        println!("Analysis of {} from {} (codesworth {}, schema {})",
+                 display_dirs(&store.source_dirs), store.generated_at, store.generator_version, store.schema_version);
+        println!("  {} files, {} functions, {} calls, {} entry points, {} groups ({} analyzed by the LLM)",
+                 store.file_hashes.len(), store.call_graph.nodes.len(), store.call_graph.edges.len(),
+                 store.entry_points.len(), store.call_chain_groups.len(),
+                 store.group_analyses.iter().filter(|analysis| !analysis.input_hash.is_empty()).count());
+
+        let changes = store.changes_since(&self.parser.hash_directories(&store.source_dirs)?);
+        if changes.is_empty() {
+            println!("Up to date");
+            return Ok(());
+        }
+
+        println!("{} file(s) changed since; run `codesworth sync` to update:", changes.total());
+        for (marker, files) in [("+", &changes.added), ("~", &changes.modified), ("-", &changes.removed)] {
+            for file in files {
+                println!("  {} {}", marker, file.display());
+            }
+        }
+
+        let changed: std::collections::HashSet<&PathBuf> = changes.modified.iter().chain(&changes.removed).collect();
+        let stale: Vec<&str> = store.call_chain_groups.iter()
+            .filter(|group| group.involved_files.iter().any(|file| changed.contains(file)))
+            .map(|group| group.name.as_str())
+            .collect();
+        if !stale.is_empty() {
+            println!("Groups to re-analyze: {}", stale.join(", "));
+        }
+
+        Ok(())
+    }
+
+    /// Explain a function from the stored analysis: the groups it takes part in and what they do
+    pub async fn explain(&self, function: &str) -> Result<()> {
+        let docs_dir = &self.config.project.docs_dir;
+        let store = AnalysisStore::load(docs_dir)?.ok_or_else(|| CodesworthError::Store(format!(
+            "No stored analysis in {}; run `codesworth generate` first", docs_dir.display()
+        )))?;
+        if !store.changes_since(&self.parser.hash_directories(&store.source_dirs)?).is_empty() {
+            warn!("The source changed since the stored analysis; run `codesworth sync` for an up-to-date answer");
+        }
+
+        let query = GraphQuery::new(&store.call_graph);
+        for method in query.resolve(function)? {
+            let node = &store.call_graph.nodes[method];
+            println!("{} ({}:{})", method.display_name(), method.file_path.display(), node.line_range.0);
+            if let Some(docs) = &node.documentation {
+                println!("  {}", docs.lines().next().unwrap_or_default());
+            }
+            for route in store.entry_points.iter().filter(|ep| ep.signature == *method).flat_map(|ep| &ep.routes) {
+                println!("  Handles {} ({})", route.endpoint(), route.framework);
+            }
+            let effects = store.side_effects.effects_of(method);
+            if !effects.is_empty() {
+                println!("  Side effects: {}", describe_effects(&effects));
+            }
+            for mode in store.failure_modes.of_entry_point(method).iter().filter(|mode| mode.reaches_entry_point) {
+                println!("  Can fail with {} from {} (line {})", mode.error_type, mode.origin.display_name(), mode.line);
+            }
+            for site in store.concurrency.sites.get(method).into_iter().flatten() {
+                let warning = if site.held_across_await { ", held across an await" } else { "" };
+                println!("  Concurrency: {} `{}` (line {}{})", site.kind.label(), site.construct, site.line, warning);
+            }
+
+            let groups: Vec<_> = store.call_chain_groups.iter()
+                .filter(|group| group.all_methods.contains(method))
+                .collect();
+            if groups.is_empty() {
+                println!("  Not reached from any entry point");
+            }
+            for group in groups {
+                println!("\n  Part of {} ({})", group.name, docs_dir.join("groups").join(format!("{}.md", group.slug)).display());
+                let Some(analysis) = store.group_analyses.iter().find(|analysis| analysis.group_id == group.group_id) else {
+                    continue;
+                };
+                println!("  {}", analysis.description);
+                if let Some(purpose) = analysis.entry_point_descriptions.get(method) {
+                    println!("  As an entry point: {}", purpose);
+                }
+                for gotcha in &analysis.gotchas {
+                    println!("  ⚠ {:?}: {}", gotcha.severity, gotcha.description);
+                }
+            }
+            println!();
+        }
+
         Ok(())
     }
 
-    pub async fn validate(&self, strict: bool) -> Result<()> {
+    pub async fn validate(&mut self, strict: bool) -> Result<()> {
         info!("✅ Validating documentation...");
 
-        let result = self.validator.validate_all(&self.config.project.docs_dir, strict).await?;
+        let mut result = self.validator.validate_all(&self.config.project.docs_dir, strict).await?;
+
+        let call_graph = self.build_call_graph(None).await?;
+
+        // Compare recursion against the call graph recorded at the last generate
+        let previous_graph = self.config.project.docs_dir.join("call_graph.json");
+        result.warnings.extend(
+            self.validator.check_recursive_clusters(&previous_graph, &call_graph.recursive_clusters)?
+        );
+
+        // Flag code nothing in production can reach
+        let entry_points = EntryPointDetector::new().detect_entry_points(&call_graph)?;
+        let coverage = TestCoverageAnalyzer::new().analyze(&call_graph, &entry_points);
+        let dead_code = DeadCodeAnalyzer::new().analyze(&call_graph, &entry_points, &coverage);
+        result.warnings.extend(self.validator.check_dead_code(&dead_code));
+
+        for warning in &result.warnings {
+            warn!("  ⚠️ {}", warning);
+        }
 
         if result.is_valid() {
             info!("✅ Documentation validation passed");
@@ -580,6 +763,169 @@ impl Engine {
         Ok(())
     }
 
+    /// Export the call graph for Graphviz, Mermaid, Gephi or Cytoscape.js
+    pub async fn export_graph(
+        &mut self,
+        source: Option<PathBuf>,
+        format: &str,
+        output: Option<PathBuf>,
+        filter: GraphFilter,
+    ) -> Result<()> {
+        let format = ExportFormat::parse(format)?;
+        let call_graph = self.build_call_graph(source).await?;
+        let export = GraphExport::new(&call_graph, &filter)?;
+
+        let output_path = output.unwrap_or_else(|| {
+            self.config.project.docs_dir.join(format!("call_graph.{}", format.extension()))
+        });
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&output_path, export.render(format)?)?;
+
+        info!("📤 Exported {} nodes and {} edges to {}",
+              export.node_count(), export.edge_count(), output_path.display());
+        Ok(())
+    }
+
+    /// Answer a callers/callees/path/reachability/impact question about the call graph
+    pub async fn query_graph(&mut self, source: Option<PathBuf>, request: GraphQueryRequest, json: bool) -> Result<()> {
+        let (call_graph, entry_points) = match self.fresh_store(source.as_deref()) {
+            Some(store) => (store.call_graph, store.entry_points),
+            None => {
+                let call_graph = self.build_call_graph(source).await?;
+                let entry_points = match request {
+                    GraphQueryRequest::Impact { .. } => EntryPointDetector::new().detect_entry_points(&call_graph)?,
+                    _ => Vec::new(),
+                };
+                (call_graph, entry_points)
+            }
+        };
+
+        let output = GraphQuery::new(&call_graph).run(&request, &entry_points)?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        } else {
+            print!("{}", output.to_text());
+        }
+
+        Ok(())
+    }
+
+    /// Report how functions, calls, entry points, chains, groups and cycles changed between two revisions
+    pub async fn diff_graph(
+        &mut self,
+        base: &str,
+        head: &str,
+        source: Option<PathBuf>,
+        json: bool,
+        output: Option<PathBuf>,
+    ) -> Result<()> {
+        let base = self.snapshot_revision(base, source.as_deref()).await?;
+        let head = self.snapshot_revision(head, source.as_deref()).await?;
+        let diff = GraphDiff::compute(&base, &head);
+
+        let report = if json {
+            serde_json::to_string_pretty(&diff)? + "\n"
+        } else {
+            diff.to_markdown()
+        };
+
+        match output {
+            Some(path) => {
+                std::fs::write(&path, report)?;
+                info!("📤 Wrote call graph diff to {}", path.display());
+            }
+            None => print!("{}", report),
+        }
+
+        Ok(())
+    }
+
+    /// Extract a revision from git and analyze it the way `generate` would
+    async fn snapshot_revision(&mut self, revision: &str, source: Option<&Path>) -> Result<GraphSnapshot> {
+        info!("🔀 Analyzing {}...", revision);
+        let tree = GitTree::checkout(revision)?;
+
+        let source_dirs = match source {
+            Some(dir) => vec![tree.source_dir(dir)?],
+            None => self.config.project.source_dirs.iter()
+                .map(|dir| tree.source_dir(dir))
+                .collect::<std::result::Result<Vec<_>, _>>()?,
+        };
+        let call_graph = self.build_call_graph_from(&source_dirs).await?;
+
+        let entry_points = EntryPointDetector::new().detect_entry_points(&call_graph)?;
+        let call_chains = CallChainTracer::new(&self.config.analysis).trace_all_chains(&call_graph, &entry_points)?;
+        let groups = CallChainGrouper::new(&self.config.analysis).group_call_chains(call_chains.clone(), &call_graph.http_routes)?;
+
+        Ok(GraphSnapshot {
+            revision: tree.revision().to_string(),
+            root: tree.root().to_path_buf(),
+            call_graph,
+            entry_points,
+            call_chains,
+            groups,
+        })
+    }
+
+    /// The directories to analyze: `source` when given, otherwise every configured one
+    fn source_dirs(&self, source: Option<PathBuf>) -> Vec<PathBuf> {
+        match source {
+            Some(dir) => vec![dir],
+            None => self.config.project.source_dirs.clone(),
+        }
+    }
+
+    /// The stored analysis, if there is one, it was made of exactly the directories
+    /// `source` selects and no file in them has changed since
+    fn fresh_store(&self, source: Option<&Path>) -> Option<AnalysisStore> {
+        let store = self.load_store(&self.config.project.docs_dir)?;
+        if store.source_dirs != self.source_dirs(source.map(Path::to_path_buf)) {
+            return None;
+        }
+
+        let file_hashes = self.parser.hash_directories(&store.source_dirs).ok()?;
+        if !store.changes_since(&file_hashes).is_empty() {
+            debug!("Stored analysis is out of date; re-analyzing");
+            return None;
+        }
+
+        info!("Using the stored analysis from {}", store.generated_at);
+        Some(store)
+    }
+
+    /// The stored analysis in a documentation directory; an unreadable store is reported and ignored
+    fn load_store(&self, docs_dir: &Path) -> Option<AnalysisStore> {
+        match AnalysisStore::load(docs_dir) {
+            Ok(store) => store,
+            Err(e) => {
+                warn!("Ignoring the stored analysis in {}: {}", docs_dir.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Parse the given source directory (or all configured ones) and build its call graph,
+    /// or take it from the stored analysis when nothing changed
+    async fn build_call_graph(&mut self, source: Option<PathBuf>) -> Result<CallGraph> {
+        if let Some(store) = self.fresh_store(source.as_deref()) {
+            return Ok(store.call_graph);
+        }
+
+        let source_dirs = self.source_dirs(source);
+        self.build_call_graph_from(&source_dirs).await
+    }
+
+    async fn build_call_graph_from(&mut self, source_dirs: &[PathBuf]) -> Result<CallGraph> {
+        let mut parsed_files = Vec::new();
+        for source_dir in source_dirs {
+            parsed_files.extend(self.parser.parse_directory(source_dir).await?);
+        }
+
+        Ok(CallGraph::build_from_files(&parsed_files)?)
+    }
+
     pub async fn publish(&self, format: &str, output: Option<PathBuf>) -> Result<()> {
         info!("📤 Publishing documentation in {} format", format);
 
@@ -610,4 +956,9 @@ impl Engine {
 
     // Remove the problematic method
     // Call chain grouper methods are accessed directly when needed
-}
\ No newline at end of file
+}
+
+/// Comma-separated list of directories, for messages
+fn display_dirs(dirs: &[PathBuf]) -> String {
+    dirs.iter().map(|dir| dir.display().to_string()).collect::<Vec<_>>().join(", ")
+}
@@ -0,0 +1,95 @@
+// src/core/git_tree.rs
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::error::{CodesworthError, Result};
+
+static CHECKOUTS: AtomicUsize = AtomicUsize::new(0);
+
+/// A git revision extracted into a temporary directory, removed again on drop
+pub struct GitTree {
+    revision: String,
+    root: PathBuf,
+    /// Path of the current directory relative to the repository root
+    prefix: PathBuf,
+}
+
+impl GitTree {
+    /// Extract `revision` of the repository containing the current directory
+    pub fn checkout(revision: &str) -> Result<Self> {
+        let toplevel = PathBuf::from(git(&["rev-parse", "--show-toplevel"])?);
+        let prefix = PathBuf::from(git(&["rev-parse", "--show-prefix"])?);
+        let commit = git(&["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", revision)])
+            .map_err(|_| CodesworthError::Git(format!("Unknown revision: {}", revision)))?;
+
+        let root = std::env::temp_dir().join(format!(
+            "codesworth-{}-{}",
+            std::process::id(),
+            CHECKOUTS.fetch_add(1, Ordering::Relaxed)
+        ));
+        if root.exists() {
+            std::fs::remove_dir_all(&root)?;
+        }
+        std::fs::create_dir_all(&root)?;
+        let tree = Self { revision: revision.to_string(), root, prefix };
+
+        // `git archive | tar -x` leaves the index and working tree alone
+        let mut archive = Command::new("git")
+            .args(["archive", "--format=tar", &commit])
+            .current_dir(&toplevel)
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let archive_output = archive.stdout.take()
+            .ok_or_else(|| CodesworthError::Git("Failed to read git archive output".to_string()))?;
+        let extracted = Command::new("tar")
+            .arg("-x")
+            .arg("-C")
+            .arg(&tree.root)
+            .stdin(archive_output)
+            .status()?;
+
+        if !archive.wait()?.success() || !extracted.success() {
+            return Err(CodesworthError::Git(format!("Failed to extract {}", revision)));
+        }
+
+        Ok(tree)
+    }
+
+    pub fn revision(&self) -> &str {
+        &self.revision
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Where a source directory of the working tree lives in this checkout
+    pub fn source_dir(&self, source_dir: &Path) -> Result<PathBuf> {
+        if source_dir.is_absolute() {
+            let toplevel = PathBuf::from(git(&["rev-parse", "--show-toplevel"])?);
+            let canonical = source_dir.canonicalize().unwrap_or_else(|_| source_dir.to_path_buf());
+            let relative = canonical.strip_prefix(&toplevel).map_err(|_| {
+                CodesworthError::Git(format!("{} is outside the git repository", source_dir.display()))
+            })?;
+            Ok(self.root.join(relative))
+        } else {
+            Ok(self.root.join(&self.prefix).join(source_dir))
+        }
+    }
+}
+
+impl Drop for GitTree {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
+
+/// Run a git command in the current directory and return its trimmed output
+fn git(args: &[&str]) -> Result<String> {
+    let output = Command::new("git").args(args).output()?;
+    if !output.status.success() {
+        return Err(CodesworthError::Git(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
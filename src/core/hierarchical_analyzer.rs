@@ -817,6 +817,7 @@ impl HierarchicalAnalyzer {
                 modules: vec![],
                 file_docs: None,
                 source_content: "".to_string(),
+                calls: vec![],
             },
             target_module: None,
             related_files: vec![],
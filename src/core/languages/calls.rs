@@ -0,0 +1,604 @@
+//! Call extraction from Tree-sitter syntax trees
+//!
+//! Each language describes which node kinds are calls, member accesses and
+//! control-flow constructs in a [`CallSyntax`] table; the walker is shared.
+
+use tree_sitter::Node;
+
+use super::super::CallType;
+use super::ParsedCall;
+
+/// Node kinds and field names that describe calls for one language
+pub struct CallSyntax {
+    /// Call kinds with the field holding the called expression. `None` when the call
+    /// node itself carries the object/name fields (Java `method_invocation`)
+    pub call_kinds: &'static [(&'static str, Option<&'static str>)],
+    /// Object creation kinds (`new Foo()`) with the field holding the type
+    pub constructor_kinds: &'static [(&'static str, &'static str)],
+    /// Name constructors are declared under; `None` when they are named after the type
+    pub constructor_name: Option<&'static str>,
+    /// Blocks that hold a lock (`synchronized (x)`, `lock (x)`, `with x:`) with their keyword;
+    /// each is recorded as a call to the keyword on the locked object
+    pub lock_kinds: &'static [(&'static str, &'static str)],
+    /// Field of the call node holding the argument list
+    pub arguments_field: &'static str,
+    /// Member access kinds as (kind, object field, member field); an empty object
+    /// field means the receiver is implicit (C# `a?.Foo()`)
+    pub member_kinds: &'static [(&'static str, &'static str, &'static str)],
+    /// Wrapper kinds around the called expression as (kind, inner field), e.g. turbofish;
+    /// an empty field means the last named child (C# `a?.Foo` conditional access)
+    pub wrapper_kinds: &'static [(&'static str, &'static str)],
+    /// Kinds whose operand is awaited
+    pub await_kinds: &'static [&'static str],
+    /// Kinds whose operand propagates errors (Rust `?`)
+    pub try_operator_kinds: &'static [&'static str],
+    /// Branching constructs (if, match, switch, ternary)
+    pub conditional_kinds: &'static [&'static str],
+    /// Looping constructs, including comprehensions
+    pub loop_kinds: &'static [&'static str],
+    /// try/catch style blocks
+    pub try_kinds: &'static [&'static str],
+    /// Closures and lambdas; calls inside them run as callbacks
+    pub callback_kinds: &'static [&'static str],
+    /// Parents that turn a closure into a named definition (`const f = () => ...`)
+    pub definition_parents: &'static [&'static str],
+    /// Whether functions passed by name (`list.forEach(handle)`) count as callback calls
+    pub function_references: bool,
+    /// Assignment kinds where `+=` subscribes a handler to an event
+    pub subscription_kinds: &'static [&'static str],
+    /// Function definitions, which reset the context
+    pub function_kinds: &'static [&'static str],
+    /// Fields of conditional/loop nodes that are evaluated unconditionally
+    /// (if condition, match subject, for iterable)
+    pub header_fields: &'static [&'static str],
+    /// Unparsed macro token trees that are scanned for calls (Rust)
+    pub token_tree_kinds: &'static [&'static str],
+}
+
+/// Context a node is evaluated in: the innermost call type and the one that
+/// applies once we leave the current await/`?` operand
+#[derive(Clone)]
+struct CallContext {
+    current: CallType,
+    enclosing: CallType,
+}
+
+impl CallSyntax {
+    /// Extract every call under `root`, in source order
+    pub fn extract_calls(&self, root: Node, source: &str) -> Vec<ParsedCall> {
+        let mut calls = Vec::new();
+        let context = CallContext {
+            current: CallType::Direct,
+            enclosing: CallType::Direct,
+        };
+        self.walk(root, source, &context, &mut calls);
+        calls
+    }
+
+    fn walk(&self, node: Node, source: &str, context: &CallContext, calls: &mut Vec<ParsedCall>) {
+        if self.token_tree_kinds.contains(&node.kind()) {
+            self.extract_token_tree_calls(node, source, &context.current, calls);
+            return;
+        }
+
+        let call_field = self.call_kinds.iter()
+            .find(|(kind, _)| *kind == node.kind())
+            .map(|(_, field)| *field);
+        let constructor_field = self.constructor_kinds.iter()
+            .find(|(kind, _)| *kind == node.kind())
+            .map(|(_, field)| *field);
+        let is_call = call_field.is_some() || constructor_field.is_some();
+
+        if let Some(function_field) = call_field {
+            if let Some(call) = self.parse_call(node, function_field, source, &context.current) {
+                calls.push(call);
+            }
+        }
+        if let Some(type_field) = constructor_field {
+            if let Some(call) = self.parse_constructor_call(node, type_field, source, &context.current) {
+                calls.push(call);
+            }
+        }
+
+        if let Some((_, keyword)) = self.lock_kinds.iter().find(|(kind, _)| *kind == node.kind()) {
+            if let Some(call) = self.parse_lock_block(node, keyword, source, &context.current) {
+                calls.push(call);
+            }
+        }
+
+        if self.function_references {
+            if is_call {
+                if let Some(arguments) = node.child_by_field_name(self.arguments_field) {
+                    self.extract_function_references(arguments, source, calls);
+                }
+            } else if self.is_subscription(node, source) {
+                if let Some(handler) = node.child_by_field_name("right") {
+                    self.push_function_reference(handler, source, calls);
+                }
+            }
+        }
+
+        let mut cursor = node.walk();
+        if !cursor.goto_first_child() {
+            return;
+        }
+
+        loop {
+            let child_context = self.child_context(node, cursor.field_name(), is_call, context);
+            self.walk(cursor.node(), source, &child_context, calls);
+
+            if !cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    /// Work out the context for a child of `parent` reached through `field`
+    fn child_context(&self, parent: Node, field: Option<&str>, parent_is_call: bool, context: &CallContext) -> CallContext {
+        let kind = parent.kind();
+        let is_header = field.is_some_and(|field| self.header_fields.contains(&field));
+
+        let entered = if self.function_kinds.contains(&kind) {
+            Some(CallType::Direct)
+        } else if self.callback_kinds.contains(&kind) {
+            let is_definition = parent.parent()
+                .is_some_and(|grandparent| self.definition_parents.contains(&grandparent.kind()));
+            Some(if is_definition { CallType::Direct } else { CallType::Callback })
+        } else if self.try_kinds.contains(&kind) {
+            Some(CallType::Try)
+        } else if self.loop_kinds.contains(&kind) && !is_header {
+            Some(CallType::Loop)
+        } else if self.conditional_kinds.contains(&kind) && !is_header {
+            Some(CallType::Conditional)
+        } else {
+            None
+        };
+
+        if let Some(call_type) = entered {
+            return CallContext {
+                current: call_type.clone(),
+                enclosing: call_type,
+            };
+        }
+
+        // await and `?` only apply to their operand, not to calls nested in its arguments
+        if self.await_kinds.contains(&kind) {
+            return CallContext {
+                current: CallType::Async,
+                enclosing: context.enclosing.clone(),
+            };
+        }
+        if self.try_operator_kinds.contains(&kind) {
+            return CallContext {
+                current: CallType::Try,
+                enclosing: context.enclosing.clone(),
+            };
+        }
+        if parent_is_call {
+            return CallContext {
+                current: context.enclosing.clone(),
+                enclosing: context.enclosing.clone(),
+            };
+        }
+
+        context.clone()
+    }
+
+    /// Build a call record from a call node
+    fn parse_call(&self, node: Node, function_field: Option<&str>, source: &str, call_type: &CallType) -> Option<ParsedCall> {
+        let target = match function_field {
+            Some(field) => node.child_by_field_name(field)?,
+            None => node,
+        };
+
+        let (name, receiver) = self.callee_parts(target, source)?;
+
+        let arg_count = node.child_by_field_name(self.arguments_field)
+            .map(|arguments| self.count_arguments(arguments))
+            .unwrap_or(0);
+
+        Some(ParsedCall {
+            callee: strip_type_arguments(&node_text(name, source)),
+            receiver: receiver.map(|receiver| node_text(receiver, source)),
+            arg_count,
+            line: name.start_position().row + 1,
+            call_type: call_type.clone(),
+            is_reference: false,
+        })
+    }
+
+    /// Build a call record for `new Foo(...)`, with the type as receiver
+    fn parse_constructor_call(&self, node: Node, type_field: &str, source: &str, call_type: &CallType) -> Option<ParsedCall> {
+        let (type_name, _) = self.callee_parts(node.child_by_field_name(type_field)?, source)?;
+        let type_name_text = strip_type_arguments(&node_text(type_name, source));
+
+        let arg_count = node.child_by_field_name(self.arguments_field)
+            .map(|arguments| self.count_arguments(arguments))
+            .unwrap_or(0);
+
+        Some(ParsedCall {
+            callee: self.constructor_name.map(str::to_string).unwrap_or_else(|| type_name_text.clone()),
+            receiver: Some(type_name_text),
+            arg_count,
+            line: type_name.start_position().row + 1,
+            call_type: call_type.clone(),
+            is_reference: false,
+        })
+    }
+
+    /// Build a call record for a lock block, with the locked object as receiver
+    fn parse_lock_block(&self, node: Node, keyword: &str, source: &str, call_type: &CallType) -> Option<ParsedCall> {
+        // Unwrap `(lock)` and Python's with clause, item and `as` pattern down to the locked expression
+        let mut object = node.named_child(0)?;
+        while matches!(object.kind(), "parenthesized_expression" | "with_clause" | "with_item" | "as_pattern") {
+            object = object.child_by_field_name("value").or_else(|| object.named_child(0))?;
+        }
+
+        // `async with lock:` acquires the lock by awaiting it
+        let is_async = node.child(0).is_some_and(|first| first.kind() == "async");
+
+        Some(ParsedCall {
+            callee: keyword.to_string(),
+            receiver: Some(node_text(object, source)),
+            arg_count: 0,
+            line: node.start_position().row + 1,
+            call_type: if is_async { CallType::Async } else { call_type.clone() },
+            is_reference: false,
+        })
+    }
+
+    /// Record functions passed by name as arguments
+    fn extract_function_references(&self, arguments: Node, source: &str, calls: &mut Vec<ParsedCall>) {
+        let mut cursor = arguments.walk();
+        for argument in arguments.named_children(&mut cursor) {
+            // C# wraps each argument (possibly named) in an `argument` node
+            let value = if argument.kind() == "argument" {
+                argument.named_child(argument.named_child_count().saturating_sub(1))
+            } else {
+                Some(argument)
+            };
+
+            if let Some(value) = value {
+                self.push_function_reference(value, source, calls);
+            }
+        }
+    }
+
+    /// Record `handler` or `this.handler` as a callback call
+    fn push_function_reference(&self, value: Node, source: &str, calls: &mut Vec<ParsedCall>) {
+        let reference = match value.kind() {
+            "identifier" => Some((value, None)),
+            kind => self.member_kinds.iter()
+                .find(|(member_kind, _, _)| *member_kind == kind)
+                .and_then(|(_, object_field, member_field)| {
+                    let object = value.child_by_field_name(object_field)?;
+                    let name = value.child_by_field_name(member_field)?;
+                    (node_text(object, source) == "this").then_some((name, Some(object)))
+                }),
+        };
+
+        if let Some((name, receiver)) = reference {
+            calls.push(ParsedCall {
+                callee: node_text(name, source),
+                receiver: receiver.map(|receiver| node_text(receiver, source)),
+                arg_count: 0,
+                line: name.start_position().row + 1,
+                call_type: CallType::Callback,
+                is_reference: true,
+            });
+        }
+    }
+
+    /// Whether a node is an event subscription (`button.Click += OnClick`)
+    fn is_subscription(&self, node: Node, source: &str) -> bool {
+        self.subscription_kinds.contains(&node.kind())
+            && node.child_by_field_name("operator").is_some_and(|operator| node_text(operator, source) == "+=")
+    }
+
+    /// Split a called expression into its name node and optional receiver node
+    fn callee_parts<'a>(&self, target: Node<'a>, source: &str) -> Option<(Node<'a>, Option<Node<'a>>)> {
+        let kind = target.kind();
+
+        if let Some((_, object_field, member_field)) = self.member_kinds.iter().find(|(k, _, _)| *k == kind) {
+            let name = target.child_by_field_name(member_field)?;
+            let object = Some(*object_field)
+                .filter(|field| !field.is_empty())
+                .and_then(|field| target.child_by_field_name(field));
+            return Some((name, object));
+        }
+
+        if let Some((_, inner_field)) = self.wrapper_kinds.iter().find(|(k, _)| *k == kind) {
+            let inner = if inner_field.is_empty() {
+                target.named_child(target.named_child_count().checked_sub(1)?)
+            } else {
+                target.child_by_field_name(inner_field)
+            };
+            return self.callee_parts(inner?, source);
+        }
+
+        // Plain identifiers; anything more complex (calling a call result, an index...) is skipped
+        if target.named_child_count() == 0 && !node_text(target, source).trim().is_empty() {
+            Some((target, None))
+        } else {
+            None
+        }
+    }
+
+    fn count_arguments(&self, arguments: Node) -> usize {
+        let mut cursor = arguments.walk();
+        let count = arguments.named_children(&mut cursor)
+            .filter(|argument| !argument.kind().contains("comment"))
+            .count();
+        count
+    }
+
+    /// Macro arguments are not parsed into expressions, so look for
+    /// `name(...)`, `recv.name(...)` and `Path::name(...)` token sequences
+    fn extract_token_tree_calls(&self, node: Node, source: &str, call_type: &CallType, calls: &mut Vec<ParsedCall>) {
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.children(&mut cursor).collect();
+
+        for (index, child) in children.iter().enumerate() {
+            if !self.token_tree_kinds.contains(&child.kind()) {
+                continue;
+            }
+
+            let opens_with_paren = child.child(0).is_some_and(|open| open.kind() == "(");
+            let name = index.checked_sub(1).map(|i| children[i]);
+
+            if let Some(name) = name.filter(|name| opens_with_paren && name.kind() == "identifier") {
+                let receiver = index.checked_sub(3)
+                    .filter(|&i| matches!(children[i + 1].kind(), "." | "::"))
+                    .map(|i| children[i])
+                    .filter(|receiver| matches!(receiver.kind(), "identifier" | "self"));
+
+                calls.push(ParsedCall {
+                    callee: node_text(name, source),
+                    receiver: receiver.map(|receiver| node_text(receiver, source)),
+                    arg_count: self.count_token_tree_arguments(*child),
+                    line: name.start_position().row + 1,
+                    call_type: call_type.clone(),
+                    is_reference: false,
+                });
+            }
+
+            self.extract_token_tree_calls(*child, source, call_type, calls);
+        }
+    }
+
+    /// Count top-level comma separated arguments inside a `( ... )` token tree
+    fn count_token_tree_arguments(&self, tree: Node) -> usize {
+        // Children are the delimiters plus the tokens between them
+        let inner = tree.child_count().saturating_sub(2);
+        if inner == 0 {
+            return 0;
+        }
+
+        let mut cursor = tree.walk();
+        let commas = tree.children(&mut cursor).filter(|token| token.kind() == ",").count();
+        let trailing_comma = tree.child(tree.child_count() - 2).is_some_and(|token| token.kind() == ",");
+
+        commas + 1 - usize::from(trailing_comma)
+    }
+}
+
+fn node_text(node: Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+/// `Convert<int>` -> `Convert`
+fn strip_type_arguments(name: &str) -> String {
+    name.split('<').next().unwrap_or(name).trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{CSharpParser, JavaParser, JavaScriptParser, LanguageParser, PythonParser, RustParser};
+    use super::*;
+
+    fn call<'a>(calls: &'a [ParsedCall], callee: &str) -> &'a ParsedCall {
+        calls.iter().find(|call| call.callee == callee).unwrap()
+    }
+
+    #[test]
+    fn test_rust_call_context() {
+        let source = r#"
+async fn run(client: &Client) -> Result<()> {
+    // helper() in a comment is not a call
+    let label = "fetch() in a string";
+    if ready(client) {
+        start(1, 2);
+    }
+    for item in items() {
+        client.process(item);
+    }
+    let body = client
+        .fetch(label)
+        .await?;
+    let values = body.iter().map(|v| convert(v)).collect();
+    parse(&values)?;
+    assert_eq!(Store::lookup(1, "a"), None);
+    Ok(())
+}
+"#;
+        let mut parser = RustParser::new().unwrap();
+        let calls = parser.extract_calls(source).unwrap();
+
+        assert!(calls.iter().all(|call| call.callee != "helper"));
+        assert!(matches!(call(&calls, "ready").call_type, CallType::Direct));
+        assert!(matches!(call(&calls, "start").call_type, CallType::Conditional));
+        assert_eq!(call(&calls, "start").arg_count, 2);
+        assert!(matches!(call(&calls, "items").call_type, CallType::Direct));
+        assert!(matches!(call(&calls, "process").call_type, CallType::Loop));
+        assert_eq!(call(&calls, "process").receiver.as_deref(), Some("client"));
+
+        let fetch = call(&calls, "fetch");
+        assert!(matches!(fetch.call_type, CallType::Async));
+        assert_eq!(fetch.line, 12);
+
+        assert!(matches!(call(&calls, "convert").call_type, CallType::Callback));
+        assert!(matches!(call(&calls, "parse").call_type, CallType::Try));
+
+        let lookup = call(&calls, "lookup");
+        assert_eq!(lookup.receiver.as_deref(), Some("Store"));
+        assert_eq!(lookup.arg_count, 2);
+    }
+
+    #[test]
+    fn test_python_call_context() {
+        let source = r#"
+async def handle(self, request):
+    try:
+        user = await self.repo.load(request.id)
+    except KeyError:
+        log_missing(request)
+    names = [format_name(u) for u in users]
+    with self._lock:
+        self.cache[user.id] = user
+    async with self.pool.acquire() as conn:
+        await conn.store(user)
+    return render(user, names)
+"#;
+        let mut parser = PythonParser::new().unwrap();
+        let calls = parser.extract_calls(source).unwrap();
+
+        let load = call(&calls, "load");
+        assert!(matches!(load.call_type, CallType::Async));
+        assert_eq!(load.receiver.as_deref(), Some("self.repo"));
+        assert!(matches!(call(&calls, "log_missing").call_type, CallType::Try));
+        assert!(matches!(call(&calls, "format_name").call_type, CallType::Loop));
+        assert!(matches!(call(&calls, "render").call_type, CallType::Direct));
+        assert_eq!(call(&calls, "render").arg_count, 2);
+
+        let blocks: Vec<_> = calls.iter().filter(|call| call.callee == "with").collect();
+        assert_eq!(blocks[0].receiver.as_deref(), Some("self._lock"));
+        assert!(matches!(blocks[0].call_type, CallType::Direct));
+        assert_eq!(blocks[1].receiver.as_deref(), Some("self.pool.acquire()"));
+        assert!(matches!(blocks[1].call_type, CallType::Async));
+    }
+
+    #[test]
+    fn test_javascript_calls() {
+        let source = r#"
+class OrderView {
+    async load(id) {
+        const order = await api.fetchOrder(id);
+        const service = new OrderService(api, id);
+        items.forEach(renderItem);
+        button.addEventListener("click", this.onClick);
+        return fetch(`/orders/${id}`)
+            .then(response => response.json())
+            .catch(reportError);
+    }
+}
+
+const renderItem = (item) => format(item);
+"#;
+        let mut parser = JavaScriptParser::new().unwrap();
+        let calls = parser.extract_calls(source).unwrap();
+
+        let fetch_order = call(&calls, "fetchOrder");
+        assert!(matches!(fetch_order.call_type, CallType::Async));
+        assert_eq!(fetch_order.receiver.as_deref(), Some("api"));
+
+        let constructor = call(&calls, "constructor");
+        assert_eq!(constructor.receiver.as_deref(), Some("OrderService"));
+        assert_eq!(constructor.arg_count, 2);
+
+        assert!(matches!(call(&calls, "renderItem").call_type, CallType::Callback));
+        assert_eq!(call(&calls, "onClick").receiver.as_deref(), Some("this"));
+        assert!(matches!(call(&calls, "json").call_type, CallType::Callback));
+        assert!(matches!(call(&calls, "reportError").call_type, CallType::Callback));
+        assert!(matches!(call(&calls, "then").call_type, CallType::Direct));
+        assert!(matches!(call(&calls, "format").call_type, CallType::Direct));
+    }
+
+    #[test]
+    fn test_java_calls() {
+        let source = r#"
+public class OrderService {
+    public Order place(String item) {
+        Order order = new Order(item, 1);
+        if (validator.isValid(order)) {
+            repository.save(order);
+        }
+        for (Line line : order.getLines()) {
+            audit(line);
+        }
+        try {
+            Pricing.apply(order);
+        } catch (PricingException e) {
+            log.warn("pricing failed");
+        }
+        lines.forEach(l -> notify(l));
+        synchronized (this.pending) {
+            pending.add(order);
+        }
+        return order;
+    }
+}
+"#;
+        let mut parser = JavaParser::new().unwrap();
+        let calls = parser.extract_calls(source).unwrap();
+
+        let constructor = call(&calls, "Order");
+        assert_eq!(constructor.receiver.as_deref(), Some("Order"));
+        assert_eq!(constructor.arg_count, 2);
+        assert_eq!(constructor.line, 4);
+
+        assert!(matches!(call(&calls, "isValid").call_type, CallType::Direct));
+        assert_eq!(call(&calls, "isValid").receiver.as_deref(), Some("validator"));
+        assert!(matches!(call(&calls, "save").call_type, CallType::Conditional));
+        assert!(matches!(call(&calls, "getLines").call_type, CallType::Direct));
+        assert!(matches!(call(&calls, "audit").call_type, CallType::Loop));
+        assert!(call(&calls, "audit").receiver.is_none());
+
+        let apply = call(&calls, "apply");
+        assert!(matches!(apply.call_type, CallType::Try));
+        assert_eq!(apply.receiver.as_deref(), Some("Pricing"));
+
+        assert!(matches!(call(&calls, "notify").call_type, CallType::Callback));
+        assert_eq!(call(&calls, "synchronized").receiver.as_deref(), Some("this.pending"));
+        assert_eq!(call(&calls, "synchronized").line, 17);
+        assert_eq!(call(&calls, "add").receiver.as_deref(), Some("pending"));
+    }
+
+    #[test]
+    fn test_csharp_calls() {
+        let source = r#"
+public class OrderService
+{
+    public OrderService(IRepository repository)
+    {
+        repository.Changed += OnChanged;
+    }
+
+    public async Task<Order> PlaceAsync(string item)
+    {
+        var order = new Order(item);
+        await _repository.SaveAsync(order);
+        var total = Pricing.Calculate<decimal>(order, 2);
+        _items.Where(i => IsOpen(i)).ToList();
+        lock (_sync) { _pending.Add(order); }
+        return order?.Validate();
+    }
+}
+"#;
+        let mut parser = CSharpParser::new().unwrap();
+        let calls = parser.extract_calls(source).unwrap();
+
+        assert!(matches!(call(&calls, "OnChanged").call_type, CallType::Callback));
+        assert!(call(&calls, "OnChanged").is_reference);
+        assert_eq!(call(&calls, "Order").receiver.as_deref(), Some("Order"));
+        assert!(matches!(call(&calls, "SaveAsync").call_type, CallType::Async));
+
+        let calculate = call(&calls, "Calculate");
+        assert_eq!(calculate.receiver.as_deref(), Some("Pricing"));
+        assert_eq!(calculate.arg_count, 2);
+
+        assert!(matches!(call(&calls, "IsOpen").call_type, CallType::Callback));
+        assert!(matches!(call(&calls, "ToList").call_type, CallType::Direct));
+        assert!(call(&calls, "Validate").receiver.is_none());
+        assert_eq!(call(&calls, "lock").receiver.as_deref(), Some("_sync"));
+    }
+}
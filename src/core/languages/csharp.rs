@@ -2,7 +2,34 @@ use std::path::Path;
 use tree_sitter::{Language, Parser, Node};
 
 use crate::error::{CodesworthError, Result};
-use super::{LanguageParser, ParsedModule};
+use super::{LanguageParser, ParsedModule, ParsedCall};
+use super::calls::CallSyntax;
+
+const CSHARP_CALLS: CallSyntax = CallSyntax {
+    call_kinds: &[("invocation_expression", Some("function"))],
+    constructor_kinds: &[("object_creation_expression", "type")],
+    constructor_name: None,
+    lock_kinds: &[("lock_statement", "lock")],
+    arguments_field: "arguments",
+    member_kinds: &[
+        ("member_access_expression", "expression", "name"),
+        ("member_binding_expression", "", "name"),
+        ("qualified_name", "qualifier", "name"),
+    ],
+    wrapper_kinds: &[("conditional_access_expression", "")],
+    await_kinds: &["await_expression"],
+    try_operator_kinds: &[],
+    conditional_kinds: &["if_statement", "switch_statement", "switch_expression", "conditional_expression"],
+    loop_kinds: &["for_statement", "foreach_statement", "while_statement", "do_statement"],
+    try_kinds: &["try_statement"],
+    callback_kinds: &["lambda_expression", "anonymous_method_expression"],
+    definition_parents: &[],
+    function_references: true,
+    subscription_kinds: &["assignment_expression"],
+    function_kinds: &["method_declaration", "constructor_declaration", "local_function_statement"],
+    header_fields: &["condition", "value", "right"],
+    token_tree_kinds: &[],
+};
 
 /// C#-specific parser using Tree-sitter
 pub struct CSharpParser {
@@ -38,6 +65,13 @@ impl LanguageParser for CSharpParser {
         }
     }
 
+    fn extract_calls(&mut self, content: &str) -> Result<Vec<ParsedCall>> {
+        let tree = self.parser.parse(content, None)
+            .ok_or_else(|| CodesworthError::Parser("Failed to parse C# code".to_string()))?;
+
+        Ok(CSHARP_CALLS.extract_calls(tree.root_node(), content))
+    }
+
     fn extract_file_docs(&self, content: &str) -> Option<String> {
         let lines: Vec<&str> = content.lines().collect();
         let mut doc_lines = Vec::new();
@@ -149,8 +183,8 @@ impl CSharpParser {
         }
 
         // Check for modifiers (public, private, protected, etc.)
-        if let Some(modifiers_node) = node.child_by_field_name("modifiers") {
-            visibility = self.parse_csharp_visibility(modifiers_node, source);
+        if let Some(modifiers) = self.modifiers_text(node, source) {
+            visibility = self.parse_csharp_visibility(&modifiers);
         }
 
         // Look for XML documentation comments
@@ -161,7 +195,7 @@ impl CSharpParser {
         if let Some(body_node) = node.child_by_field_name("body") {
             let mut cursor = body_node.walk();
             for child in body_node.children(&mut cursor) {
-                if child.kind() == "method_declaration" {
+                if matches!(child.kind(), "method_declaration" | "constructor_declaration") {
                     if let Some(method) = self.parse_csharp_method(child, source)? {
                         methods.push(method);
                     }
@@ -197,8 +231,8 @@ impl CSharpParser {
             name = Some(self.node_text(name_node, source));
         }
 
-        if let Some(modifiers_node) = node.child_by_field_name("modifiers") {
-            visibility = self.parse_csharp_visibility(modifiers_node, source);
+        if let Some(modifiers) = self.modifiers_text(node, source) {
+            visibility = self.parse_csharp_visibility(&modifiers);
         }
 
         docs = self.extract_docs_before_node(node, source);
@@ -231,8 +265,8 @@ impl CSharpParser {
             name = Some(self.node_text(name_node, source));
         }
 
-        if let Some(modifiers_node) = node.child_by_field_name("modifiers") {
-            visibility = self.parse_csharp_visibility(modifiers_node, source);
+        if let Some(modifiers) = self.modifiers_text(node, source) {
+            visibility = self.parse_csharp_visibility(&modifiers);
         }
 
         docs = self.extract_docs_before_node(node, source);
@@ -265,8 +299,8 @@ impl CSharpParser {
             name = Some(self.node_text(name_node, source));
         }
 
-        if let Some(modifiers_node) = node.child_by_field_name("modifiers") {
-            visibility = self.parse_csharp_visibility(modifiers_node, source);
+        if let Some(modifiers) = self.modifiers_text(node, source) {
+            visibility = self.parse_csharp_visibility(&modifiers);
         }
 
         docs = self.extract_docs_before_node(node, source);
@@ -299,8 +333,8 @@ impl CSharpParser {
             name = Some(self.node_text(name_node, source));
         }
 
-        if let Some(modifiers_node) = node.child_by_field_name("modifiers") {
-            visibility = self.parse_csharp_visibility(modifiers_node, source);
+        if let Some(modifiers) = self.modifiers_text(node, source) {
+            visibility = self.parse_csharp_visibility(&modifiers);
         }
 
         docs = self.extract_docs_before_node(node, source);
@@ -335,6 +369,11 @@ impl CSharpParser {
 
         docs = self.extract_docs_before_node(node, source);
 
+        let mut children = Vec::new();
+        if let Some(body_node) = node.child_by_field_name("body") {
+            self.extract_csharp_items(body_node, source, &mut children)?;
+        }
+
         if let Some(namespace_name) = name {
             Ok(Some(ParsedModule {
                 name: namespace_name,
@@ -346,16 +385,26 @@ impl CSharpParser {
                     node.start_position().row + 1,
                     node.end_position().row + 1
                 ),
-                children: vec![], // TODO: Parse namespace contents
+                children,
             }))
         } else {
             Ok(None)
         }
     }
 
+    /// Text of a declaration's `modifier` children (`public static async`), if any
+    fn modifiers_text(&self, node: Node, source: &str) -> Option<String> {
+        let mut cursor = node.walk();
+        let modifiers: Vec<String> = node.children(&mut cursor)
+            .filter(|child| child.kind() == "modifier")
+            .map(|child| self.node_text(child, source))
+            .collect();
+
+        if modifiers.is_empty() { None } else { Some(modifiers.join(" ")) }
+    }
+
     /// Parse C# visibility modifiers
-    fn parse_csharp_visibility(&self, node: Node, source: &str) -> String {
-        let modifiers_text = self.node_text(node, source);
+    fn parse_csharp_visibility(&self, modifiers_text: &str) -> String {
         if modifiers_text.contains("public") {
             "public".to_string()
         } else if modifiers_text.contains("private") {
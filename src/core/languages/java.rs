@@ -3,7 +3,30 @@ use std::path::Path;
 use tree_sitter::{Language, Parser, Node};
 
 use crate::error::{CodesworthError, Result};
-use super::{LanguageParser, ParsedModule};
+use super::{LanguageParser, ParsedModule, ParsedCall};
+use super::calls::CallSyntax;
+
+const JAVA_CALLS: CallSyntax = CallSyntax {
+    call_kinds: &[("method_invocation", None)],
+    constructor_kinds: &[("object_creation_expression", "type")],
+    constructor_name: None,
+    lock_kinds: &[("synchronized_statement", "synchronized")],
+    arguments_field: "arguments",
+    member_kinds: &[("method_invocation", "object", "name")],
+    wrapper_kinds: &[],
+    await_kinds: &[],
+    try_operator_kinds: &[],
+    conditional_kinds: &["if_statement", "switch_expression", "ternary_expression"],
+    loop_kinds: &["for_statement", "enhanced_for_statement", "while_statement", "do_statement"],
+    try_kinds: &["try_statement", "try_with_resources_statement"],
+    callback_kinds: &["lambda_expression"],
+    definition_parents: &[],
+    function_references: false,
+    subscription_kinds: &[],
+    function_kinds: &["method_declaration", "constructor_declaration"],
+    header_fields: &["condition", "value"],
+    token_tree_kinds: &[],
+};
 
 /// Java-specific parser using Tree-sitter with enhanced call detection
 pub struct JavaParser {
@@ -39,6 +62,13 @@ impl LanguageParser for JavaParser {
         }
     }
 
+    fn extract_calls(&mut self, content: &str) -> Result<Vec<ParsedCall>> {
+        let tree = self.parser.parse(content, None)
+            .ok_or_else(|| CodesworthError::Parser("Failed to parse Java code".to_string()))?;
+
+        Ok(JAVA_CALLS.extract_calls(tree.root_node(), content))
+    }
+
     fn extract_file_docs(&self, content: &str) -> Option<String> {
         let lines: Vec<&str> = content.lines().collect();
         let mut doc_lines = Vec::new();
@@ -141,7 +171,7 @@ impl JavaParser {
         }
 
         // Check for modifiers (public, private, protected, etc.)
-        if let Some(modifiers_node) = node.child_by_field_name("modifiers") {
+        if let Some(modifiers_node) = self.find_child_by_kind(node, "modifiers") {
             visibility = self.parse_java_visibility(modifiers_node, source);
         }
 
@@ -190,7 +220,7 @@ impl JavaParser {
             name = Some(self.node_text(name_node, source));
         }
 
-        if let Some(modifiers_node) = node.child_by_field_name("modifiers") {
+        if let Some(modifiers_node) = self.find_child_by_kind(node, "modifiers") {
             visibility = self.parse_java_visibility(modifiers_node, source);
         }
 
@@ -224,7 +254,7 @@ impl JavaParser {
             name = Some(self.node_text(name_node, source));
         }
 
-        if let Some(modifiers_node) = node.child_by_field_name("modifiers") {
+        if let Some(modifiers_node) = self.find_child_by_kind(node, "modifiers") {
             visibility = self.parse_java_visibility(modifiers_node, source);
         }
 
@@ -258,7 +288,7 @@ impl JavaParser {
             name = Some(self.node_text(name_node, source));
         }
 
-        if let Some(modifiers_node) = node.child_by_field_name("modifiers") {
+        if let Some(modifiers_node) = self.find_child_by_kind(node, "modifiers") {
             visibility = self.parse_java_visibility(modifiers_node, source);
         }
 
@@ -2,7 +2,30 @@ use std::path::Path;
 use tree_sitter::{Language, Parser, Node};
 
 use crate::error::{CodesworthError, Result};
-use super::{LanguageParser, ParsedModule};
+use super::{LanguageParser, ParsedModule, ParsedCall};
+use super::calls::CallSyntax;
+
+const JAVASCRIPT_CALLS: CallSyntax = CallSyntax {
+    call_kinds: &[("call_expression", Some("function"))],
+    constructor_kinds: &[("new_expression", "constructor")],
+    constructor_name: Some("constructor"),
+    lock_kinds: &[],
+    arguments_field: "arguments",
+    member_kinds: &[("member_expression", "object", "property")],
+    wrapper_kinds: &[],
+    await_kinds: &["await_expression"],
+    try_operator_kinds: &[],
+    conditional_kinds: &["if_statement", "switch_statement", "ternary_expression"],
+    loop_kinds: &["for_statement", "for_in_statement", "while_statement", "do_statement"],
+    try_kinds: &["try_statement"],
+    callback_kinds: &["arrow_function", "function_expression"],
+    definition_parents: &["variable_declarator", "field_definition"],
+    function_references: true,
+    subscription_kinds: &[],
+    function_kinds: &["function_declaration", "generator_function_declaration", "method_definition"],
+    header_fields: &["condition", "value", "right"],
+    token_tree_kinds: &[],
+};
 
 /// JavaScript/TypeScript-specific parser using Tree-sitter
 pub struct JavaScriptParser {
@@ -38,6 +61,13 @@ impl LanguageParser for JavaScriptParser {
         }
     }
 
+    fn extract_calls(&mut self, content: &str) -> Result<Vec<ParsedCall>> {
+        let tree = self.parser.parse(content, None)
+            .ok_or_else(|| CodesworthError::Parser("Failed to parse JavaScript code".to_string()))?;
+
+        Ok(JAVASCRIPT_CALLS.extract_calls(tree.root_node(), content))
+    }
+
     fn extract_file_docs(&self, content: &str) -> Option<String> {
         let lines: Vec<&str> = content.lines().collect();
         let mut doc_lines = Vec::new();
@@ -118,7 +148,7 @@ impl JavaScriptParser {
                         modules.push(parsed_arrow);
                     }
                 }
-                "variable_declaration" => {
+                "variable_declaration" | "lexical_declaration" => {
                     // Check if this is a function assignment
                     if let Some(parsed_var) = self.parse_javascript_variable(child, source)? {
                         modules.push(parsed_var);
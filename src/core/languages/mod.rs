@@ -8,6 +8,7 @@ mod java;
 mod python;
 mod csharp;
 mod javascript;
+mod calls;
 
 pub use rust::RustParser;
 pub use java::JavaParser;
@@ -16,7 +17,7 @@ pub use csharp::CSharpParser;
 pub use javascript::JavaScriptParser;
 
 use crate::error::Result;
-use super::{ParsedModule, ParsedFile};
+use super::{ParsedModule, ParsedCall};
 
 /// Trait that all language parsers must implement
 pub trait LanguageParser {
@@ -26,6 +27,11 @@ pub trait LanguageParser {
     /// Extract file-level documentation from source code
     fn extract_file_docs(&self, content: &str) -> Option<String>;
 
+    /// Extract call sites from source code
+    fn extract_calls(&mut self, _content: &str) -> Result<Vec<ParsedCall>> {
+        Ok(Vec::new())
+    }
+
     /// Get the file extensions this parser handles
     fn file_extensions(&self) -> &[&str];
 
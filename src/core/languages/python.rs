@@ -2,7 +2,33 @@ use std::path::Path;
 use tree_sitter::{Language, Parser, Node};
 
 use crate::error::{CodesworthError, Result};
-use super::{LanguageParser, ParsedModule};
+use super::{LanguageParser, ParsedModule, ParsedCall};
+use super::calls::CallSyntax;
+
+const PYTHON_CALLS: CallSyntax = CallSyntax {
+    call_kinds: &[("call", Some("function"))],
+    constructor_kinds: &[],
+    constructor_name: None,
+    lock_kinds: &[("with_statement", "with")],
+    arguments_field: "arguments",
+    member_kinds: &[("attribute", "object", "attribute")],
+    wrapper_kinds: &[],
+    await_kinds: &["await"],
+    try_operator_kinds: &[],
+    conditional_kinds: &["if_statement", "match_statement", "conditional_expression"],
+    loop_kinds: &[
+        "for_statement", "while_statement", "list_comprehension",
+        "set_comprehension", "dictionary_comprehension", "generator_expression",
+    ],
+    try_kinds: &["try_statement"],
+    callback_kinds: &["lambda"],
+    definition_parents: &[],
+    function_references: false,
+    subscription_kinds: &[],
+    function_kinds: &["function_definition"],
+    header_fields: &["condition", "subject", "right"],
+    token_tree_kinds: &[],
+};
 
 /// Python-specific parser using Tree-sitter
 pub struct PythonParser {
@@ -38,6 +64,13 @@ impl LanguageParser for PythonParser {
         }
     }
 
+    fn extract_calls(&mut self, content: &str) -> Result<Vec<ParsedCall>> {
+        let tree = self.parser.parse(content, None)
+            .ok_or_else(|| CodesworthError::Parser("Failed to parse Python code".to_string()))?;
+
+        Ok(PYTHON_CALLS.extract_calls(tree.root_node(), content))
+    }
+
     fn extract_file_docs(&self, content: &str) -> Option<String> {
         let lines: Vec<&str> = content.lines().collect();
         let mut doc_lines = Vec::new();
@@ -2,7 +2,30 @@ use std::path::Path;
 use tree_sitter::{Language, Parser, Node};
 
 use crate::error::{CodesworthError, Result};
-use super::{LanguageParser, ParsedModule};
+use super::{LanguageParser, ParsedModule, ParsedCall};
+use super::calls::CallSyntax;
+
+const RUST_CALLS: CallSyntax = CallSyntax {
+    call_kinds: &[("call_expression", Some("function"))],
+    constructor_kinds: &[],
+    constructor_name: None,
+    lock_kinds: &[],
+    arguments_field: "arguments",
+    member_kinds: &[("field_expression", "value", "field"), ("scoped_identifier", "path", "name")],
+    wrapper_kinds: &[("generic_function", "function")],
+    await_kinds: &["await_expression"],
+    try_operator_kinds: &["try_expression"],
+    conditional_kinds: &["if_expression", "match_expression"],
+    loop_kinds: &["for_expression", "while_expression", "loop_expression"],
+    try_kinds: &["try_block"],
+    callback_kinds: &["closure_expression"],
+    definition_parents: &[],
+    function_references: false,
+    subscription_kinds: &[],
+    function_kinds: &["function_item"],
+    header_fields: &["condition", "value"],
+    token_tree_kinds: &["token_tree"],
+};
 
 /// Rust-specific parser using Tree-sitter
 pub struct RustParser {
@@ -38,6 +61,13 @@ impl LanguageParser for RustParser {
         }
     }
 
+    fn extract_calls(&mut self, content: &str) -> Result<Vec<ParsedCall>> {
+        let tree = self.parser.parse(content, None)
+            .ok_or_else(|| CodesworthError::Parser("Failed to parse Rust code".to_string()))?;
+
+        Ok(RUST_CALLS.extract_calls(tree.root_node(), content))
+    }
+
     fn extract_file_docs(&self, content: &str) -> Option<String> {
         let lines: Vec<&str> = content.lines().collect();
         let mut doc_lines = Vec::new();
@@ -264,8 +294,11 @@ impl RustParser {
             }
         }
 
-        // Create a concise impl signature
-        let impl_signature = format!("impl {}", type_name);
+        // Create a concise impl signature, keeping the trait for the type hierarchy
+        let impl_signature = match node.child_by_field_name("trait") {
+            Some(trait_node) => format!("impl {} for {}", self.node_text(trait_node, source), type_name),
+            None => format!("impl {}", type_name),
+        };
 
         Ok(Some(ParsedModule {
             name: format!("impl {}", type_name),
@@ -297,18 +330,24 @@ impl RustParser {
 
         docs = self.extract_docs_before_node(node, source);
 
+        // Inline modules (e.g. `#[cfg(test)] mod tests { ... }`) carry their own items
+        let mut children = Vec::new();
+        if let Some(body_node) = node.child_by_field_name("body") {
+            self.extract_rust_items(body_node, source, &mut children)?;
+        }
+
         if let Some(mod_name) = name {
             Ok(Some(ParsedModule {
                 name: mod_name,
                 item_type: "module".to_string(),
                 visibility,
                 docs,
-                signature: Some(self.node_text(node, source)),
+                signature: Some(self.extract_function_signature(node, source)),
                 line_range: (
                     node.start_position().row + 1,
                     node.end_position().row + 1
                 ),
-                children: vec![], // TODO: Parse module contents
+                children,
             }))
         } else {
             Ok(None)
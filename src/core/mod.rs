@@ -6,6 +6,7 @@ mod differ;
 mod protector;
 mod validator;
 mod llm;
+mod git_tree;
 
 // Call graph analysis (replacing package-level analysis)
 mod call_graph;
@@ -21,11 +22,12 @@ mod batch_processor;
 mod hierarchical_analyzer;
 mod system_overview_generator;
 
-pub use parser::{CodeParser, ParsedFile, ParsedModule};
-pub use generator::{DocGenerator, GeneratedDoc};
-pub use differ::{ContentDiffer, ContentDiff};
-pub use protector::{EditProtector, ProtectedRegion};
-pub use validator::{DocValidator, ValidationResult};
+pub use parser::{CodeParser, ParsedFile, ParsedModule, ParsedCall};
+pub use generator::DocGenerator;
+pub use differ::ContentDiffer;
+pub use protector::EditProtector;
+pub use validator::DocValidator;
+pub use git_tree::GitTree;
 pub use llm::{
     LlmDocumenter, DocumentationContext, EnhancementRequest, EnhancementResponse,
     EnhancementType, ProjectInfo, ArchitectureDocs, ArchitectureDetector
@@ -36,7 +38,7 @@ pub use package_analysis::*;
 pub use package_analyzer::PackageAnalyzer;
 pub use context_scanner::ContextScanner;
 pub use batch_processor::{
-    BatchProcessor, BatchDocumentationRequest, BatchDocumentationResponse,
+    BatchProcessor, BatchDocumentationRequest,
     HumanContext, SystemContext, AnalysisFocus, FocusArea, DepthLevel, TargetAudience
 };
 pub use hierarchical_analyzer::HierarchicalAnalyzer;
@@ -44,12 +46,15 @@ pub use system_overview_generator::SystemOverviewGenerator;
 
 // New call graph exports
 pub use call_graph::{
-    CallGraph, CallNode, CallEdge, MethodSignature, CallType,
-    EntryPointDetector, EntryPoint, EntryPointType,
-    CallChainTracer, CallChain, CallStep,
+    CallGraph, CallType,
+    EntryPointDetector,
+    CallChainTracer,
     CallChainGrouper, CallChainGroup,
-    CallChainAnalyzer, GroupAnalysis, VisitedSet,
-    CallChainEngine, CallChainAnalysisResult
+    GroupAnalysis,
+    CallChainEngine, CallChainAnalysisResult,
+    GraphExport, GraphFilter, ExportFormat, Granularity,
+    GraphQuery, GraphQueryRequest, GraphDiff, GraphSnapshot,
+    TestCoverageAnalyzer, DeadCodeAnalyzer, AnalysisStore, describe_effects
 };
 
 // Export the main engine
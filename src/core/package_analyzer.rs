@@ -9,6 +9,7 @@ use super::{
     ParsedFile, ParsedModule, CodeParser,
     package_analysis::*
 };
+use super::call_graph::locks_held_across_await;
 
 /// Analyzes and groups files into logical packages for documentation
 pub struct PackageAnalyzer {
@@ -618,13 +619,13 @@ impl PackageAnalyzer {
             let content = &file.source_content;
 
             // Concurrency gotchas
-            if content.contains("Arc<Mutex<") && content.contains("await") {
+            for line in locks_held_across_await(file) {
                 gotchas.push(GotchaIndicator {
                     category: GotchaCategory::Concurrency,
-                    description: "Potential deadlock: Arc<Mutex<>> with async code".to_string(),
+                    description: format!("Blocking lock held across an await (line {}): can stall the executor or deadlock", line),
                     severity: GotchaSeverity::Warning,
                     file_path: Some(file.path.clone()),
-                    suggestion: Some("Consider using async-aware synchronization primitives".to_string()),
+                    suggestion: Some("Release the guard before awaiting, or use an async-aware lock".to_string()),
                 });
             }
 
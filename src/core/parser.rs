@@ -1,5 +1,5 @@
 use std::path::{Path, PathBuf};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use serde::{Deserialize, Serialize};
 use sha2::{Sha256, Digest};
 use walkdir::WalkDir;
@@ -7,6 +7,7 @@ use ignore::WalkBuilder;
 
 use crate::config::ParsingConfig;
 use crate::error::{CodesworthError, Result};
+use super::call_graph::CallType;
 use super::languages::{LanguageParser, RustParser, JavaParser, PythonParser, CSharpParser, JavaScriptParser};
 
 /// Represents a parsed source file with extracted metadata
@@ -32,6 +33,10 @@ pub struct ParsedFile {
 
     /// Raw source content (for template context)
     pub source_content: String,
+
+    /// Call sites extracted from the syntax tree
+    #[serde(default)]
+    pub calls: Vec<ParsedCall>,
 }
 
 /// Represents a parsed module, function, struct, etc.
@@ -59,6 +64,29 @@ pub struct ParsedModule {
     pub children: Vec<ParsedModule>,
 }
 
+/// A call site found in the syntax tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedCall {
+    /// Name of the called function or method
+    pub callee: String,
+
+    /// Receiver expression (`self`, `client`, `Store`), if any
+    pub receiver: Option<String>,
+
+    /// Number of arguments passed
+    pub arg_count: usize,
+
+    /// Line of the callee name
+    pub line: usize,
+
+    /// Context of the call (await, conditional, loop, try, callback)
+    pub call_type: CallType,
+
+    /// Function passed by name (callback, event handler) rather than invoked here
+    #[serde(default)]
+    pub is_reference: bool,
+}
+
 /// Multi-language code parser that delegates to language-specific parsers
 pub struct CodeParser {
     config: ParsingConfig,
@@ -109,6 +137,44 @@ impl CodeParser {
     pub async fn parse_directory<P: AsRef<Path>>(&mut self, dir: P) -> Result<Vec<ParsedFile>> {
         let mut parsed_files = Vec::new();
 
+        for path in self.source_files(dir.as_ref())? {
+            if let Ok(parsed) = self.parse_file(&path).await {
+                parsed_files.push(parsed);
+            }
+        }
+
+        Ok(parsed_files)
+    }
+
+    /// Content hashes of the files in several directories, keyed by path
+    pub fn hash_directories(&self, dirs: &[PathBuf]) -> Result<BTreeMap<PathBuf, String>> {
+        let mut hashes = BTreeMap::new();
+        for dir in dirs {
+            hashes.extend(self.hash_directory(dir)?);
+        }
+        Ok(hashes)
+    }
+
+    /// Content hashes of the files `parse_directory` would parse, without parsing them
+    pub fn hash_directory<P: AsRef<Path>>(&self, dir: P) -> Result<BTreeMap<PathBuf, String>> {
+        let mut hashes = BTreeMap::new();
+
+        for path in self.source_files(dir.as_ref())? {
+            let Ok(source_content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            if source_content.len() <= self.config.max_file_size {
+                hashes.insert(path, self.calculate_hash(&source_content));
+            }
+        }
+
+        Ok(hashes)
+    }
+
+    /// Files in a directory that a language parser handles
+    fn source_files(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+
         // Use ignore crate to respect .gitignore and custom patterns
         let walker = WalkBuilder::new(dir)
             .hidden(false)
@@ -120,13 +186,11 @@ impl CodeParser {
             let path = entry.path();
 
             if path.is_file() && self.should_parse_file(path)? {
-                if let Ok(parsed) = self.parse_file(path).await {
-                    parsed_files.push(parsed);
-                }
+                files.push(path.to_path_buf());
             }
         }
 
-        Ok(parsed_files)
+        Ok(files)
     }
 
     /// Parse a single source file
@@ -177,6 +241,13 @@ impl CodeParser {
             None
         };
 
+        // Extract call sites
+        let calls = if let Some(parser) = self.language_parsers.get_mut(&language) {
+            parser.extract_calls(&source_content)?
+        } else {
+            Vec::new()
+        };
+
         Ok(ParsedFile {
             path: path.to_path_buf(),
             language,
@@ -185,6 +256,7 @@ impl CodeParser {
             modules,
             file_docs,
             source_content,
+            calls,
         })
     }
 
@@ -1,5 +1,7 @@
 use crate::error::Result;
 use crate::config::Config;
+use super::call_graph::{DeadCodeConfidence, DeadCodeReport, RecursiveCluster};
+use std::collections::HashSet;
 use std::path::Path;
 
 #[derive(Debug, Clone)]
@@ -45,4 +47,42 @@ impl DocValidator {
             warnings: vec![],
         })
     }
-}
\ No newline at end of file
+
+    /// Warn about recursive clusters that span packages and were not present in the
+    /// previously generated `call_graph.json`
+    pub fn check_recursive_clusters(&self, previous_graph: &Path, current: &[RecursiveCluster]) -> Result<Vec<String>> {
+        if !previous_graph.exists() {
+            return Ok(vec![]);
+        }
+
+        let data: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(previous_graph)?)?;
+        let previous: Vec<RecursiveCluster> = data.get("recursive_clusters")
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()?
+            .unwrap_or_default();
+        let known: HashSet<String> = previous.iter().map(|cluster| cluster.key()).collect();
+
+        Ok(current.iter()
+            .filter(|cluster| cluster.crosses_packages() && !known.contains(&cluster.key()))
+            .map(|cluster| {
+                let names: Vec<String> = cluster.members.iter().map(|member| member.display_name()).collect();
+                let packages: Vec<String> = cluster.packages().into_iter().collect();
+                format!("New cycle across packages {}: {}", packages.join(", "), names.join(" -> "))
+            })
+            .collect())
+    }
+
+    /// Warn about functions that are unused with high confidence
+    pub fn check_dead_code(&self, report: &DeadCodeReport) -> Vec<String> {
+        report.at_least(DeadCodeConfidence::High)
+            .map(|finding| format!(
+                "Unused function {} ({}:{}): {}",
+                finding.method.display_name(),
+                finding.method.file_path.display(),
+                finding.line,
+                finding.reasons.join("; ")
+            ))
+            .collect()
+    }
+}
@@ -32,6 +32,15 @@ pub enum CodesworthError {
 
     #[error("Protected region parse error: {0}")]
     ProtectedRegion(String),
+
+    #[error("Graph query error: {0}")]
+    Query(String),
+
+    #[error("Git error: {0}")]
+    Git(String),
+
+    #[error("Analysis store error: {0}")]
+    Store(String),
 }
 
 pub type Result<T> = std::result::Result<T, CodesworthError>;
\ No newline at end of file
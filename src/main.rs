@@ -17,6 +17,7 @@ async fn main() -> Result<()> {
     tracing_subscriber::fmt()
         .with_max_level(Level::INFO)
         .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
         .init();
 
     let cli = Cli::parse();